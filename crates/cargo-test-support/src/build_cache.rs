@@ -0,0 +1,158 @@
+//! A remote build cache for testing `build.cache-url`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use cargo_test_support::build_cache::BuildCacheServer;
+//! use cargo_test_support::project;
+//!
+//! let server = BuildCacheServer::new();
+//! let p = project().build();
+//! p.cargo("build -Zbuild-cache")
+//!     .masquerade_as_nightly_cargo(&["build-cache"])
+//!     .env("CARGO_BUILD_CACHE_URL", server.url())
+//!     .run();
+//! assert!(server.entries().is_empty());
+//! ```
+
+use crate::paths;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// An HTTP server storing the files `PUT` to it in a directory, and serving
+/// them back with `GET`.
+pub struct BuildCacheServer {
+    addr: SocketAddr,
+    dir: PathBuf,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl BuildCacheServer {
+    /// Starts a server backed by `<test root>/remote-build-cache`.
+    pub fn new() -> BuildCacheServer {
+        let dir = paths::root().join("remote-build-cache");
+        fs::create_dir_all(&dir).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_dir = dir.clone();
+        let server_requests = requests.clone();
+        thread::spawn(move || serve(listener, &server_dir, &server_requests));
+        BuildCacheServer {
+            addr,
+            dir,
+            requests,
+        }
+    }
+
+    /// The URL to use as `build.cache-url`.
+    pub fn url(&self) -> String {
+        format!("http://{}/cache", self.addr)
+    }
+
+    /// The directory holding the files uploaded to the server.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The keys of the entries on the server, sorted.
+    pub fn entries(&self) -> Vec<String> {
+        let mut entries: Vec<_> = fs::read_dir(&self.dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Takes the requests received so far, as `METHOD /path` lines.
+    pub fn take_requests(&self) -> Vec<String> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+impl Drop for BuildCacheServer {
+    fn drop(&mut self) {
+        if let Ok(mut stream) = TcpStream::connect(self.addr) {
+            // shutdown the server
+            let _ = stream.write_all(b"stop");
+            let _ = stream.flush();
+        }
+    }
+}
+
+fn serve(listener: TcpListener, dir: &Path, requests: &Mutex<Vec<String>>) {
+    let mut line = String::new();
+    'server: loop {
+        let (socket, _) = listener.accept().unwrap();
+        let mut buf = BufReader::new(socket);
+        line.clear();
+        if buf.read_line(&mut line).unwrap() == 0 {
+            // Connection terminated.
+            continue;
+        }
+        // Read the "GET path HTTP/1.1" line.
+        let mut parts = line.split_ascii_whitespace();
+        let method = parts.next().unwrap().to_string();
+        if method == "stop" {
+            // Shutdown the server.
+            return;
+        }
+        let path = parts.next().unwrap().to_string();
+
+        let mut content_len = None;
+        let mut expect_continue = false;
+        loop {
+            line.clear();
+            if buf.read_line(&mut line).unwrap() == 0 {
+                continue 'server;
+            }
+            if line == "\r\n" {
+                // End of headers.
+                break;
+            }
+            let (name, value) = line.split_once(':').unwrap();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_len = Some(value.trim().parse::<usize>().unwrap()),
+                "expect" => expect_continue = value.trim() == "100-continue",
+                _ => {}
+            }
+        }
+        if expect_continue {
+            let socket = buf.get_mut();
+            socket.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").unwrap();
+            socket.flush().unwrap();
+        }
+        let mut body = vec![0u8; content_len.unwrap_or(0)];
+        buf.read_exact(&mut body).unwrap();
+
+        requests.lock().unwrap().push(format!("{method} {path}"));
+        let file = path
+            .strip_prefix("/cache/")
+            .filter(|p| !p.split('/').any(|c| c == ".." || c.is_empty()))
+            .map(|p| dir.join(p));
+        let (code, body) = match (method.as_str(), file) {
+            ("GET", Some(file)) => match fs::read(&file) {
+                Ok(contents) => (200, contents),
+                Err(_) => (404, Vec::new()),
+            },
+            ("PUT", Some(file)) => {
+                fs::create_dir_all(file.parent().unwrap()).unwrap();
+                fs::write(&file, body).unwrap();
+                (201, Vec::new())
+            }
+            _ => (400, Vec::new()),
+        };
+        let socket = buf.get_mut();
+        write!(socket, "HTTP/1.1 {code}\r\n").unwrap();
+        write!(socket, "Content-Length: {}\r\n", body.len()).unwrap();
+        write!(socket, "Connection: close\r\n").unwrap();
+        write!(socket, "\r\n").unwrap();
+        socket.write_all(&body).unwrap();
+        socket.flush().unwrap();
+    }
+}
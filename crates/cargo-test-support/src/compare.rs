@@ -310,6 +310,7 @@ static E2E_LITERAL_REDACTIONS: &[(&str, &str)] = &[
     ("[LOCKING]", "     Locking"),
     ("[UPDATING]", "    Updating"),
     ("[UPGRADING]", "   Upgrading"),
+    ("[BUMPING]", "     Bumping"),
     ("[ADDING]", "      Adding"),
     ("[REMOVING]", "    Removing"),
     ("[REMOVED]", "     Removed"),
@@ -322,6 +323,7 @@ static E2E_LITERAL_REDACTIONS: &[(&str, &str)] = &[
     ("[UPLOADING]", "   Uploading"),
     ("[UPLOADED]", "    Uploaded"),
     ("[VERIFYING]", "   Verifying"),
+    ("[VALIDATING]", "  Validating"),
     ("[ARCHIVING]", "   Archiving"),
     ("[INSTALLING]", "  Installing"),
     ("[REPLACING]", "   Replacing"),
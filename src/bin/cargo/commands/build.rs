@@ -1,6 +1,8 @@
 use crate::command_prelude::*;
 
+use anyhow::Context as _;
 use cargo::ops;
+use cargo::workspace::PackageIdSpec;
 
 pub fn cli() -> Command {
     subcommand("build")
@@ -37,6 +39,7 @@ pub fn cli() -> Command {
         .arg_artifact_dir()
         .arg_unit_graph()
         .arg_timings()
+        .arg_explain_fresh()
         .arg_compile_time_deps()
         .arg_manifest_path()
         .arg_ignore_rust_version()
@@ -65,6 +68,18 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             .fail_if_stable_opt("--artifact-dir", 6790)?;
     }
 
+    if let Some(spec) = args.get_one::<String>("explain-fresh") {
+        gctx.cli_unstable().fail_if_stable_opt_custom_z(
+            "--explain-fresh",
+            15844,
+            "build-analysis",
+            gctx.cli_unstable().build_analysis,
+        )?;
+        let spec = PackageIdSpec::parse(spec)
+            .with_context(|| format!("invalid package ID specification: `{spec}`"))?;
+        compile_opts.build_config.explain_fresh = Some(spec);
+    }
+
     ops::compile(&ws, &compile_opts)?;
     Ok(())
 }
@@ -0,0 +1,58 @@
+use crate::command_prelude::*;
+
+use cargo::ops;
+use cargo::ops::cargo_bump::BumpLevel;
+use cargo::ops::cargo_bump::BumpOptions;
+use cargo::ops::cargo_bump::bump;
+use clap_complete::ArgValueCandidates;
+
+pub fn cli() -> Command {
+    subcommand("bump")
+        .about("Bump the version of packages and the requirements of their dependents (unstable)")
+        .arg(
+            Arg::new("level")
+                .value_name("LEVEL")
+                .required(true)
+                .value_parser(BumpLevel::POSSIBLE_VALUES)
+                .help("Part of the version to bump"),
+        )
+        .arg_dry_run("Don't actually write the manifests")
+        .arg_silent_suggestion()
+        .arg_package_spec_no_all(
+            "Package(s) to bump",
+            "Bump all packages in the workspace",
+            "Don't bump specified packages",
+            ArgValueCandidates::new(get_ws_member_candidates),
+        )
+        .arg_manifest_path()
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    if !gctx.cli_unstable().unstable_options {
+        return Err(anyhow::format_err!(
+            "the `cargo bump` command is unstable, pass `-Z unstable-options` to enable it"
+        )
+        .into());
+    }
+
+    let dry_run = args.dry_run();
+    let ws = args.workspace(gctx)?;
+    let to_bump = args.packages_from_flags()?.get_packages(&ws)?;
+
+    bump(
+        &ws,
+        &BumpOptions {
+            gctx,
+            to_bump,
+            level: args.get_one::<String>("level").unwrap().parse()?,
+            dry_run,
+        },
+    )?;
+
+    if !dry_run {
+        // Reload the workspace to update the lockfile with the new versions.
+        let ws = args.workspace(gctx)?;
+        ops::resolve_ws(&ws, dry_run)?;
+    }
+    Ok(())
+}
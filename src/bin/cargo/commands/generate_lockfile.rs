@@ -19,6 +19,10 @@ pub fn cli() -> Command {
                 .help("Latest publish time allowed for registry packages (unstable)")
                 .help_heading(heading::MANIFEST_OPTIONS)
         )
+        .arg(flag(
+            "merge",
+            "Resolve merge conflicts in the lockfile, keeping the versions of both sides (unstable)",
+        ))
         .after_help(color_print::cstr!(
             "Run `<bright-cyan,bold>cargo help generate-lockfile</>` for more detailed information.\n"
         ))
@@ -53,6 +57,16 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             cargo_util_schemas::index::parse_pubtime(publish_time).map_err(anyhow::Error::from)?;
         ws.set_resolve_publish_time(publish_time);
     }
-    ops::generate_lockfile(&ws)?;
+    if args.flag("merge") {
+        if !gctx.cli_unstable().unstable_options {
+            return Err(anyhow::anyhow!(
+                "the `--merge` flag is unstable, pass `-Z unstable-options` to enable it"
+            )
+            .into());
+        }
+        ops::merge_lockfile(&ws)?;
+    } else {
+        ops::generate_lockfile(&ws)?;
+    }
     Ok(())
 }
@@ -23,6 +23,17 @@ pub fn cli() -> Command {
             "allow-dirty",
             "Allow dirty working directories to be packaged",
         ))
+        .arg(
+            flag(
+                "resume",
+                "Skip the packages already published by a previous run (unstable)",
+            )
+            .conflicts_with("dry-run"),
+        )
+        .arg(flag(
+            "verify-index",
+            "Resolve the packages from a local stand-in for the registry before uploading (unstable)",
+        ))
         .arg_silent_suggestion()
         .arg_package_spec_no_all(
             "Package(s) to publish",
@@ -51,6 +62,20 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
         .into());
     }
 
+    if args.flag("resume") && !gctx.cli_unstable().unstable_options {
+        return Err(anyhow::format_err!(
+            "the `--resume` flag is unstable, pass `-Z unstable-options` to enable it"
+        )
+        .into());
+    }
+
+    if args.flag("verify-index") && !gctx.cli_unstable().unstable_options {
+        return Err(anyhow::format_err!(
+            "the `--verify-index` flag is unstable, pass `-Z unstable-options` to enable it"
+        )
+        .into());
+    }
+
     let token = args.get_one::<String>("token").cloned().map(Secret::from);
     if token.is_some() {
         let _ = gctx.shell().warn("`cargo publish --token` is deprecated in favor of using `cargo login` and environment variables");
@@ -70,6 +95,8 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             keep_going: args.keep_going(),
             dry_run: args.dry_run(),
             cli_features: args.cli_features()?,
+            resume: args.flag("resume"),
+            verify_index: args.flag("verify-index"),
         },
     )?;
     Ok(())
@@ -34,13 +34,24 @@ pub fn cli() -> Command {
             subcommand("timings")
                 .about("Reports the build timings of previous sessions (unstable)")
                 .arg_manifest_path()
+                .arg(
+                    opt("message-format", "Output representation")
+                        .value_name("FMT")
+                        .value_parser(ops::ReportTimingsFormat::POSSIBLE_VALUES),
+                )
                 .arg(flag("open", "Opens the timing report in a browser"))
-                .arg(opt("id", "Session ID to report on").value_name("ID")),
+                .arg(opt("id", "Session ID to report on").value_name("ID"))
+                .arg(
+                    opt("last", "Include timing trends across the last N sessions")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(u64).range(1..)),
+                ),
         )
         .subcommand(
             subcommand("sessions")
                 .about("Reports the previous sessions (unstable)")
                 .arg_manifest_path()
+                .arg(arg_message_format())
                 .arg(
                     opt("limit", "Limit the number of results")
                         .value_name("N")
@@ -52,10 +63,30 @@ pub fn cli() -> Command {
             subcommand("rebuilds")
                 .about("Reports rebuild reasons from previous sessions (unstable)")
                 .arg_manifest_path()
-                .arg(opt("id", "Session ID to report on").value_name("ID")),
+                .arg(arg_message_format())
+                .arg(opt("id", "Session ID to report on").value_name("ID"))
+                .arg(
+                    opt("compare", "Compare unit rebuilds between two sessions")
+                        .value_names(["BASE_ID", "NEW_ID"])
+                        .num_args(2)
+                        .conflicts_with("id"),
+                ),
         )
 }
 
+fn arg_message_format() -> Arg {
+    opt("message-format", "Output representation")
+        .value_name("FMT")
+        .value_parser(ops::ReportMessageFormat::POSSIBLE_VALUES)
+}
+
+fn message_format(args: &ArgMatches) -> CargoResult<ops::ReportMessageFormat> {
+    match args.get_one::<String>("message-format") {
+        Some(fmt) => fmt.parse(),
+        None => Ok(ops::ReportMessageFormat::Human),
+    }
+}
+
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     match args.subcommand() {
         Some(("future-incompatibilities", args)) => report_future_incompatibilities(gctx, args),
@@ -129,11 +160,19 @@ fn timings_opts<'a>(
         .get_one::<String>("id")
         .map(|s| s.parse())
         .transpose()?;
+    let last = args
+        .get_one::<u64>("last")
+        .map(|n| (*n).min(usize::MAX as u64) as usize);
 
     Ok(ops::ReportTimingsOptions {
         open_result,
         gctx,
         id,
+        last,
+        fmt: match args.get_one::<String>("message-format") {
+            Some(fmt) => fmt.parse()?,
+            None => ops::ReportTimingsFormat::Human,
+        },
     })
 }
 
@@ -141,7 +180,10 @@ fn sessions_opts(args: &ArgMatches) -> CargoResult<ops::ReportSessionsOptions> {
     let limit = *args.get_one::<u64>("limit").unwrap_or(&10);
     let limit = limit.min(usize::MAX as u64) as usize;
 
-    Ok(ops::ReportSessionsOptions { limit })
+    Ok(ops::ReportSessionsOptions {
+        limit,
+        fmt: message_format(args)?,
+    })
 }
 
 fn rebuilds_opts(args: &ArgMatches) -> CargoResult<ops::ReportRebuildsOptions> {
@@ -149,6 +191,19 @@ fn rebuilds_opts(args: &ArgMatches) -> CargoResult<ops::ReportRebuildsOptions> {
         .get_one::<String>("id")
         .map(|s| s.parse())
         .transpose()?;
+    let compare = match args.get_many::<String>("compare") {
+        Some(mut ids) => {
+            // clap enforces exactly two values
+            let base = ids.next().unwrap().parse()?;
+            let new = ids.next().unwrap().parse()?;
+            Some((base, new))
+        }
+        None => None,
+    };
 
-    Ok(ops::ReportRebuildsOptions { id })
+    Ok(ops::ReportRebuildsOptions {
+        id,
+        compare,
+        fmt: message_format(args)?,
+    })
 }
@@ -94,6 +94,15 @@ pub fn cli() -> Command {
                 .short('f')
                 .default_value("{p}"),
         )
+        .arg(
+            opt(
+                "output-format",
+                "Output format of the dependency graph (unstable)",
+            )
+            .value_name("FMT")
+            .value_parser(["text", "dot", "mermaid", "json"])
+            .default_value("text"),
+        )
         .arg(
             // Backwards compatibility with old cargo-tree.
             flag("version", "Print version info and exit")
@@ -181,6 +190,13 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     };
     let target = cargo_tree::Target::from_cli(targets);
 
+    let output_format = args.get_one::<String>("output-format").unwrap();
+    let output_format =
+        cargo_tree::OutputFormat::from_str(output_format).map_err(|e| anyhow::anyhow!("{}", e))?;
+    if output_format != cargo_tree::OutputFormat::Text && !gctx.cli_unstable().unstable_options {
+        return Err(format_err!("`--output-format` requires `-Zunstable-options`").into());
+    }
+
     let (edge_kinds, no_proc_macro, public) = parse_edge_kinds(gctx, args)?;
     let graph_features = edge_kinds.contains(&EdgeKind::Feature);
 
@@ -254,6 +270,7 @@ help: if you are in a workspace and want to search across all members, use:
         display_depth,
         no_proc_macro,
         public,
+        output_format,
     };
 
     if opts.graph_features && opts.duplicates {
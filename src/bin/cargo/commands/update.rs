@@ -45,6 +45,43 @@ pub fn cli() -> Command {
             )
             .short('b'),
         )
+        .arg(
+            flag(
+                "report",
+                "Print the upgrades grouped by package with the affected members (unstable)",
+            )
+            .requires("breaking"),
+        )
+        .arg(
+            flag(
+                "dedupe",
+                "Unify duplicate versions of packages within the current requirements (unstable)",
+            )
+            .conflicts_with_all(["package-group", "breaking", "precise", "recursive", "workspace"]),
+        )
+        .arg(
+            opt(
+                "plan",
+                "Write the changes to PATH for review instead of updating the lockfile (unstable)",
+            )
+            .value_name("PATH")
+            .conflicts_with_all(["breaking", "dedupe", "dry-run"]),
+        )
+        .arg(
+            opt(
+                "apply",
+                "Apply the changes in a plan written by --plan, or only those for [SPEC]... (unstable)",
+            )
+            .value_name("PATH")
+            .conflicts_with_all([
+                "breaking",
+                "dedupe",
+                "precise",
+                "recursive",
+                "workspace",
+                "plan",
+            ]),
+        )
         .arg_silent_suggestion()
         .arg(
             flag("workspace", "Only update the workspace packages")
@@ -81,22 +118,46 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
         }
     }
 
+    let unstable_flags = [
+        ("dedupe", args.flag("dedupe")),
+        ("plan", args.contains_id("plan")),
+        ("apply", args.contains_id("apply")),
+    ];
+    for (flag, present) in unstable_flags {
+        if present && !gctx.cli_unstable().unstable_options {
+            return Err(anyhow!(
+                "the `--{flag}` flag is unstable, pass `-Z unstable-options` to enable it"
+            )
+            .into());
+        }
+    }
+
+    let plan = args.value_of_path("plan", gctx);
     let update_opts = UpdateOptions {
         recursive: args.flag("recursive"),
         precise: args.get_one::<String>("precise").map(String::as_str),
         to_update,
         dry_run: args.dry_run(),
         workspace: args.flag("workspace"),
+        plan: plan.as_deref(),
         gctx,
     };
 
-    if args.flag("breaking") {
+    if args.flag("dedupe") {
+        ops::dedupe_lockfile(&ws, update_opts.dry_run)?;
+    } else if let Some(plan) = args.value_of_path("apply", gctx) {
+        ops::apply_update_plan(&ws, &plan, &update_opts.to_update, update_opts.dry_run)?;
+    } else if args.flag("breaking") {
         gctx.cli_unstable()
             .fail_if_stable_opt("--breaking", 12425)?;
 
-        let upgrades = ops::upgrade_manifests(&mut ws, &update_opts.to_update)?;
-        ops::resolve_ws(&ws, update_opts.dry_run)?;
+        let (upgrades, report) = ops::upgrade_manifests(&mut ws, &update_opts.to_update)?;
+        let previous_resolve = ops::load_pkg_lockfile(&ws)?;
+        let (_, resolve) = ops::resolve_ws(&ws, update_opts.dry_run)?;
         ops::write_manifest_upgrades(&ws, &upgrades, update_opts.dry_run)?;
+        if args.flag("report") {
+            ops::print_upgrade_report(&ws, &report, previous_resolve.as_ref(), &resolve)?;
+        }
 
         if update_opts.dry_run {
             update_opts
@@ -0,0 +1,409 @@
+//! A local cache of compiled artifacts, shared between workspaces.
+//!
+//! With `-Zbuild-cache`, the outputs of units from non-path packages are
+//! stored under `$CARGO_HOME/build-cache` after they are compiled. Before
+//! compiling such a unit, Cargo looks for an entry with the same key, and
+//! copies its outputs into the build directory instead of running `rustc`.
+//!
+//! The key of an entry is a hash of the unit's [`Metadata`], its
+//! [`Fingerprint`], and the final `rustc` invocation, which includes the
+//! flags and environment variables set by build scripts. Paths into the build
+//! directory are stripped from the invocation, so different workspaces end up
+//! with the same key.
+//!
+//! Only units whose whole dependency tree comes from non-path sources are
+//! cached, since the source of a path package can change without changing
+//! any of the above.
+//!
+//! Each entry is a directory holding the output files, and the dep-info file
+//! Cargo translated from `rustc`'s (see [`fingerprint::dep_info_loc`]).
+//! Entries are tracked in the [`global_cache_tracker`] like the other data in
+//! `CARGO_HOME`, so `cargo clean gc` can delete them based on their age or
+//! the total size of the cache.
+//!
+//! ## Remote cache
+//!
+//! With `build.cache-url` set, entries missing from the local cache are also
+//! looked up on an HTTP server, and entries compiled locally are uploaded to
+//! it. The protocol is a plain `GET`/`PUT` of the files of an entry under
+//! `<cache-url>/<key>/`, followed by a `manifest.json` listing the size and
+//! SHA-256 checksum of each file. The manifest is uploaded last, so an entry
+//! is only visible to other cargos once all its files are there, and the
+//! downloaded files are verified against it before being used. Since only
+//! units from non-path sources are cached, the code of path packages is never
+//! uploaded.
+//!
+//! Failing to reach the server only emits a warning, and the unit is compiled
+//! as if it wasn't cached.
+//!
+//! [`Metadata`]: super::Metadata
+//! [`Fingerprint`]: super::fingerprint::Fingerprint
+//! [`fingerprint::dep_info_loc`]: super::fingerprint::dep_info_loc
+//! [`global_cache_tracker`]: crate::workspace::global_cache_tracker
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context as _, bail};
+use cargo_util::{ProcessBuilder, Sha256, paths};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::build_runner::OutputFile;
+use super::{BuildRunner, CompileMode, FileFlavor, Unit};
+use crate::GlobalContext;
+use crate::util::cache_lock::CacheLockMode;
+use crate::util::data_structures::HashSet;
+use crate::util::errors::CargoResult;
+use crate::util::network::http::HandleConfiguration;
+use crate::util::network::http_async;
+use crate::util::{hex, interning::InternedString};
+use crate::workspace::global_cache_tracker::BuildCacheEntry;
+
+/// The name of the dep-info file within an entry.
+const DEP_INFO: &str = "dep-info";
+
+/// The name of the file listing the files of an entry on the remote cache.
+const MANIFEST: &str = "manifest.json";
+
+/// The build cache under `CARGO_HOME`.
+///
+/// This is shared by all jobs of a build, and records the entries they used.
+pub struct BuildCache {
+    /// The root directory of the cache, `$CARGO_HOME/build-cache`.
+    root: PathBuf,
+    /// Entries restored or stored during this build, to be marked as used in
+    /// the [`global_cache_tracker`](crate::workspace::global_cache_tracker).
+    used: Mutex<Vec<BuildCacheEntry>>,
+    /// The remote cache configured with `build.cache-url`.
+    remote: Option<RemoteCache>,
+}
+
+/// A build cache served over HTTP.
+struct RemoteCache {
+    /// The base URL of the cache, without a trailing slash.
+    url: String,
+    client: http_async::Client,
+}
+
+/// The `manifest.json` of an entry on the remote cache.
+#[derive(Default, Serialize, Deserialize)]
+struct RemoteManifest {
+    /// Keyed by the file name within the entry.
+    files: BTreeMap<String, RemoteFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RemoteFile {
+    size: u64,
+    sha256: String,
+}
+
+/// The part of the key of an entry known before the build starts.
+#[derive(Clone, Copy)]
+pub struct UnitKey(u64);
+
+impl BuildCache {
+    /// Returns the build cache, if `-Zbuild-cache` is enabled.
+    pub fn new(gctx: &GlobalContext) -> CargoResult<Option<Arc<BuildCache>>> {
+        let cache_url = gctx.build_config()?.cache_url.as_deref();
+        if !gctx.cli_unstable().build_cache {
+            if cache_url.is_some() {
+                gctx.shell()
+                    .warn("ignoring 'cache-url' config, pass `-Zbuild-cache` to enable it")?;
+            }
+            return Ok(None);
+        }
+        let remote = match cache_url {
+            // The remote cache is only an optimization, so it is skipped
+            // rather than failing offline builds.
+            Some(url) if gctx.network_allowed() => {
+                url::Url::parse(url).with_context(|| {
+                    format!("invalid `build.cache-url` config, expected a URL, found `{url}`")
+                })?;
+                Some(RemoteCache {
+                    url: url.trim_end_matches('/').to_string(),
+                    client: http_async::Client::new(HandleConfiguration::new(gctx)?),
+                })
+            }
+            _ => None,
+        };
+        Ok(Some(Arc::new(BuildCache {
+            root: gctx.build_cache_path().into_path_unlocked(),
+            used: Mutex::new(Vec::new()),
+            remote,
+        })))
+    }
+
+    /// Returns the [`UnitKey`] of `unit`, or `None` if it can't be cached.
+    ///
+    /// The fingerprint of `unit` must have already been calculated.
+    pub fn unit_key(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> Option<UnitKey> {
+        if !matches!(
+            unit.mode,
+            CompileMode::Build | CompileMode::Check { test: false }
+        ) || unit.artifact.is_true()
+            || unit.profile.incremental
+        {
+            return None;
+        }
+        // Every unit in the dependency tree must come from a non-path source.
+        let mut seen = HashSet::default();
+        let mut queue = vec![unit];
+        while let Some(unit) = queue.pop() {
+            if unit.pkg.package_id().source_id().is_path() {
+                return None;
+            }
+            for dep in build_runner.unit_deps(unit) {
+                if seen.insert(&dep.unit) {
+                    queue.push(&dep.unit);
+                }
+            }
+        }
+        let fingerprint = build_runner.fingerprints.get(unit)?;
+        let unit_id = build_runner.files().metadata(unit).unit_id();
+        Some(UnitKey(hex::hash_u64((
+            unit_id.to_string(),
+            hex::hash_u64(&**fingerprint),
+        ))))
+    }
+
+    /// Computes the key of an entry from its [`UnitKey`] and the final
+    /// `rustc` invocation, with paths into `build_dir` stripped.
+    pub fn key(&self, unit_key: UnitKey, rustc: &ProcessBuilder, build_dir: &Path) -> String {
+        let build_dir = build_dir.to_string_lossy();
+        let normalize = |s: &std::ffi::OsStr| s.to_string_lossy().replace(&*build_dir, "");
+        let args: Vec<_> = rustc.get_args().map(|arg| normalize(arg)).collect();
+        let envs: Vec<_> = rustc
+            .get_envs()
+            .iter()
+            .map(|(k, v)| (k, v.as_deref().map(normalize)))
+            .collect();
+        hex::short_hash(&(unit_key.0, normalize(rustc.get_program()), args, envs))
+    }
+
+    /// Copies the outputs of the entry `key` into place, returning whether
+    /// the entry was found.
+    ///
+    /// An entry missing from the local cache is downloaded from the remote
+    /// cache, if any. Only failing to do so is returned as an error.
+    pub fn restore(&self, key: &str, outputs: &[OutputFile], dep_info: &Path) -> CargoResult<bool> {
+        if self.restore_local(key, outputs, dep_info) {
+            return Ok(true);
+        }
+        let Some(remote) = &self.remote else {
+            return Ok(false);
+        };
+        if !remote.download(&self.root, key)? {
+            return Ok(false);
+        }
+        Ok(self.restore_local(key, outputs, dep_info))
+    }
+
+    fn restore_local(&self, key: &str, outputs: &[OutputFile], dep_info: &Path) -> bool {
+        let entry = self.root.join(key);
+        match restore_entry(&entry, outputs, dep_info) {
+            Ok(true) => {
+                debug!("restored {key} from the build cache");
+                self.mark_used(key, &entry);
+                true
+            }
+            Ok(false) => false,
+            Err(e) => {
+                debug!("failed to restore {key} from the build cache: {e:?}");
+                false
+            }
+        }
+    }
+
+    /// Stores the outputs of a unit that was just compiled as the entry `key`,
+    /// and uploads it to the remote cache, if any.
+    ///
+    /// Failing to store an entry doesn't fail the build. Only failing to
+    /// upload it is returned as an error.
+    pub fn store(&self, key: &str, outputs: &[OutputFile], dep_info: &Path) -> CargoResult<()> {
+        let entry = self.root.join(key);
+        if !entry.exists() {
+            match store_entry(&self.root, &entry, outputs, dep_info) {
+                Ok(()) => self.mark_used(key, &entry),
+                Err(e) => {
+                    debug!("failed to store {key} in the build cache: {e:?}");
+                    return Ok(());
+                }
+            }
+        }
+        if let Some(remote) = &self.remote {
+            remote.upload(&entry, key)?;
+        }
+        Ok(())
+    }
+
+    fn mark_used(&self, key: &str, entry: &Path) {
+        let size = cargo_util::du(entry, &[]).unwrap_or(0);
+        self.used.lock().unwrap().push(BuildCacheEntry {
+            name: InternedString::new(key),
+            size,
+        });
+    }
+
+    /// Records the entries used during this build in the global cache
+    /// tracker, so they are kept by `cargo clean gc`.
+    pub fn save_last_use(&self, gctx: &GlobalContext) -> CargoResult<()> {
+        let used = std::mem::take(&mut *self.used.lock().unwrap());
+        if used.is_empty() {
+            return Ok(());
+        }
+        let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+        let mut deferred = gctx.deferred_global_last_use()?;
+        for entry in used {
+            deferred.mark_build_cache_used(entry);
+        }
+        deferred.save_no_error(gctx);
+        Ok(())
+    }
+}
+
+fn file_name(path: &Path) -> &std::ffi::OsStr {
+    path.file_name().expect("output files have a file name")
+}
+
+fn restore_entry(entry: &Path, outputs: &[OutputFile], dep_info: &Path) -> CargoResult<bool> {
+    if !entry.join(DEP_INFO).exists() {
+        return Ok(false);
+    }
+    let missing_output = outputs
+        .iter()
+        .filter(|output| !matches!(output.flavor, FileFlavor::DebugInfo | FileFlavor::Auxiliary))
+        .any(|output| !entry.join(file_name(&output.path)).exists());
+    if missing_output {
+        return Ok(false);
+    }
+    for output in outputs {
+        let cached = entry.join(file_name(&output.path));
+        if cached.exists() {
+            paths::copy(&cached, &output.path)?;
+        }
+    }
+    paths::copy(entry.join(DEP_INFO), dep_info)?;
+    Ok(true)
+}
+
+fn store_entry(
+    root: &Path,
+    entry: &Path,
+    outputs: &[OutputFile],
+    dep_info: &Path,
+) -> CargoResult<()> {
+    paths::create_dir_all(root)?;
+    // Entries are written to a temporary directory first, so other cargos
+    // never see a partial entry.
+    let tmp = tempfile::Builder::new().prefix(".tmp").tempdir_in(root)?;
+    for output in outputs {
+        if output.path.exists() {
+            paths::copy(&output.path, tmp.path().join(file_name(&output.path)))?;
+        }
+    }
+    paths::copy(dep_info, tmp.path().join(DEP_INFO))?;
+    commit_entry(tmp, entry)
+}
+
+/// Moves a complete entry written to `tmp` into place.
+fn commit_entry(tmp: tempfile::TempDir, entry: &Path) -> CargoResult<()> {
+    // Another cargo may have stored the same entry in the meantime, in which
+    // case the temporary directory is removed on drop.
+    if let Err(e) = fs::rename(tmp.path(), entry) {
+        if !entry.exists() {
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+impl RemoteCache {
+    fn file_url(&self, key: &str, name: &str) -> String {
+        format!("{}/{key}/{name}", self.url)
+    }
+
+    fn get(&self, url: &str) -> CargoResult<Option<Vec<u8>>> {
+        let request = http::Request::get(url).body(Vec::new())?;
+        let response = self
+            .client
+            .request_blocking(request)
+            .with_context(|| format!("failed to download `{url}`"))?;
+        match response.status() {
+            http::StatusCode::OK => Ok(Some(response.into_body())),
+            http::StatusCode::NOT_FOUND => Ok(None),
+            status => bail!("failed to download `{url}`, got status {status}"),
+        }
+    }
+
+    fn put(&self, url: &str, body: Vec<u8>) -> CargoResult<()> {
+        let request = http::Request::put(url).body(body)?;
+        let response = self
+            .client
+            .request_blocking(request)
+            .with_context(|| format!("failed to upload `{url}`"))?;
+        if !response.status().is_success() {
+            bail!("failed to upload `{url}`, got status {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Downloads the entry `key` into the local cache under `root`, returning
+    /// whether the remote cache has it.
+    fn download(&self, root: &Path, key: &str) -> CargoResult<bool> {
+        let manifest_url = self.file_url(key, MANIFEST);
+        let Some(manifest) = self.get(&manifest_url)? else {
+            return Ok(false);
+        };
+        let manifest: RemoteManifest = serde_json::from_slice(&manifest)
+            .with_context(|| format!("failed to parse `{manifest_url}`"))?;
+        paths::create_dir_all(root)?;
+        let tmp = tempfile::Builder::new().prefix(".tmp").tempdir_in(root)?;
+        for (name, file) in &manifest.files {
+            // The manifest comes from the network, don't let it write
+            // outside of the entry.
+            if Path::new(name).file_name() != Some(name.as_ref()) {
+                bail!("invalid file name `{name}` in `{manifest_url}`");
+            }
+            let url = self.file_url(key, name);
+            let Some(contents) = self.get(&url)? else {
+                bail!("failed to download `{url}`, listed in `{manifest_url}`");
+            };
+            let sha256 = Sha256::new().update(&contents).finish_hex();
+            if contents.len() as u64 != file.size || sha256 != file.sha256 {
+                bail!(
+                    "checksum mismatch for `{url}`, expected {} but got {sha256}",
+                    file.sha256
+                );
+            }
+            paths::write(tmp.path().join(name), contents)?;
+        }
+        debug!("downloaded {key} from {}", self.url);
+        commit_entry(tmp, &root.join(key))?;
+        Ok(true)
+    }
+
+    /// Uploads the local entry `key`, with its manifest last.
+    fn upload(&self, entry: &Path, key: &str) -> CargoResult<()> {
+        let mut manifest = RemoteManifest::default();
+        for file in fs::read_dir(entry)? {
+            let file = file?;
+            let name = file.file_name().to_string_lossy().into_owned();
+            let contents = paths::read_bytes(&file.path())?;
+            let sha256 = Sha256::new().update(&contents).finish_hex();
+            let size = contents.len() as u64;
+            self.put(&self.file_url(key, &name), contents)?;
+            manifest.files.insert(name, RemoteFile { size, sha256 });
+        }
+        self.put(
+            &self.file_url(key, MANIFEST),
+            serde_json::to_vec(&manifest)?,
+        )?;
+        debug!("uploaded {key} to {}", self.url);
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::compiler::CompileKind;
 use crate::context::JobsConfig;
 use crate::util::interning::InternedString;
 use crate::util::{CargoResult, GlobalContext, RustfixDiagnosticServer};
+use crate::workspace::PackageIdSpec;
 use anyhow::{Context as _, bail};
 use cargo_util::ProcessBuilder;
 use serde::ser;
@@ -31,6 +32,9 @@ pub struct BuildConfig {
     pub unit_graph: bool,
     /// `true` to avoid really compiling.
     pub dry_run: bool,
+    /// Explain why the units of this package would be rebuilt instead of
+    /// actually compiling.
+    pub explain_fresh: Option<PackageIdSpec>,
     /// An optional override of the rustc process for primary units
     pub primary_unit_rustc: Option<ProcessBuilder>,
     /// A thread used by `cargo fix` to receive messages on a socket regarding
@@ -123,6 +127,7 @@ impl BuildConfig {
             force_rebuild: false,
             unit_graph: false,
             dry_run: false,
+            explain_fresh: None,
             primary_unit_rustc: None,
             rustfix_diagnostic_server: Rc::new(RefCell::new(None)),
             export_dir: None,
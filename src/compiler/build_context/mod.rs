@@ -93,6 +93,9 @@ pub struct BuildContext<'a, 'gctx> {
 
     /// The list of all kinds that are involved in this build
     pub all_kinds: HashSet<CompileKind>,
+
+    /// The number of lint warnings emitted while preparing the build.
+    pub lint_warning_count: usize,
 }
 
 impl<'a, 'gctx> BuildContext<'a, 'gctx> {
@@ -132,6 +135,7 @@ impl<'a, 'gctx> BuildContext<'a, 'gctx> {
             unit_to_index,
             scrape_units,
             all_kinds,
+            lint_warning_count: 0,
         })
     }
 
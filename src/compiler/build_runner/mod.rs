@@ -9,7 +9,7 @@ use crate::compiler::locking::LockManager;
 use crate::compiler::{self, Unit, UserIntent, artifact};
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::errors::CargoResult;
-use crate::workspace::PackageId;
+use crate::workspace::{PackageId, PackageIdSpec, PackageIdSpecQuery};
 use anyhow::{Context as _, bail};
 use cargo_util::paths;
 use cargo_util_terminal::report::{Level, Message};
@@ -25,7 +25,9 @@ use super::layout::Layout;
 use super::lto::Lto;
 use super::unit_graph::UnitDep;
 use super::unused_deps::UnusedDepState;
-use super::{BuildContext, Compilation, CompileKind, CompileMode, Executor, FileFlavor};
+use super::{
+    BuildCache, BuildContext, Compilation, CompileKind, CompileMode, Executor, FileFlavor,
+};
 
 mod compilation_files;
 use self::compilation_files::CompilationFiles;
@@ -94,6 +96,9 @@ pub struct BuildRunner<'a, 'gctx> {
 
     /// Manages locks for build units when fine grain locking is enabled.
     pub lock_manager: Arc<LockManager>,
+
+    /// The build cache shared between workspaces, if `-Zbuild-cache` is enabled.
+    pub build_cache: Option<Arc<BuildCache>>,
 }
 
 impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
@@ -135,6 +140,7 @@ impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
             failed_scrape_units: Arc::new(Mutex::new(HashSet::default())),
             unused_dep_state: UnusedDepState::new(bcx),
             lock_manager: Arc::new(LockManager::new()),
+            build_cache: BuildCache::new(bcx.gctx)?,
         })
     }
 
@@ -159,6 +165,48 @@ impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
         Ok(self.compilation)
     }
 
+    /// Explains why the units matching `spec` would be rebuilt, without
+    /// compiling anything.
+    ///
+    /// Units requested on the command line are preferred; otherwise every
+    /// unit of the matching package in the unit graph is explained.
+    /// Please keep in sync with non-compilation part in [`BuildRunner::compile`].
+    pub fn explain_fresh(mut self, spec: &PackageIdSpec) -> CargoResult<Compilation<'gctx>> {
+        let _lock = self
+            .bcx
+            .gctx
+            .acquire_package_cache_lock(CacheLockMode::Shared)?;
+        self.lto = super::lto::generate(self.bcx)?;
+        self.prepare_units()?;
+        self.prepare()?;
+        custom_build::build_map(&mut self)?;
+        self.check_collisions()?;
+        self.compute_metadata_for_doc_units();
+
+        let matches =
+            |unit: &&Unit| spec.matches(unit.pkg.package_id()) && !unit.mode.is_doc_test();
+        let mut units: Vec<_> = self.bcx.roots.iter().filter(matches).cloned().collect();
+        if units.is_empty() {
+            units = self
+                .bcx
+                .unit_graph
+                .keys()
+                .filter(matches)
+                .cloned()
+                .collect();
+        }
+        if units.is_empty() {
+            bail!("package ID specification `{spec}` did not match any units in this build");
+        }
+        units.sort();
+
+        for unit in &units {
+            super::fingerprint::explain_target(&mut self, unit)?;
+        }
+
+        Ok(self.compilation)
+    }
+
     /// Starts compilation, waits for it to finish, and returns information
     /// about the result of compilation.
     ///
@@ -207,6 +255,10 @@ impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
         // Now that we've figured out everything that we're going to do, do it!
         queue.execute(&mut self)?;
 
+        if let Some(build_cache) = &self.build_cache {
+            build_cache.save_last_use(self.bcx.gctx)?;
+        }
+
         // Add `OUT_DIR` to env vars if unit has a build script.
         let units_with_build_script = &self
             .bcx
@@ -750,14 +802,7 @@ impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
     /// Returns whether when `parent` depends on `dep` if it only requires the
     /// metadata file from `dep`.
     pub fn only_requires_rmeta(&self, parent: &Unit, dep: &Unit) -> bool {
-        // We're only a candidate for requiring an `rmeta` file if we
-        // ourselves are building an rlib,
-        !parent.requires_upstream_objects()
-            && parent.mode == CompileMode::Build
-            // Our dependency must also be built as an rlib, otherwise the
-            // object code must be useful in some fashion
-            && !dep.requires_upstream_objects()
-            && dep.mode == CompileMode::Build
+        parent.only_requires_rmeta(dep)
     }
 
     /// Returns whether when `unit` is built whether it should emit metadata as
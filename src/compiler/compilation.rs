@@ -198,7 +198,7 @@ impl<'gctx> Compilation<'gctx> {
             primary_rustc_process,
             runners,
             linkers,
-            lint_warning_count: 0,
+            lint_warning_count: bcx.lint_warning_count,
         })
     }
 
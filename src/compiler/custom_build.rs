@@ -524,6 +524,8 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
     paths::create_dir_all(&run_files.root)?;
 
     let nightly_features_allowed = build_runner.bcx.gctx.nightly_features_allowed;
+    let checksum_freshness = build_runner.bcx.gctx.cli_unstable().checksum_freshness;
+    let pkg_root = unit.pkg.root().to_path_buf();
     let targets: Vec<Target> = unit.pkg.targets().to_vec();
     let msrv = unit.pkg.rust_version().cloned();
     // Need a separate copy for the fresh closure.
@@ -683,7 +685,7 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
         paths::write(&run_files.stdout, &output.stdout)?;
         // This mtime shift allows Cargo to detect if a source file was
         // modified in the middle of the build.
-        paths::set_file_time_no_err(run_files.stdout, timestamp);
+        paths::set_file_time_no_err(&run_files.stdout, timestamp);
         paths::write(&run_files.stderr, &output.stderr)?;
         paths::write(&run_files.root_output, paths::path2bytes(&script_out_dir)?)?;
         let parsed_output = BuildOutput::parse(
@@ -697,6 +699,15 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
             &msrv,
         )?;
 
+        if checksum_freshness && !parsed_output.rerun_if_changed.is_empty() {
+            fingerprint::record_rerun_if_changed_checksums(
+                &run_files.stdout,
+                &pkg_root,
+                &parsed_output.rerun_if_changed,
+                timestamp,
+            )?;
+        }
+
         if json_messages {
             emit_build_output(state, &parsed_output, script_out_dir.as_path(), id)?;
         }
@@ -37,7 +37,9 @@
 //!    mtimes are ignored entirely in favor of comparing first the file size, and
 //!    then the checksum with a known prior value emitted by rustc. Only nightly
 //!    rustc will emit the needed metadata at the time of writing. This is dependent
-//!    on the unstable feature `-Z checksum-hash-algorithm`.
+//!    on the unstable feature `-Z checksum-hash-algorithm`. Files under the
+//!    `rerun-if-changed` paths of build scripts are hashed by Cargo itself
+//!    (see [`record_rerun_if_changed_checksums`]).
 //!
 //! Note: Fingerprinting is not a perfect solution. Filesystem mtime tracking
 //! is notoriously imprecise and problematic. Only a small part of the
@@ -374,6 +376,7 @@
 
 mod dep_info;
 mod dirty_reason;
+mod rerun_if_changed;
 mod rustdoc;
 
 use crate::util::data_structures::HashMap;
@@ -421,6 +424,7 @@ pub use self::dep_info::parse_dep_info;
 pub use self::dep_info::parse_rustc_dep_info;
 pub use self::dep_info::translate_dep_info;
 pub use self::dirty_reason::DirtyReason;
+pub use self::rerun_if_changed::record_rerun_if_changed_checksums;
 pub use self::rustdoc::RustdocFingerprint;
 
 /// Result of comparing fingerprints between the current and previous builds.
@@ -585,6 +589,53 @@ pub fn prepare_target(
     Ok(Job::new_dirty(write_fingerprint, dirty_reason))
 }
 
+/// Explains why a [`Unit`] would be rebuilt, without building anything.
+///
+/// This calculates the fingerprint of `unit`, compares it against the one on
+/// disk, and prints the [`DirtyReason`]. If the unit is dirty because of one
+/// of its dependencies, the dependency is explained next, until reaching the
+/// unit that is the root cause.
+///
+/// Unlike [`prepare_target`], this never touches the fingerprint files.
+pub fn explain_target(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResult<()> {
+    let bcx = build_runner.bcx;
+    let ws_root = bcx.ws.root().to_path_buf();
+    let index_to_unit: HashMap<_, _> = bcx
+        .unit_to_index
+        .iter()
+        .map(|(unit, &index)| (index, unit.clone()))
+        .collect();
+
+    let mut unit = unit.clone();
+    loop {
+        let loc = build_runner.files().fingerprint_file_path(&unit, "");
+        let fingerprint = calculate(build_runner, &unit)?;
+        let mut shell = bcx.gctx.shell();
+        let reason = match compare_old_fingerprint(&unit, &loc, &*fingerprint, false, false) {
+            FingerprintComparison::Fresh => {
+                shell.status("Fresh", &unit.pkg)?;
+                return Ok(());
+            }
+            FingerprintComparison::Dirty { reason } => reason,
+        };
+        reason.present_to(&mut shell, &unit, &ws_root, &index_to_unit)?;
+
+        // The unit graph is acyclic, so following dependencies always ends.
+        let dep = match &reason {
+            DirtyReason::UnitDependencyInfoChanged { unit }
+            | DirtyReason::FsStatusOutdated(FsStatus::StaleDependency { unit, .. })
+            | DirtyReason::FsStatusOutdated(FsStatus::StaleDepFingerprint { unit }) => {
+                index_to_unit.get(unit)
+            }
+            _ => None,
+        };
+        let Some(dep) = dep else {
+            return Ok(());
+        };
+        unit = dep.clone();
+    }
+}
+
 /// Dependency edge information for fingerprints. This is generated for each
 /// dependency and is stored in a [`Fingerprint`].
 #[derive(Clone)]
@@ -840,9 +891,15 @@ enum LocalFingerprint {
     ///
     /// This is considered up-to-date if all of the `paths` are older than
     /// `output`, otherwise we need to recompile.
+    ///
+    /// If the `checksum` bool is true then the files under `paths` are
+    /// instead compared against the checksums recorded when the build script
+    /// ran, see [`record_rerun_if_changed_checksums`].
     RerunIfChanged {
         output: PathBuf,
         paths: Vec<PathBuf>,
+        #[serde(default)]
+        checksum: bool,
     },
 
     /// This represents a single `rerun-if-env-changed` annotation printed by a
@@ -1000,13 +1057,29 @@ impl LocalFingerprint {
 
             // We need to verify that no paths listed in `paths` are newer than
             // the `output` path itself, or the last time the build script ran.
-            LocalFingerprint::RerunIfChanged { output, paths } => Ok(find_stale_file(
-                mtime_cache,
-                checksum_cache,
-                &build_root.join(output),
-                paths.iter().map(|p| (pkg_root.join(p), None)),
-                false,
-            )),
+            LocalFingerprint::RerunIfChanged {
+                output,
+                paths,
+                checksum,
+            } => {
+                let output = build_root.join(output);
+                if *checksum {
+                    Ok(rerun_if_changed::find_stale_rerun_if_changed(
+                        checksum_cache,
+                        &output,
+                        pkg_root,
+                        paths,
+                    ))
+                } else {
+                    Ok(find_stale_file(
+                        mtime_cache,
+                        checksum_cache,
+                        &output,
+                        paths.iter().map(|p| (pkg_root.join(p), None)),
+                        false,
+                    ))
+                }
+            }
 
             // These have no dependencies on the filesystem, and their values
             // are included natively in the `Fingerprint` hash so nothing
@@ -1147,10 +1220,12 @@ impl Fingerprint {
                     LocalFingerprint::RerunIfChanged {
                         output: a_out,
                         paths: a_paths,
+                        checksum: checksum_a,
                     },
                     LocalFingerprint::RerunIfChanged {
                         output: b_out,
                         paths: b_paths,
+                        checksum: checksum_b,
                     },
                 ) => {
                     if a_out != b_out {
@@ -1165,6 +1240,9 @@ impl Fingerprint {
                             new: a_paths.clone(),
                         };
                     }
+                    if checksum_a != checksum_b {
+                        return DirtyReason::ChecksumUseChanged { old: *checksum_b };
+                    }
                 }
                 (
                     LocalFingerprint::RerunIfEnvChanged {
@@ -1832,6 +1910,7 @@ fn build_script_local_fingerprints(
     let pkg_root = unit.pkg.root().to_path_buf();
     let build_dir = build_root(build_runner);
     let env_config = Arc::clone(build_runner.bcx.gctx.env_config()?);
+    let checksum = build_runner.bcx.gctx.cli_unstable().checksum_freshness;
     let calculate =
         move |deps: &BuildDeps, pkg_fingerprint: Option<&dyn Fn() -> CargoResult<String>>| {
             if deps.rerun_if_changed.is_empty() && deps.rerun_if_env_changed.is_empty() {
@@ -1866,6 +1945,7 @@ fn build_script_local_fingerprints(
                 &build_dir,
                 &pkg_root,
                 &env_config,
+                checksum,
             )))
         };
 
@@ -1902,6 +1982,7 @@ fn local_fingerprints_deps(
     build_root: &Path,
     pkg_root: &Path,
     env_config: &Arc<HashMap<String, OsString>>,
+    checksum: bool,
 ) -> Vec<LocalFingerprint> {
     debug!("new local fingerprints deps {:?}", pkg_root);
     let mut local = Vec::new();
@@ -1920,7 +2001,11 @@ fn local_fingerprints_deps(
             .iter()
             .map(|p| p.strip_prefix(pkg_root).unwrap_or(p).to_path_buf())
             .collect();
-        local.push(LocalFingerprint::RerunIfChanged { output, paths });
+        local.push(LocalFingerprint::RerunIfChanged {
+            output,
+            paths,
+            checksum,
+        });
     }
 
     local.extend(
@@ -0,0 +1,215 @@
+//! Checksum freshness for the `rerun-if-changed` paths of build scripts.
+//!
+//! With `-Zchecksum-freshness`, the files under the `rerun-if-changed` paths
+//! of a build script are hashed after it runs, and recorded next to its
+//! output. The next build compares the files against those checksums rather
+//! than comparing their mtimes to the build script output, so touching a file
+//! without changing its content doesn't rerun the build script.
+//!
+//! Each record also keeps the size and mtime of the file, acting as a cache:
+//! a file whose size and mtime are unchanged is not hashed again.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use cargo_util::paths;
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::{Checksum, StaleItem};
+use crate::util::data_structures::{HashMap, HashSet};
+use crate::util::errors::CargoResult;
+
+/// The algorithm used to hash files, matching the one Cargo asks rustc to use.
+const ALGO: super::dep_info::ChecksumAlgo = super::dep_info::ChecksumAlgo::Blake3;
+
+/// Checksums of the files under the `rerun-if-changed` paths,
+/// recorded when the build script last ran.
+#[derive(Default, Serialize, Deserialize)]
+struct RerunIfChangedChecksums {
+    /// Keyed by the file path, relative to the package root when possible.
+    files: BTreeMap<PathBuf, FileChecksum>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileChecksum {
+    len: u64,
+    /// Unix seconds and nanoseconds, kept exact so they can be compared.
+    mtime: (i64, u32),
+    checksum: Checksum,
+}
+
+fn mtime(meta: &fs::Metadata) -> (i64, u32) {
+    let mtime = FileTime::from_last_modification_time(meta);
+    (mtime.unix_seconds(), mtime.nanoseconds())
+}
+
+/// The location of the checksums of a build script run, next to its `output`.
+fn checksums_path(output: &Path) -> PathBuf {
+    output.with_file_name("rerun-if-changed-checksums.json")
+}
+
+/// Lists the files under a `rerun-if-changed` path, walking into directories.
+///
+/// Returns `None` if the path doesn't exist.
+fn files_under(path: &Path) -> Option<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return path.exists().then(|| vec![path.to_path_buf()]);
+    }
+    let mut files: Vec<_> = walkdir::WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| match e {
+            Ok(e) => Some(e),
+            Err(e) => {
+                // Same as for mtimes, if Cargo can't access it, the build
+                // script probably can't access it, either.
+                debug!("failed to walk directory for checksums: {}", e);
+                None
+            }
+        })
+        .filter(|e| !e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    Some(files)
+}
+
+/// Hashes the files under the `rerun-if-changed` `paths` of a build script,
+/// and records them next to its `output`.
+///
+/// `timestamp` is when the build script started running. Files modified after
+/// that are left out, so the next build considers the build script stale.
+pub fn record_rerun_if_changed_checksums(
+    output: &Path,
+    pkg_root: &Path,
+    paths: &[PathBuf],
+    timestamp: FileTime,
+) -> CargoResult<()> {
+    let timestamp = (timestamp.unix_seconds(), timestamp.nanoseconds());
+    let mut checksums = RerunIfChangedChecksums::default();
+    for path in paths {
+        let Some(files) = files_under(&pkg_root.join(path)) else {
+            continue;
+        };
+        for file in files {
+            let Ok(meta) = fs::metadata(&file) else {
+                continue;
+            };
+            if mtime(&meta) > timestamp {
+                debug!("{} changed while the build script ran", file.display());
+                continue;
+            }
+            let Ok(checksum) = File::open(&file).and_then(|f| Checksum::compute(ALGO, f)) else {
+                continue;
+            };
+            let key = file.strip_prefix(pkg_root).unwrap_or(&file).to_path_buf();
+            checksums.files.insert(
+                key,
+                FileChecksum {
+                    len: meta.len(),
+                    mtime: mtime(&meta),
+                    checksum,
+                },
+            );
+        }
+    }
+    paths::write(checksums_path(output), serde_json::to_vec(&checksums)?)
+}
+
+/// Finds the first file under the `rerun-if-changed` `paths` whose content
+/// differs from when the build script last ran.
+///
+/// When a file was touched without its content changing, its new mtime is
+/// recorded, so the next build doesn't need to hash it again.
+pub fn find_stale_rerun_if_changed(
+    checksum_cache: &mut HashMap<PathBuf, Checksum>,
+    output: &Path,
+    pkg_root: &Path,
+    paths: &[PathBuf],
+) -> Option<StaleItem> {
+    let checksums_path = checksums_path(output);
+    let Ok(contents) = paths::read_bytes(&checksums_path) else {
+        return Some(StaleItem::MissingFile {
+            path: checksums_path,
+        });
+    };
+    let Ok(mut checksums) = serde_json::from_slice::<RerunIfChangedChecksums>(&contents) else {
+        return Some(StaleItem::UnableToReadFile {
+            path: checksums_path,
+        });
+    };
+
+    let mut seen = HashSet::default();
+    let mut touched = false;
+    for path in paths {
+        let path = pkg_root.join(path);
+        let Some(files) = files_under(&path) else {
+            return Some(StaleItem::MissingFile { path });
+        };
+        for file in files {
+            let key = file.strip_prefix(pkg_root).unwrap_or(&file).to_path_buf();
+            let Some(recorded) = checksums.files.get_mut(&key) else {
+                return Some(StaleItem::MissingChecksum { path: file });
+            };
+            let Ok(meta) = fs::metadata(&file) else {
+                return Some(StaleItem::FailedToReadMetadata { path: file });
+            };
+            if meta.len() != recorded.len {
+                return Some(StaleItem::FileSizeChanged {
+                    path: file,
+                    old_size: recorded.len,
+                    new_size: meta.len(),
+                });
+            }
+            seen.insert(key);
+            if mtime(&meta) == recorded.mtime {
+                continue;
+            }
+
+            let algo = recorded.checksum.algo();
+            let checksum = match checksum_cache.get(&file) {
+                Some(checksum) if checksum.algo() == algo => *checksum,
+                _ => {
+                    let Ok(f) = File::open(&file) else {
+                        return Some(StaleItem::MissingFile { path: file });
+                    };
+                    let Ok(checksum) = Checksum::compute(algo, f) else {
+                        return Some(StaleItem::UnableToReadFile { path: file });
+                    };
+                    checksum_cache.insert(file.clone(), checksum);
+                    checksum
+                }
+            };
+            if checksum != recorded.checksum {
+                return Some(StaleItem::ChangedChecksum {
+                    source: file,
+                    stored_checksum: recorded.checksum,
+                    new_checksum: checksum,
+                });
+            }
+            recorded.mtime = mtime(&meta);
+            touched = true;
+        }
+    }
+
+    // A file was removed from one of the directories.
+    if let Some(removed) = checksums.files.keys().find(|key| !seen.contains(*key)) {
+        return Some(StaleItem::MissingFile {
+            path: pkg_root.join(removed),
+        });
+    }
+
+    if touched {
+        let result = serde_json::to_vec(&checksums)
+            .map_err(Into::into)
+            .and_then(|contents| paths::write(&checksums_path, contents));
+        if let Err(e) = result {
+            debug!("failed to update {}: {e}", checksums_path.display());
+        }
+    }
+    None
+}
@@ -29,6 +29,7 @@
 //! [`ops::cargo_compile::compile`]: crate::ops::compile
 
 pub mod artifact;
+mod build_cache;
 mod build_config;
 pub(crate) mod build_context;
 pub(crate) mod build_runner;
@@ -73,6 +74,7 @@ use itertools::Itertools;
 use regex::Regex;
 use tracing::{debug, instrument, trace};
 
+pub(crate) use self::build_cache::BuildCache;
 pub use self::build_config::UserIntent;
 pub use self::build_config::{BuildConfig, CompileMode, MessageFormat};
 pub use self::build_context::BuildContext;
@@ -340,6 +342,10 @@ fn rustc(
     let artifact = unit.artifact;
     let sbom_files = build_runner.sbom_output_files(unit)?;
     let sbom = build_sbom(build_runner, unit)?;
+    let build_cache = build_runner
+        .build_cache
+        .clone()
+        .and_then(|cache| Some((cache, BuildCache::unit_key(build_runner, unit)?)));
 
     let hide_diagnostics_for_scrape_unit = build_runner.bcx.unit_can_fail_for_docscraping(unit)
         && !matches!(
@@ -421,7 +427,6 @@ fn rustc(
             }
         }
 
-        state.running(&rustc);
         let timestamp = paths::set_invocation_time(&fingerprint_dir)?;
         for file in sbom_files {
             tracing::debug!("writing sbom to {}", file.display());
@@ -429,6 +434,25 @@ fn rustc(
             serde_json::to_writer(outfile, &sbom)?;
         }
 
+        // Only now that the flags from build scripts are known can the build
+        // cache be consulted.
+        let build_cache = build_cache.map(|(cache, unit_key)| {
+            let key = cache.key(unit_key, &rustc, &build_dir);
+            (cache, key)
+        });
+        if let Some((cache, key)) = &build_cache {
+            match cache.restore(key, &outputs, &dep_info_loc) {
+                Ok(true) => {
+                    paths::set_file_time_no_err(&dep_info_loc, timestamp);
+                    return Ok(());
+                }
+                Ok(false) => {}
+                Err(e) => state.warning(format!("{e:#}")),
+            }
+        }
+
+        state.running(&rustc);
+
         let result = exec
             .exec(
                 &rustc,
@@ -505,7 +529,7 @@ fn rustc(
             })?;
             // This mtime shift allows Cargo to detect if a source file was
             // modified in the middle of the build.
-            paths::set_file_time_no_err(dep_info_loc, timestamp);
+            paths::set_file_time_no_err(&dep_info_loc, timestamp);
         }
 
         // This mtime shift for .rmeta is a workaround as rustc incremental build
@@ -527,6 +551,12 @@ fn rustc(
             }
         }
 
+        if let Some((cache, key)) = &build_cache {
+            if let Err(e) = cache.store(key, &outputs, &dep_info_loc) {
+                state.warning(format!("{e:#}"));
+            }
+        }
+
         Ok(())
     }));
 
@@ -0,0 +1,159 @@
+//! Render timing tracking data in the Chrome [Trace Event Format],
+//! which can be loaded into tools like [Perfetto] or `chrome://tracing`.
+//!
+//! [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+//! [Perfetto]: https://ui.perfetto.dev/
+
+use std::io::Write;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::CargoResult;
+
+use super::report::RenderContext;
+
+/// A phase of the build outside of any unit, like dependency resolution.
+pub struct Phase {
+    pub name: &'static str,
+    /// Seconds elapsed from build start.
+    pub start: f64,
+    /// Seconds elapsed from build start.
+    pub end: f64,
+}
+
+/// A single event in the trace.
+#[derive(Serialize)]
+struct TraceEvent {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cat: Option<&'static str>,
+    ph: &'static str,
+    /// Timestamp in microseconds.
+    ts: f64,
+    /// Duration in microseconds, for complete events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    pid: u32,
+    tid: usize,
+    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    args: serde_json::Value,
+}
+
+const PID: u32 = 1;
+/// Track for build phases outside of any unit.
+const CARGO_TID: usize = 0;
+
+fn micros(secs: f64) -> f64 {
+    (secs * 1_000_000.0).round()
+}
+
+fn metadata(name: &str, tid: usize, args: serde_json::Value) -> TraceEvent {
+    TraceEvent {
+        name: name.to_owned(),
+        cat: None,
+        ph: "M",
+        ts: 0.0,
+        dur: None,
+        pid: PID,
+        tid,
+        args,
+    }
+}
+
+/// Writes a trace in the JSON object format of the Trace Event Format.
+///
+/// Units are laid out on one track per job slot,
+/// with their compilation sections nested inside them.
+pub fn write_chrome_trace(
+    ctx: &RenderContext<'_>,
+    phases: &[Phase],
+    f: &mut impl Write,
+) -> CargoResult<()> {
+    let mut events = vec![
+        metadata("process_name", CARGO_TID, json!({ "name": "cargo" })),
+        metadata("thread_name", CARGO_TID, json!({ "name": "cargo" })),
+    ];
+
+    for phase in phases {
+        events.push(TraceEvent {
+            name: phase.name.to_owned(),
+            cat: Some("phase"),
+            ph: "X",
+            ts: micros(phase.start),
+            dur: Some(micros((phase.end - phase.start).max(0.0))),
+            pid: PID,
+            tid: CARGO_TID,
+            args: serde_json::Value::Null,
+        });
+    }
+
+    // Assign each unit to the first job slot that is free by the time it starts.
+    // `unit_data` is already sorted by start time.
+    let mut slot_ends: Vec<f64> = Vec::new();
+    for unit in &ctx.unit_data {
+        let end = unit.start + unit.duration;
+        let slot = match slot_ends
+            .iter()
+            .position(|slot_end| *slot_end <= unit.start)
+        {
+            Some(slot) => {
+                slot_ends[slot] = end;
+                slot
+            }
+            None => {
+                slot_ends.push(end);
+                slot_ends.len() - 1
+            }
+        };
+        let tid = slot + 1;
+
+        events.push(TraceEvent {
+            name: format!("{} v{}{}", unit.name, unit.version, unit.target),
+            cat: Some("unit"),
+            ph: "X",
+            ts: micros(unit.start),
+            dur: Some(micros(unit.duration)),
+            pid: PID,
+            tid,
+            args: json!({
+                "features": unit.features,
+                "critical": unit.critical,
+                "slack": unit.slack,
+            }),
+        });
+        for (name, section) in unit.sections.iter().flatten() {
+            events.push(TraceEvent {
+                name: name.name().into_owned(),
+                cat: Some("section"),
+                ph: "X",
+                ts: micros(unit.start + section.start),
+                dur: Some(micros(section.end - section.start).max(0.0)),
+                pid: PID,
+                tid,
+                args: serde_json::Value::Null,
+            });
+        }
+    }
+
+    for slot in 0..slot_ends.len() {
+        let tid = slot + 1;
+        let name = format!("job slot {tid}");
+        events.push(metadata("thread_name", tid, json!({ "name": name })));
+        events.push(metadata(
+            "thread_sort_index",
+            tid,
+            json!({ "sort_index": tid }),
+        ));
+    }
+
+    serde_json::to_writer(
+        &mut *f,
+        &json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        }),
+    )?;
+    writeln!(f)?;
+    Ok(())
+}
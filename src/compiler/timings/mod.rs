@@ -3,6 +3,7 @@
 //! This module implements some simple tracking information for timing of how
 //! long it takes for different units to compile.
 
+pub mod chrome_trace;
 pub mod report;
 
 use super::CompileMode;
@@ -76,6 +77,10 @@ pub struct UnitData {
     pub unblocked_units: Vec<UnitIndex>,
     pub unblocked_rmeta_units: Vec<UnitIndex>,
     pub sections: Option<Vec<(report::SectionName, report::SectionData)>>,
+    /// Whether this unit is on the critical path of the build.
+    pub critical: bool,
+    /// How long this unit could have been delayed without delaying the build.
+    pub slack: f64,
 }
 
 impl<'gctx> Timings<'gctx> {
@@ -25,7 +25,7 @@ pub enum SectionName {
 
 impl SectionName {
     /// Lower case name.
-    fn name(&self) -> Cow<'static, str> {
+    pub fn name(&self) -> Cow<'static, str> {
         match self {
             SectionName::Frontend => "frontend".into(),
             SectionName::Codegen => "codegen".into(),
@@ -121,12 +121,120 @@ pub struct RenderContext<'a> {
     pub num_cpus: Option<u64>,
     /// Fatal error during the build.
     pub error: &'a Option<anyhow::Error>,
+    /// Timing trends across previous sessions, if requested.
+    pub trends: Option<Trends>,
+}
+
+impl RenderContext<'_> {
+    /// Wall-clock time of the build in seconds.
+    pub fn duration(&self) -> f64 {
+        // The last concurrency record should equal to the last unit finished time.
+        self.concurrency.last().map(|c| c.t).unwrap_or(0.0)
+    }
+}
+
+/// Timing data aggregated across multiple build sessions.
+pub struct Trends {
+    /// Per-session summaries, from oldest to newest.
+    pub sessions: Vec<SessionTrend>,
+    /// Per-unit statistics, slowest median first.
+    pub units: Vec<UnitTrend>,
+}
+
+/// Summary of a single session in [`Trends`].
+pub struct SessionTrend {
+    /// A rendered string of when the session started.
+    pub start_str: String,
+    /// Wall-clock time of the session in seconds.
+    pub duration: f64,
+    /// Number of units compiled in the session.
+    pub units: usize,
+}
+
+/// Statistics of a single unit across sessions in [`Trends`].
+pub struct UnitTrend {
+    pub name: String,
+    pub version: String,
+    pub target: String,
+    /// Number of sessions that compiled this unit.
+    pub samples: usize,
+    /// Median duration in seconds.
+    pub median: f64,
+    /// 90th percentile duration in seconds.
+    pub p90: f64,
+    /// Duration in the most recent session that compiled this unit.
+    pub latest: f64,
+    /// Median time between rmeta generation and unit completion,
+    /// if any session recorded when rmeta was generated.
+    pub rmeta_gap: Option<f64>,
+}
+
+impl Trends {
+    /// Aggregates timing data of multiple sessions, ordered from oldest to newest.
+    pub fn new<'a>(sessions: impl IntoIterator<Item = &'a RenderContext<'a>>) -> Trends {
+        #[derive(Default)]
+        struct Samples {
+            durations: Vec<f64>,
+            rmeta_gaps: Vec<f64>,
+        }
+
+        let mut session_trends = Vec::new();
+        let mut samples: IndexMap<(&str, &str, &str), Samples> = IndexMap::default();
+        for ctx in sessions {
+            session_trends.push(SessionTrend {
+                start_str: ctx.start_str.clone(),
+                duration: ctx.duration(),
+                units: ctx.unit_data.len(),
+            });
+            for unit in &ctx.unit_data {
+                let entry = samples
+                    .entry((&unit.name, &unit.version, &unit.target))
+                    .or_default();
+                entry.durations.push(unit.duration);
+                if let Some(rmeta) = frontend_end(unit) {
+                    entry.rmeta_gaps.push((unit.duration - rmeta).max(0.0));
+                }
+            }
+        }
+
+        let units = samples
+            .into_iter()
+            .map(|((name, version, target), samples)| {
+                let latest = *samples.durations.last().unwrap();
+                let mut durations = samples.durations;
+                durations.sort_unstable_by(f64::total_cmp);
+                let mut rmeta_gaps = samples.rmeta_gaps;
+                rmeta_gaps.sort_unstable_by(f64::total_cmp);
+                UnitTrend {
+                    name: name.to_owned(),
+                    version: version.to_owned(),
+                    target: target.to_owned(),
+                    samples: durations.len(),
+                    median: percentile(&durations, 50.0),
+                    p90: percentile(&durations, 90.0),
+                    latest,
+                    rmeta_gap: (!rmeta_gaps.is_empty()).then(|| percentile(&rmeta_gaps, 50.0)),
+                }
+            })
+            .sorted_by(|a, b| b.median.total_cmp(&a.median))
+            .collect();
+
+        Trends {
+            sessions: session_trends,
+            units,
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted, non-empty samples.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
 /// Writes an HTML report.
 pub fn write_html(ctx: RenderContext<'_>, f: &mut impl Write) -> CargoResult<()> {
-    // The last concurrency record should equal to the last unit finished time.
-    let duration = ctx.concurrency.last().map(|c| c.t).unwrap_or(0.0);
+    let duration = ctx.duration();
     let roots: Vec<&str> = ctx
         .root_units
         .iter()
@@ -134,6 +242,9 @@ pub fn write_html(ctx: RenderContext<'_>, f: &mut impl Write) -> CargoResult<()>
         .collect();
     f.write_all(HTML_TMPL.replace("{ROOTS}", &roots.join(", ")).as_bytes())?;
     write_summary_table(&ctx, f, duration)?;
+    if let Some(trends) = &ctx.trends {
+        write_trend_tables(trends, f)?;
+    }
     f.write_all(HTML_CANVAS.as_bytes())?;
     write_unit_table(&ctx, f)?;
     // It helps with pixel alignment to use whole numbers.
@@ -187,6 +298,14 @@ fn write_summary_table(
 
     let requested_targets = ctx.requested_targets.join(", ");
 
+    let critical_path = ctx
+        .unit_data
+        .iter()
+        .filter(|u| u.critical)
+        .sorted_by(|a, b| a.start.total_cmp(&b.start))
+        .map(|u| format!("{} v{}{}", u.name, u.version, u.target))
+        .join(" &rarr;<br>");
+
     let error_msg = match ctx.error {
         Some(e) => format!(r#"<tr><td class="error-text">Error:</td><td>{e}</td></tr>"#),
         None => "".to_string(),
@@ -232,6 +351,9 @@ fn write_summary_table(
 <td>Total time:</td><td>{total_time}</td>
 </tr>
 <tr>
+<td>Critical path:</td><td>{critical_path}</td>
+</tr>
+<tr>
 <td>rustc:</td><td>{rustc_version}<br>Host: {host}<br>Target: {requested_targets}</td>
 </tr>
 {error_msg}
@@ -241,6 +363,89 @@ fn write_summary_table(
     Ok(())
 }
 
+/// Render the tables of timing trends across sessions.
+fn write_trend_tables(trends: &Trends, f: &mut impl Write) -> CargoResult<()> {
+    write!(
+        f,
+        r#"
+<h2>Trends across {} sessions</h2>
+<table class="my-table">
+<thead>
+<tr>
+  <th>Build start</th>
+  <th>Units</th>
+  <th>Total time</th>
+</tr>
+</thead>
+<tbody>
+"#,
+        trends.sessions.len()
+    )?;
+    for session in &trends.sessions {
+        write!(
+            f,
+            r#"
+<tr>
+<td>{}</td>
+<td>{}</td>
+<td>{:.1}s</td>
+</tr>
+"#,
+            session.start_str, session.units, session.duration
+        )?;
+    }
+    write!(
+        f,
+        r#"</tbody>
+</table>
+
+<table class="my-table">
+<thead>
+<tr>
+  <th></th>
+  <th>Unit</th>
+  <th>Sessions</th>
+  <th>Median</th>
+  <th>P90</th>
+  <th>Latest</th>
+  <th>Rmeta to finish</th>
+</tr>
+</thead>
+<tbody>
+"#
+    )?;
+    for (i, unit) in trends.units.iter().enumerate() {
+        let rmeta_gap = unit
+            .rmeta_gap
+            .map(|gap| format!("{gap:.1}s"))
+            .unwrap_or_default();
+        write!(
+            f,
+            r#"
+<tr>
+<td>{}.</td>
+<td>{} v{}{}</td>
+<td>{}</td>
+<td>{:.1}s</td>
+<td>{:.1}s</td>
+<td>{:.1}s</td>
+<td>{rmeta_gap}</td>
+</tr>
+"#,
+            i + 1,
+            unit.name,
+            unit.version,
+            unit.target,
+            unit.samples,
+            unit.median,
+            unit.p90,
+            unit.latest,
+        )?;
+    }
+    write!(f, "</tbody>\n</table>\n")?;
+    Ok(())
+}
+
 /// Write timing data in JavaScript. Primarily for `timings.js` to put data
 /// in a `<script>` HTML element to draw graphs.
 fn write_js_data(ctx: &RenderContext<'_>, f: &mut impl Write) -> CargoResult<()> {
@@ -292,6 +497,7 @@ fn write_unit_table(ctx: &RenderContext<'_>, f: &mut impl Write) -> CargoResult<
   <th></th>
   <th>Unit</th>
   <th>Total</th>
+  <th>Slack</th>
   {headers}
   <th>Features</th>
 </tr>
@@ -336,6 +542,7 @@ fn write_unit_table(ctx: &RenderContext<'_>, f: &mut impl Write) -> CargoResult<
 <td>{}.</td>
 <td>{}{}</td>
 <td>{:.1}s</td>
+<td>{:.1}s</td>
 {cells}
 <td>{features}</td>
 </tr>
@@ -344,6 +551,7 @@ fn write_unit_table(ctx: &RenderContext<'_>, f: &mut impl Write) -> CargoResult<
             format_args!("{} v{}", unit.name, unit.version),
             unit.target,
             unit.duration,
+            unit.slack,
         )?;
     }
     write!(f, "</tbody>\n</table>\n")?;
@@ -388,11 +596,7 @@ pub fn compute_concurrency(unit_data: &[UnitData]) -> Vec<Concurrency> {
         match dep {
             UnblockedBy::Rmeta(id) => {
                 let dep = unit_by_index.get(id)?;
-                let duration = dep.sections.iter().flatten().find_map(|(name, section)| {
-                    matches!(name, SectionName::Frontend).then_some(section.end)
-                });
-
-                Some(dep.start + duration.unwrap_or(dep.duration))
+                Some(dep.start + frontend_end(dep).unwrap_or(dep.duration))
             }
             UnblockedBy::Full(id) => {
                 let dep = unit_by_index.get(id)?;
@@ -475,6 +679,99 @@ pub fn compute_concurrency(unit_data: &[UnitData]) -> Vec<Concurrency> {
     concurrency
 }
 
+/// Computes the critical path through the unit graph and the slack of every unit.
+///
+/// The slack of a unit is how long it could have been delayed without delaying
+/// the end of the build, given the dependencies between units. A dependency that
+/// only requires metadata is satisfied once its frontend section ends.
+///
+/// `dependencies` maps each unit to its dependencies, paired with whether only
+/// the metadata of that dependency is required. Dependencies that were not built
+/// in this session are ignored.
+pub fn compute_critical_path(
+    unit_data: &mut [UnitData],
+    dependencies: &HashMap<UnitIndex, Vec<(UnitIndex, bool)>>,
+) {
+    let Some(last) = unit_data
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| (a.start + a.duration).total_cmp(&(b.start + b.duration)))
+        .map(|(pos, _)| pos)
+    else {
+        return;
+    };
+    let end = unit_data[last].start + unit_data[last].duration;
+
+    let position: HashMap<_, _> = unit_data
+        .iter()
+        .enumerate()
+        .map(|(pos, unit)| (unit.i, pos))
+        .collect();
+    let deps_of = |unit: &UnitData| {
+        dependencies
+            .get(&unit.i)
+            .into_iter()
+            .flatten()
+            .filter_map(|(dep, rmeta)| Some((*position.get(dep)?, *rmeta)))
+    };
+    // Time after the start of `dep` that its output is available to dependents.
+    let available_after = |dep: &UnitData, rmeta: bool| match frontend_end(dep) {
+        Some(frontend_end) if rmeta => frontend_end,
+        _ => dep.duration,
+    };
+
+    let mut dependents: Vec<Vec<(usize, bool)>> = vec![Vec::new(); unit_data.len()];
+    for (pos, unit) in unit_data.iter().enumerate() {
+        for (dep, rmeta) in deps_of(unit) {
+            dependents[dep].push((pos, rmeta));
+        }
+    }
+
+    // Dependents always start after their dependencies, so visiting units
+    // from the latest start makes sure dependents are computed first.
+    let mut order: Vec<usize> = (0..unit_data.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&unit_data[a], &unit_data[b]);
+        b.start
+            .total_cmp(&a.start)
+            .then_with(|| (b.start + b.duration).total_cmp(&(a.start + a.duration)))
+    });
+    let mut latest_start = vec![f64::INFINITY; unit_data.len()];
+    for pos in order {
+        let unit = &unit_data[pos];
+        let mut latest = end - unit.duration;
+        for &(dependent, rmeta) in &dependents[pos] {
+            latest = latest.min(latest_start[dependent] - available_after(unit, rmeta));
+        }
+        latest_start[pos] = latest;
+    }
+
+    // Walk back from the unit that finished last,
+    // following the dependency that became available last.
+    let mut current = last;
+    unit_data[current].critical = true;
+    while let Some((dep, _)) = deps_of(&unit_data[current]).max_by(|(a, a_rmeta), (b, b_rmeta)| {
+        let a = unit_data[*a].start + available_after(&unit_data[*a], *a_rmeta);
+        let b = unit_data[*b].start + available_after(&unit_data[*b], *b_rmeta);
+        a.total_cmp(&b)
+    }) {
+        current = dep;
+        unit_data[current].critical = true;
+    }
+
+    for (unit, latest_start) in unit_data.iter_mut().zip(latest_start) {
+        unit.slack = round_to_centisecond((latest_start - unit.start).max(0.0));
+    }
+}
+
+/// End of the frontend section of a unit, i.e., when its metadata was generated.
+pub fn frontend_end(unit: &UnitData) -> Option<f64> {
+    unit.sections
+        .iter()
+        .flatten()
+        .find_map(|(name, section)| matches!(name, SectionName::Frontend).then_some(section.end))
+}
+
 /// Aggregates section timing information from individual compilation sections.
 ///
 /// We can have a bunch of situations here.
@@ -771,3 +1068,52 @@ static HTML_CANVAS: &str = r#"
 <div id="pipeline-container" class="canvas-container"></div>
 <div id="timing-container" class="canvas-container"></div>
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(i: u64, start: f64, duration: f64, rmeta: Option<f64>) -> UnitData {
+        UnitData {
+            i: UnitIndex(i),
+            name: format!("unit{i}"),
+            version: "0.0.0".to_owned(),
+            mode: "todo".to_owned(),
+            target: String::new(),
+            features: Vec::new(),
+            start,
+            duration,
+            unblocked_units: Vec::new(),
+            unblocked_rmeta_units: Vec::new(),
+            sections: aggregate_sections(IndexMap::default(), duration, rmeta),
+            critical: false,
+            slack: 0.0,
+        }
+    }
+
+    #[test]
+    fn critical_path_with_pipelining() {
+        // 0 is pipelined into 1, which 2 fully depends on.
+        // 3 depends on 0 fully and finishes early.
+        let mut units = vec![
+            unit(0, 0.0, 4.0, Some(1.0)),
+            unit(1, 1.0, 5.0, Some(2.0)),
+            unit(3, 4.0, 1.0, None),
+            unit(2, 6.0, 2.0, None),
+        ];
+        let dependencies = [
+            (UnitIndex(1), vec![(UnitIndex(0), true)]),
+            (UnitIndex(2), vec![(UnitIndex(1), false)]),
+            (UnitIndex(3), vec![(UnitIndex(0), false)]),
+        ]
+        .into_iter()
+        .collect();
+
+        compute_critical_path(&mut units, &dependencies);
+
+        let critical: Vec<_> = units.iter().filter(|u| u.critical).map(|u| u.i).collect();
+        assert_eq!(critical, [UnitIndex(0), UnitIndex(1), UnitIndex(2)]);
+        let slack: Vec<_> = units.iter().map(|u| u.slack).collect();
+        assert_eq!(slack, [0.0, 0.0, 3.0, 0.0]);
+    }
+}
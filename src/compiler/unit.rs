@@ -150,6 +150,19 @@ impl UnitInner {
         self.mode.is_any_test() || self.target.kind().requires_upstream_objects()
     }
 
+    /// Returns whether this unit only requires the metadata file from `dep`
+    /// when depending on it.
+    pub fn only_requires_rmeta(&self, dep: &UnitInner) -> bool {
+        // We're only a candidate for requiring an `rmeta` file if we
+        // ourselves are building an rlib,
+        !self.requires_upstream_objects()
+            && self.mode == CompileMode::Build
+            // Our dependency must also be built as an rlib, otherwise the
+            // object code must be useful in some fashion
+            && !dep.requires_upstream_objects()
+            && dep.mode == CompileMode::Build
+    }
+
     /// Returns whether compilation of this unit could benefit from splitting metadata
     /// into a .rmeta file.
     pub fn benefits_from_no_embed_metadata(&self) -> bool {
@@ -78,6 +78,7 @@ use std::time::Instant;
 use self::ConfigValue as CV;
 use crate::compiler::rustdoc::RustdocExternMap;
 use crate::ops::RegistryCredentialConfig;
+use crate::resolver::AdvisoryDb;
 use crate::sources::CRATES_IO_INDEX;
 use crate::sources::CRATES_IO_REGISTRY;
 use crate::util::OnceExt as _;
@@ -278,6 +279,8 @@ pub struct GlobalContext {
     doc_extern_map: OnceLock<RustdocExternMap>,
     progress_config: ProgressConfig,
     env_config: OnceLock<Arc<HashMap<String, OsString>>>,
+    /// The advisory database of `resolver.advisory-db`, parsed once per process.
+    advisory_db: OnceLock<Option<Arc<AdvisoryDb>>>,
     /// This should be false if:
     /// - this is an artifact of the rustc distribution process for "stable" or for "beta"
     /// - this is an `#[test]` that does not opt in with `enable_nightly_features`
@@ -407,6 +410,7 @@ impl GlobalContext {
             doc_extern_map: Default::default(),
             progress_config: ProgressConfig::default(),
             env_config: Default::default(),
+            advisory_db: Default::default(),
             nightly_features_allowed: matches!(&*features::channel(), "nightly" | "dev"),
             ws_roots: Default::default(),
             global_cache_tracker: Default::default(),
@@ -485,6 +489,11 @@ impl GlobalContext {
         self.registry_base_path().join("src")
     }
 
+    /// Gets the Cargo build cache directory (`<cargo_home>/build-cache`).
+    pub fn build_cache_path(&self) -> Filesystem {
+        self.home_path.join("build-cache")
+    }
+
     /// Gets the default Cargo registry.
     pub fn default_registry(&self) -> CargoResult<Option<String>> {
         Ok(self
@@ -2020,6 +2029,16 @@ impl GlobalContext {
         Ok(env_config)
     }
 
+    /// The advisory database configured with `resolver.advisory-db`.
+    ///
+    /// Returns `None` without `-Zadvisories` or when no database is configured.
+    pub fn advisory_db(&self) -> CargoResult<Option<&Arc<AdvisoryDb>>> {
+        let db = self
+            .advisory_db
+            .try_borrow_with(|| CargoResult::Ok(AdvisoryDb::read(self)?.map(Arc::new)))?;
+        Ok(db.as_ref())
+    }
+
     /// This is used to validate the `term` table has valid syntax.
     ///
     /// This is necessary because loading the term settings happens very
@@ -218,6 +218,8 @@ pub struct CargoBuildConfig {
     pub sbom: Option<bool>,
     /// Unstable feature `-Zbuild-analysis`.
     pub analysis: Option<CargoBuildAnalysis>,
+    /// Unstable feature `-Zbuild-cache`.
+    pub cache_url: Option<String>,
 }
 
 /// Metrics collection for build analysis.
@@ -310,6 +312,7 @@ impl BuildTargetConfig {
 /// incompatible-publish-age = "deny"
 /// feature-unification = "workspace"
 /// lockfile-path = "my/Cargo.lock"
+/// hold = { tokio = "~1.38" }
 /// ```
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -318,6 +321,12 @@ pub struct CargoResolverConfig {
     pub incompatible_publish_age: Option<IncompatiblePublishAge>,
     pub feature_unification: Option<FeatureUnification>,
     pub lockfile_path: Option<ConfigRelativePath>,
+    /// Unstable feature `-Zadvisories`.
+    pub advisory_db: Option<ConfigRelativePath>,
+    /// Unstable feature `-Zadvisories`.
+    pub incompatible_advisories: Option<IncompatibleAdvisories>,
+    /// Unstable feature `-Zresolver-hold`.
+    pub hold: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -334,6 +343,14 @@ pub enum IncompatiblePublishAge {
     Deny,
 }
 
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IncompatibleAdvisories {
+    Allow,
+    Fallback,
+    Deny,
+}
+
 #[derive(Copy, Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum FeatureUnification {
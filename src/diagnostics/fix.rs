@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+/// A machine-applicable suggestion for a manifest, recorded by a lint
+///
+/// See [`super::ScopedDiagnosticStats::record_fix`]
+#[derive(Clone, Debug)]
+pub struct ManifestFix {
+    /// The manifest to edit
+    pub manifest_path: PathBuf,
+    /// Edits that must be applied together for the fix to be correct
+    pub edits: Vec<ManifestEdit>,
+}
+
+/// An edit to the item at `key_path`, the keys from the root of the document
+#[derive(Clone, Debug)]
+pub enum ManifestEdit {
+    /// Remove the key and its value
+    Remove { key_path: Vec<String> },
+    /// Rename the key, keeping its value
+    Rename { key_path: Vec<String>, new: String },
+    /// Replace the value of the key
+    Set {
+        key_path: Vec<String>,
+        value: toml_edit::Value,
+    },
+}
+
+impl ManifestFix {
+    pub fn new(manifest_path: impl Into<PathBuf>) -> ManifestFix {
+        ManifestFix {
+            manifest_path: manifest_path.into(),
+            edits: Vec::new(),
+        }
+    }
+
+    pub fn edit(mut self, edit: ManifestEdit) -> ManifestFix {
+        self.edits.push(edit);
+        self
+    }
+}
+
+impl ManifestEdit {
+    pub fn remove(key_path: &[&str]) -> ManifestEdit {
+        ManifestEdit::Remove {
+            key_path: to_key_path(key_path),
+        }
+    }
+
+    pub fn rename(key_path: &[&str], new: impl Into<String>) -> ManifestEdit {
+        ManifestEdit::Rename {
+            key_path: to_key_path(key_path),
+            new: new.into(),
+        }
+    }
+
+    pub fn set(key_path: &[&str], value: impl Into<toml_edit::Value>) -> ManifestEdit {
+        ManifestEdit::Set {
+            key_path: to_key_path(key_path),
+            value: value.into(),
+        }
+    }
+
+    pub fn key_path(&self) -> &[String] {
+        match self {
+            ManifestEdit::Remove { key_path }
+            | ManifestEdit::Rename { key_path, .. }
+            | ManifestEdit::Set { key_path, .. } => key_path,
+        }
+    }
+
+    fn apply(&self, document: &mut toml_edit::DocumentMut) -> bool {
+        let Some((key, parent_path)) = self.key_path().split_last() else {
+            return false;
+        };
+        let mut parent: &mut dyn toml_edit::TableLike = document.as_table_mut();
+        for parent_key in parent_path {
+            let Some(table) = parent
+                .get_mut(parent_key)
+                .and_then(|t| t.as_table_like_mut())
+            else {
+                return false;
+            };
+            parent = table;
+        }
+
+        match self {
+            ManifestEdit::Remove { .. } => parent.remove(key).is_some(),
+            ManifestEdit::Rename { new, .. } => {
+                if parent.contains_key(new) {
+                    return false;
+                }
+                let keys = parent.iter().map(|(k, _)| k.to_owned()).collect::<Vec<_>>();
+                let Some(position) = keys.iter().position(|k| k == key) else {
+                    return false;
+                };
+                // Re-insert the keys that follow so the renamed key keeps its place
+                for (i, k) in keys[position..].iter().enumerate() {
+                    let mut formatted = parent.key(k).cloned().expect("key exists");
+                    let item = parent.remove(k).expect("key exists");
+                    if i == 0 {
+                        let mut renamed = toml_edit::Key::new(new.as_str());
+                        *renamed.dotted_decor_mut() = formatted.dotted_decor().clone();
+                        *renamed.leaf_decor_mut() = formatted.leaf_decor().clone();
+                        formatted = renamed;
+                    }
+                    parent.entry_format(&formatted).or_insert(item);
+                }
+                true
+            }
+            ManifestEdit::Set { value, .. } => {
+                let Some(existing) = parent.get_mut(key).and_then(|v| v.as_value_mut()) else {
+                    return false;
+                };
+                if existing.to_string().trim() == value.to_string().trim() {
+                    return false;
+                }
+                let mut replacement = value.clone();
+                *replacement.decor_mut() = existing.decor().clone();
+                *existing = replacement;
+                true
+            }
+        }
+    }
+}
+
+/// Apply the fixes to a manifest, preserving the formatting of the rest of the document
+///
+/// Returns the number of fixes that changed the document.
+pub fn apply_fixes(document: &mut toml_edit::DocumentMut, fixes: &[&ManifestFix]) -> usize {
+    let mut changed = 0;
+    for fix in fixes {
+        let mut fix_changed = false;
+        for edit in &fix.edits {
+            fix_changed |= edit.apply(document);
+        }
+        if fix_changed {
+            changed += 1;
+        }
+    }
+    changed
+}
+
+fn to_key_path(key_path: &[&str]) -> Vec<String> {
+    key_path.iter().map(|k| (*k).to_owned()).collect()
+}
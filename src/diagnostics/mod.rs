@@ -18,7 +18,7 @@
 //!
 //! The mechanics of adding a diagnostic is dependent on the requirements:
 //! - TOML syntax or manifest schema: [`passes::emit_parse_diagnostics`], [`rules::PARSE_PASS_RULES`]
-//! - Lockfile
+//! - Lockfile: [`passes::emit_lockfile_diagnostics`], [`rules::LOCKFILE_PASS_RULES`]
 //!   - May be overly broad for what dependencies are checked
 //! - Pre-build unit graph
 //!   - Tailored to a specific configuration (features, targets) but requires users to enumerate every configuration
@@ -52,33 +52,46 @@
 //!
 //! See [`passes::emit_parse_diagnostics`] as an example.
 //!
+//! # Suggesting a fix
+//!
+//! When a lint's help can be applied mechanically to the manifest, record it with
+//! [`ScopedDiagnosticStats::record_fix`] so `cargo fix` can apply it.
+//! Only record a fix when applying it preserves the meaning of the manifest.
+//!
 //! [future-incompat lint]: https://rustc-dev-guide.rust-lang.org/diagnostics.html#future-incompatible-lints
 
 use cargo_util_schemas::manifest::RustVersion;
 use cargo_util_schemas::manifest::TomlToolLints;
+use cargo_util_terminal::report::Report;
 
 use crate::CargoResult;
 use crate::util::GlobalContext;
 use crate::workspace::Workspace;
 use crate::workspace::{Edition, Features, MaybePackage, Package};
 
+mod fix;
 mod lint;
 mod report;
 
 pub mod passes;
 pub mod rules;
 
+pub use fix::{ManifestEdit, ManifestFix, apply_fixes};
 pub use lint::{Lint, LintGroup, LintLevel, LintLevelProduct, LintLevelSource};
 pub use report::{AsIndex, cwd_rel_path, get_key_value, get_key_value_span, workspace_rel_path};
 pub use rules::{LINT_GROUPS, LINTS};
 
 pub struct PassOutput {
     pub lint_warning_count: usize,
+    pub fixes: Vec<ManifestFix>,
 }
 
 pub struct GlobalDiagnosticStats {
     error_count: usize,
     lint_warning_count: usize,
+    fixes: Vec<ManifestFix>,
+    /// Only record fixes, without reporting to the user
+    collect_only: bool,
 }
 
 impl GlobalDiagnosticStats {
@@ -86,13 +99,28 @@ impl GlobalDiagnosticStats {
         Self {
             error_count: 0,
             lint_warning_count: 0,
+            fixes: Vec::new(),
+            collect_only: false,
+        }
+    }
+
+    /// Stats for evaluating rules for their fixes, without reporting to the user
+    pub fn collect_only() -> Self {
+        Self {
+            collect_only: true,
+            ..Self::new()
         }
     }
 
+    pub fn is_collect_only(&self) -> bool {
+        self.collect_only
+    }
+
     pub fn scope(&mut self) -> ScopedDiagnosticStats<'_> {
         ScopedDiagnosticStats {
             warning_count: 0,
             error_count: 0,
+            fix_count: 0,
             global: self,
         }
     }
@@ -105,7 +133,11 @@ impl GlobalDiagnosticStats {
         self.lint_warning_count
     }
 
-    pub fn ok(&self) -> CargoResult<PassOutput> {
+    pub fn into_fixes(self) -> Vec<ManifestFix> {
+        self.fixes
+    }
+
+    pub fn ok(self) -> CargoResult<PassOutput> {
         if 0 < self.error_count {
             Err(crate::Error::new(crate::AlreadyPrintedError::new(
                 anyhow::format_err!("see above"),
@@ -113,6 +145,7 @@ impl GlobalDiagnosticStats {
         } else {
             Ok(PassOutput {
                 lint_warning_count: self.lint_warning_count,
+                fixes: self.fixes,
             })
         }
     }
@@ -121,6 +154,7 @@ impl GlobalDiagnosticStats {
 pub struct ScopedDiagnosticStats<'g> {
     warning_count: usize,
     error_count: usize,
+    fix_count: usize,
     global: &'g mut GlobalDiagnosticStats,
 }
 
@@ -142,6 +176,25 @@ impl ScopedDiagnosticStats<'_> {
         self.global.error_count += 1;
     }
 
+    /// Record a machine-applicable fix for the lint being reported
+    pub fn record_fix(&mut self, fix: ManifestFix) {
+        self.fix_count += 1;
+        self.global.fixes.push(fix);
+    }
+
+    /// Print a diagnostic to the user, unless only collecting fixes
+    pub fn print_report(
+        &self,
+        report: Report<'_>,
+        force: bool,
+        gctx: &GlobalContext,
+    ) -> CargoResult<()> {
+        if self.global.collect_only {
+            return Ok(());
+        }
+        gctx.shell().print_report(report, force)
+    }
+
     pub fn record_lint(&mut self, lint: LintLevel) {
         match lint {
             LintLevel::Forbid | LintLevel::Deny => {
@@ -164,13 +217,26 @@ impl ScopedDiagnosticStats<'_> {
         name: Option<&str>,
         gctx: &GlobalContext,
     ) -> CargoResult<()> {
+        if self.global.collect_only {
+            return Ok(());
+        }
+
         if 0 < self.warning_count {
             let plural = if self.warning_count == 1 { "" } else { "s" };
             let name = name
                 .map(|n| format!("`{n}`"))
                 .unwrap_or_else(|| "workspace".to_owned());
+            let fix_hint = if 0 < self.fix_count {
+                let fix_plural = if self.fix_count == 1 { "" } else { "s" };
+                format!(
+                    " (run `cargo fix` to apply {} suggestion{fix_plural})",
+                    self.fix_count
+                )
+            } else {
+                String::new()
+            };
             gctx.shell().warn(format!(
-                "{name} (manifest) generated {} warning{plural}",
+                "{name} (manifest) generated {} warning{plural}{fix_hint}",
                 self.warning_count
             ))?;
         }
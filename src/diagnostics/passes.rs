@@ -1,16 +1,32 @@
+use std::cell::OnceCell;
+use std::cell::RefCell;
 use std::path::Path;
+use std::rc::Rc;
 
 use cargo_util_schemas::manifest;
 
 use crate::CargoResult;
 use crate::GlobalContext;
+use crate::compiler::CompileKind;
+use crate::compiler::RustcTargetData;
 use crate::diagnostics::GlobalDiagnosticStats;
 use crate::diagnostics::Lint;
 use crate::diagnostics::LintLevel;
 use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ManifestFix;
 use crate::diagnostics::ManifestFor;
 use crate::diagnostics::PassOutput;
 use crate::diagnostics::ScopedDiagnosticStats;
+use crate::ops;
+use crate::ops::WorkspaceResolve;
+use crate::resolver::Resolve;
+use crate::resolver::ResolveBehavior;
+use crate::resolver::features::CliFeatures;
+use crate::resolver::features::FeatureOpts;
+use crate::resolver::features::FeatureResolver;
+use crate::resolver::features::HasDevUnits;
+use crate::resolver::features::ResolvedFeatures;
+use crate::workspace::FeatureValue;
 use crate::workspace::MaybePackage;
 use crate::workspace::Package;
 use crate::workspace::Workspace;
@@ -40,6 +56,18 @@ pub enum ParsePassRule<'r> {
     },
 }
 
+impl ParsePassRule<'_> {
+    /// Hard-coded diagnostics do not suggest fixes
+    fn is_diagnostic(&self) -> bool {
+        matches!(
+            self,
+            ParsePassRule::DiagnosticManifest { .. }
+                | ParsePassRule::DiagnosticWorkspace { .. }
+                | ParsePassRule::DiagnosticPackage { .. }
+        )
+    }
+}
+
 type FnDiagnosticManifest = fn(
     &Workspace<'_>,
     ManifestFor<'_>,
@@ -91,26 +119,274 @@ type FnLintPackage = fn(
     &GlobalContext,
 ) -> CargoResult<()>;
 
+#[derive(Clone)]
+pub enum LockfilePassRule<'r> {
+    LintPackage {
+        rule: FnLintLockedPackage,
+        lint: &'r Lint,
+    },
+}
+
+type FnLintLockedPackage = fn(
+    &Workspace<'_>,
+    &Package,
+    &LockfilePass<'_, '_>,
+    &Path,
+    LintLevelProduct,
+    &mut ScopedDiagnosticStats<'_>,
+    &GlobalContext,
+) -> CargoResult<()>;
+
 pub fn emit_parse_diagnostics(
     workspace: &Workspace<'_>,
     rules: &[ParsePassRule<'_>],
 ) -> CargoResult<PassOutput> {
     let mut stats = GlobalDiagnosticStats::new();
+    emit_parse_diagnostics_inner(workspace, rules, &mut stats)?;
+    stats.ok()
+}
+
+/// Evaluate the rules without reporting, for the fixes they suggest
+///
+/// Unlike [`emit_parse_diagnostics`], this does not fail on errors as those are reported when the
+/// manifests are next evaluated.
+pub fn collect_parse_fixes(
+    workspace: &Workspace<'_>,
+    rules: &[ParsePassRule<'_>],
+) -> CargoResult<Vec<ManifestFix>> {
+    let mut stats = GlobalDiagnosticStats::collect_only();
+    emit_parse_diagnostics_inner(workspace, rules, &mut stats)?;
+    Ok(stats.into_fixes())
+}
+
+/// Resolved workspace state shared by the [`LockfilePassRule`]s
+pub struct LockfilePass<'a, 'gctx> {
+    ws: &'a Workspace<'gctx>,
+    ws_resolve: &'a WorkspaceResolve<'gctx>,
+    target_data: RefCell<&'a mut RustcTargetData<'gctx>>,
+    requested_kinds: &'a [CompileKind],
+    member_features: OnceCell<ResolvedFeatures>,
+}
+
+impl<'a, 'gctx> LockfilePass<'a, 'gctx> {
+    pub fn new(
+        ws: &'a Workspace<'gctx>,
+        ws_resolve: &'a WorkspaceResolve<'gctx>,
+        target_data: &'a mut RustcTargetData<'gctx>,
+        requested_kinds: &'a [CompileKind],
+    ) -> Self {
+        Self {
+            ws,
+            ws_resolve,
+            target_data: RefCell::new(target_data),
+            requested_kinds,
+            member_features: OnceCell::new(),
+        }
+    }
 
+    /// Dependencies selected for the packages being built
+    pub fn resolve(&self) -> &'a Resolve {
+        &self.ws_resolve.targeted_resolve
+    }
+
+    /// Dependencies selected for `member` on its own with the lowest compatible versions, like
+    /// with `-Zminimal-versions`
+    ///
+    /// This ignores `Cargo.lock` and returns `None` when the lower bounds do not resolve.
+    pub fn resolve_minimal_versions(&self, member: &Package) -> CargoResult<Option<Resolve>> {
+        let gctx = self.ws.gctx();
+        let mut member_ws = Workspace::ephemeral(member.clone(), gctx, None, true)?;
+        member_ws.set_resolve_minimal_versions(true);
+        let mut registry = member_ws.package_registry()?;
+        let resolve = ops::resolve_with_previous(
+            &mut registry,
+            &member_ws,
+            &CliFeatures::new_all(true),
+            HasDevUnits::Yes,
+            None,
+            None,
+            &[],
+            true,
+        );
+        match resolve {
+            Ok(resolve) => Ok(Some(resolve)),
+            Err(e) => {
+                tracing::debug!(
+                    "failed to resolve `{}` with minimal versions: {e:#}",
+                    member.name()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Features enabled by building each workspace member on its own, with its default features
+    /// and the `required-features` of its targets
+    ///
+    /// Features are unified across platforms and dependency kinds and are resolved on first use.
+    pub fn member_features(&self) -> CargoResult<&ResolvedFeatures> {
+        if let Some(features) = self.member_features.get() {
+            return Ok(features);
+        }
+        let features = self.resolve_member_features()?;
+        Ok(self.member_features.get_or_init(|| features))
+    }
+
+    fn resolve_member_features(&self) -> CargoResult<ResolvedFeatures> {
+        let resolve = self
+            .ws_resolve
+            .workspace_resolve
+            .as_ref()
+            .unwrap_or(&self.ws_resolve.targeted_resolve);
+        let mut target_data = self.target_data.borrow_mut();
+        let opts = || FeatureOpts::new_behavior(ResolveBehavior::V1, HasDevUnits::Yes);
+
+        let mut member_features = ResolvedFeatures {
+            activated_features: Default::default(),
+            activated_dependencies: Default::default(),
+            opts: opts(),
+        };
+        for member in self.ws.members() {
+            if !resolve.contains(&member.package_id()) {
+                continue;
+            }
+            let resolved = FeatureResolver::resolve(
+                self.ws,
+                &mut target_data,
+                resolve,
+                &self.ws_resolve.pkg_set,
+                &required_features(self.ws, member),
+                &[member.package_id().to_spec()],
+                self.requested_kinds,
+                opts(),
+            )?;
+            for (key, features) in resolved.activated_features {
+                member_features
+                    .activated_features
+                    .entry(key)
+                    .or_default()
+                    .extend(features);
+            }
+            for (key, deps) in resolved.activated_dependencies {
+                member_features
+                    .activated_dependencies
+                    .entry(key)
+                    .or_default()
+                    .extend(deps);
+            }
+        }
+        Ok(member_features)
+    }
+}
+
+/// The `required-features` of `member`'s targets, as requested on the command-line
+fn required_features(ws: &Workspace<'_>, member: &Package) -> CliFeatures {
+    let summary = member.summary();
+    let is_current = ws.current_opt() == Some(member);
+    let features = member
+        .targets()
+        .iter()
+        .filter_map(|target| target.required_features())
+        .flatten()
+        .map(|feature| FeatureValue::new(feature.as_str().into()))
+        // Unknown features are reported when building the target
+        .filter_map(|value| match value {
+            FeatureValue::Feature(feature) if summary.features().contains_key(&feature) => {
+                // Outside of the current package, older workspaces only apply `member/feat`
+                if ws.allows_new_cli_feature_behavior() || is_current {
+                    Some(value)
+                } else {
+                    Some(FeatureValue::DepFeature {
+                        dep_name: member.name(),
+                        dep_feature: feature,
+                        weak: false,
+                    })
+                }
+            }
+            FeatureValue::DepFeature { dep_name, .. }
+                if summary
+                    .dependencies()
+                    .iter()
+                    .any(|dep| dep.name_in_toml() == dep_name) =>
+            {
+                Some(value)
+            }
+            _ => None,
+        })
+        .collect();
+    CliFeatures {
+        features: Rc::new(features),
+        all_features: false,
+        uses_default_features: true,
+    }
+}
+
+/// Evaluate the rules against the dependencies resolved for the workspace members
+pub fn emit_lockfile_diagnostics(
+    workspace: &Workspace<'_>,
+    pass: &LockfilePass<'_, '_>,
+    rules: &[LockfilePassRule<'_>],
+) -> CargoResult<PassOutput> {
+    let mut stats = GlobalDiagnosticStats::new();
+    if !workspace.gctx().cli_unstable().cargo_lints {
+        return stats.ok();
+    }
+
+    for pkg in workspace.members() {
+        if !is_local_package(pkg) {
+            continue;
+        }
+        let mut pkg_stats = stats.scope();
+        let cargo_lints = pkg_cargo_lints(pkg);
+        let path = pkg.manifest_path();
+        for rule in rules {
+            match rule {
+                LockfilePassRule::LintPackage { rule, lint } => {
+                    let level = lint.level(
+                        &cargo_lints,
+                        pkg.rust_version(),
+                        pkg.manifest().unstable_features(),
+                        workspace.gctx(),
+                    );
+                    if level.level != LintLevel::Allow {
+                        rule(
+                            workspace,
+                            pkg,
+                            pass,
+                            &path,
+                            level,
+                            &mut pkg_stats,
+                            workspace.gctx(),
+                        )?;
+                    }
+                }
+            }
+        }
+        pkg_stats.report_summary("resolve", Some(&*pkg.name()), workspace.gctx())?;
+    }
+
+    stats.ok()
+}
+
+fn emit_parse_diagnostics_inner(
+    workspace: &Workspace<'_>,
+    rules: &[ParsePassRule<'_>],
+    stats: &mut GlobalDiagnosticStats,
+) -> CargoResult<()> {
     if is_local_workspace(workspace) {
-        emit_parse_ws_diagnostics(workspace, rules, &mut stats)?;
+        emit_parse_ws_diagnostics(workspace, rules, stats)?;
     }
 
     for maybe_pkg in workspace.loaded_maybe() {
         if let MaybePackage::Package(pkg) = maybe_pkg {
             if is_local_package(pkg) {
                 let path = pkg.manifest_path();
-                emit_parse_pkg_diagnostics(workspace, pkg, &path, rules, &mut stats)?;
+                emit_parse_pkg_diagnostics(workspace, pkg, &path, rules, stats)?;
             }
         }
     }
 
-    stats.ok()
+    Ok(())
 }
 
 fn is_local_workspace(workspace: &Workspace<'_>) -> bool {
@@ -132,21 +408,15 @@ fn emit_parse_pkg_diagnostics(
     rules: &[ParsePassRule<'_>],
     global_stats: &mut GlobalDiagnosticStats,
 ) -> CargoResult<()> {
+    let collect_only = global_stats.is_collect_only();
     let mut pkg_stats = global_stats.scope();
 
-    let toml_lints = pkg
-        .manifest()
-        .normalized_toml()
-        .lints
-        .clone()
-        .map(|lints| lints.lints)
-        .unwrap_or(manifest::TomlLints::default());
-    let cargo_lints = toml_lints
-        .get("cargo")
-        .cloned()
-        .unwrap_or(manifest::TomlToolLints::default());
+    let cargo_lints = pkg_cargo_lints(pkg);
 
     for rule in rules {
+        if collect_only && rule.is_diagnostic() {
+            continue;
+        }
         match rule {
             ParsePassRule::DiagnosticManifest { rule } => {
                 let manifest = pkg.into();
@@ -201,11 +471,26 @@ fn emit_parse_pkg_diagnostics(
     Ok(())
 }
 
+fn pkg_cargo_lints(pkg: &Package) -> manifest::TomlToolLints {
+    let toml_lints = pkg
+        .manifest()
+        .normalized_toml()
+        .lints
+        .clone()
+        .map(|lints| lints.lints)
+        .unwrap_or(manifest::TomlLints::default());
+    toml_lints
+        .get("cargo")
+        .cloned()
+        .unwrap_or(manifest::TomlToolLints::default())
+}
+
 fn emit_parse_ws_diagnostics(
     workspace: &Workspace<'_>,
     rules: &[ParsePassRule<'_>],
     global_stats: &mut GlobalDiagnosticStats,
 ) -> CargoResult<()> {
+    let collect_only = global_stats.is_collect_only();
     let mut pkg_stats = global_stats.scope();
 
     let cargo_lints = match workspace.root_maybe() {
@@ -230,6 +515,9 @@ fn emit_parse_ws_diagnostics(
     .unwrap_or(manifest::TomlToolLints::default());
 
     for rule in rules {
+        if collect_only && rule.is_diagnostic() {
+            continue;
+        }
         match rule {
             ParsePassRule::DiagnosticManifest { rule } => {
                 let manifest = (workspace, workspace.root_maybe()).into();
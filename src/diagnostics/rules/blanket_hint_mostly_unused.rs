@@ -170,7 +170,7 @@ pub(crate) fn lint_workspace(
         report.insert(0, primary_group);
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
@@ -72,7 +72,7 @@ pub(crate) fn lint_package(
         let report = &[desc.element(Level::NOTE.message(&emitted_source))];
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(report, lint_level.force())?;
+        pkg_stats.print_report(report, lint_level.force(), gctx)?;
     }
     Ok(())
 }
@@ -0,0 +1,354 @@
+use std::ops::Range;
+use std::path::Path;
+
+use cargo_util_schemas::manifest::RustVersion;
+use cargo_util_terminal::report::AnnotationKind;
+use cargo_util_terminal::report::Group;
+use cargo_util_terminal::report::Level;
+use cargo_util_terminal::report::Origin;
+use cargo_util_terminal::report::Snippet;
+use tracing::instrument;
+
+use super::PEDANTIC;
+use crate::CargoResult;
+use crate::GlobalContext;
+use crate::diagnostics::Lint;
+use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ManifestFor;
+use crate::diagnostics::ScopedDiagnosticStats;
+use crate::diagnostics::get_key_value_span;
+use crate::diagnostics::passes::LockfilePass;
+use crate::diagnostics::workspace_rel_path;
+use crate::workspace::MaybePackage;
+use crate::workspace::Package;
+use crate::workspace::Workspace;
+use crate::workspace::dependency::DepKind;
+
+pub static LINT: &Lint = &Lint {
+    name: "incompatible_rust_version",
+    desc: "incompatible with `package.rust-version`",
+    primary_group: &PEDANTIC,
+    msrv: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+
+Checks for manifest keys and dependencies that require a newer Rust version than
+`package.rust-version`.
+
+Manifest keys are checked against the Rust version that stabilized them, like
+- `[lints]`: 1.74
+- `resolver = "3"`: 1.84
+- `dep:` and `?` in `[features]`: 1.60
+
+Dependencies are checked against the `rust-version` of the version selected by the resolver.
+`[dev-dependencies]` are not checked as they are not built by dependents.
+
+### Why is this bad?
+
+Older toolchains that `package.rust-version` claims to support ignore or reject the manifest key or
+fail to build the dependency.
+This is found by users of the old toolchain, rather than when the change is made.
+
+### Drawbacks
+
+Only the manifest keys known to this lint are checked.
+
+### Example
+
+```toml
+[package]
+name = "foo"
+rust-version = "1.70"
+
+[lints.rust]
+unsafe_code = "forbid"
+```
+
+Should be written as:
+
+```toml
+[package]
+name = "foo"
+rust-version = "1.74"
+
+[lints.rust]
+unsafe_code = "forbid"
+```
+"#,
+    ),
+};
+
+const LINTS_TABLE: RustVersion = RustVersion::new(1, 74, 0);
+const WORKSPACE_INHERITANCE: RustVersion = RustVersion::new(1, 64, 0);
+const NAMESPACED_FEATURES: RustVersion = RustVersion::new(1, 60, 0);
+const RESOLVER_2: RustVersion = RustVersion::new(1, 51, 0);
+const RESOLVER_3: RustVersion = RustVersion::new(1, 84, 0);
+const AUTOLIB: RustVersion = RustVersion::new(1, 83, 0);
+
+const DEPENDENCY_TABLES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+#[instrument(skip_all)]
+pub(crate) fn lint_manifest(
+    ws: &Workspace<'_>,
+    manifest: ManifestFor<'_>,
+    manifest_path: &Path,
+    level: LintLevelProduct,
+    pkg_stats: &mut ScopedDiagnosticStats<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let LintLevelProduct {
+        level: lint_level,
+        source,
+    } = level;
+
+    let rust_version_note = match &manifest {
+        ManifestFor::Package(_) => "`package.rust-version` is",
+        ManifestFor::Workspace {
+            maybe_pkg: MaybePackage::Virtual(_),
+            ..
+        } => "the lowest `package.rust-version` in the workspace is",
+        ManifestFor::Workspace {
+            maybe_pkg: MaybePackage::Package(_),
+            ..
+        } => {
+            // For real manifests, lint as a package, rather than a workspace
+            return Ok(());
+        }
+    };
+    let Some(rust_version) = manifest.rust_version() else {
+        return Ok(());
+    };
+    let (Some(document), Some(contents)) = (manifest.document(), manifest.contents()) else {
+        return Ok(());
+    };
+    let manifest_path = workspace_rel_path(ws, manifest_path);
+
+    let mut incompatible = incompatible_keys(document.get_ref());
+    incompatible.retain(|(_, required)| !required.is_compatible_with(&rust_version.to_partial()));
+    incompatible.sort_by_key(|(span, _)| span.start);
+
+    for (i, (span, required)) in incompatible.into_iter().enumerate() {
+        let level = lint_level.to_diagnostic_level();
+        let emitted_source = LINT.emitted_source(lint_level, source);
+
+        let mut primary = Group::with_title(level.primary_title(LINT.desc))
+            .element(
+                Snippet::source(contents).path(&manifest_path).annotation(
+                    AnnotationKind::Primary
+                        .span(span)
+                        .label(format!("requires Rust {required}")),
+                ),
+            )
+            .element(Level::NOTE.message(format!("{rust_version_note} {rust_version}")));
+        if i == 0 {
+            primary = primary.element(Level::NOTE.message(emitted_source));
+        }
+        let report = [
+            primary,
+            Group::with_title(Level::HELP.secondary_title(format!(
+                "consider raising `package.rust-version` to {required}"
+            ))),
+        ];
+
+        pkg_stats.record_lint(lint_level);
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
+    }
+
+    Ok(())
+}
+
+/// Lint dependencies whose selected version requires a newer Rust than the package
+#[instrument(skip_all)]
+pub(crate) fn lint_locked_package(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    pass: &LockfilePass<'_, '_>,
+    manifest_path: &Path,
+    level: LintLevelProduct,
+    pkg_stats: &mut ScopedDiagnosticStats<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let LintLevelProduct {
+        level: lint_level,
+        source,
+    } = level;
+
+    let Some(rust_version) = pkg.rust_version() else {
+        return Ok(());
+    };
+    let resolve = pass.resolve();
+    let manifest_path = workspace_rel_path(ws, manifest_path);
+    let manifest = pkg.manifest();
+    let document = manifest.document();
+    let contents = manifest.contents();
+
+    let mut emitted = 0;
+    for (dep_id, deps) in resolve.deps_not_replaced(pkg.package_id()) {
+        let Some(dep) = deps
+            .iter()
+            .filter(|dep| dep.kind() != DepKind::Development)
+            .min_by_key(|dep| (dep.kind(), dep.platform().is_some()))
+        else {
+            continue;
+        };
+        let Some(required) = resolve.summary(dep_id).rust_version() else {
+            continue;
+        };
+        if required.is_compatible_with(&rust_version.to_partial()) {
+            continue;
+        }
+
+        let level = lint_level.to_diagnostic_level();
+        let emitted_source = LINT.emitted_source(lint_level, source);
+
+        let mut dep_path = Vec::new();
+        if let Some(platform) = dep.platform() {
+            dep_path.push("target".to_owned());
+            dep_path.push(platform.to_string());
+        }
+        dep_path.push(dep.kind().kind_table().to_owned());
+        dep_path.push(dep.name_in_toml().to_string());
+
+        let label = format!(
+            "`{} v{}` requires Rust {required}",
+            dep_id.name(),
+            dep_id.version()
+        );
+        let mut primary = Group::with_title(level.primary_title(LINT.desc));
+        if let Some(document) = document
+            && let Some(contents) = contents
+            && let Some(span) = get_key_value_span(document, &dep_path)
+        {
+            primary = primary.element(
+                Snippet::source(contents)
+                    .path(&manifest_path)
+                    .annotation(AnnotationKind::Primary.span(span.key).label(label)),
+            );
+        } else {
+            primary = primary
+                .element(Origin::path(&manifest_path))
+                .element(Level::NOTE.message(label));
+        }
+        primary = primary
+            .element(Level::NOTE.message(format!("`package.rust-version` is {rust_version}")));
+        if emitted == 0 {
+            primary = primary.element(Level::NOTE.message(emitted_source));
+        }
+        let report = [
+            primary,
+            Group::with_title(Level::HELP.secondary_title(format!(
+                "consider raising `package.rust-version` to {required} or selecting an older version of `{}`",
+                dep_id.name()
+            ))),
+        ];
+
+        emitted += 1;
+        pkg_stats.record_lint(lint_level);
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
+    }
+
+    Ok(())
+}
+
+/// Spans of keys and values along with the Rust version that stabilized them
+fn incompatible_keys(document: &toml::de::DeTable<'_>) -> Vec<(Range<usize>, RustVersion)> {
+    let mut found = Vec::new();
+
+    if let Some((key, _)) = document.get_key_value("lints") {
+        found.push((key.span(), LINTS_TABLE));
+    }
+
+    if let Some(package) = document.get("package").and_then(|p| p.get_ref().as_table()) {
+        check_resolver(package, &mut found);
+        if let Some((key, _)) = package.get_key_value("autolib") {
+            found.push((key.span(), AUTOLIB));
+        }
+        for (key, value) in package.iter() {
+            if is_inherited(value.get_ref()) {
+                found.push((key.span(), WORKSPACE_INHERITANCE));
+            }
+        }
+    }
+
+    if let Some(workspace) = document
+        .get("workspace")
+        .and_then(|w| w.get_ref().as_table())
+    {
+        check_resolver(workspace, &mut found);
+        for (name, required) in [
+            ("lints", LINTS_TABLE),
+            ("package", WORKSPACE_INHERITANCE),
+            ("dependencies", WORKSPACE_INHERITANCE),
+        ] {
+            if let Some((key, _)) = workspace.get_key_value(name) {
+                found.push((key.span(), required));
+            }
+        }
+    }
+
+    let targets = document
+        .get("target")
+        .and_then(|t| t.get_ref().as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values())
+        .filter_map(|target| target.get_ref().as_table());
+    for table in std::iter::once(document).chain(targets) {
+        for deps in DEPENDENCY_TABLES
+            .iter()
+            .filter_map(|name| table.get(*name))
+            .filter_map(|deps| deps.get_ref().as_table())
+        {
+            for (key, value) in deps.iter() {
+                if is_inherited(value.get_ref()) {
+                    found.push((key.span(), WORKSPACE_INHERITANCE));
+                }
+            }
+        }
+    }
+
+    if let Some(features) = document
+        .get("features")
+        .and_then(|f| f.get_ref().as_table())
+    {
+        for value in features
+            .values()
+            .filter_map(|values| values.get_ref().as_array())
+            .flatten()
+        {
+            if let Some(value_str) = value.get_ref().as_str()
+                && (value_str.starts_with("dep:") || value_str.contains("?/"))
+            {
+                found.push((value.span(), NAMESPACED_FEATURES));
+            }
+        }
+    }
+
+    found
+}
+
+fn check_resolver(table: &toml::de::DeTable<'_>, found: &mut Vec<(Range<usize>, RustVersion)>) {
+    let Some(resolver) = table.get("resolver") else {
+        return;
+    };
+    let required = match resolver.get_ref().as_str() {
+        Some("2") => RESOLVER_2,
+        Some("3") => RESOLVER_3,
+        _ => return,
+    };
+    found.push((resolver.span(), required));
+}
+
+fn is_inherited(value: &toml::de::DeValue<'_>) -> bool {
+    value
+        .get("workspace")
+        .and_then(|w| w.get_ref().as_bool())
+        .unwrap_or(false)
+}
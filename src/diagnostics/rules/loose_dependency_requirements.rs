@@ -0,0 +1,265 @@
+use std::path::Path;
+
+use cargo_util_terminal::report::AnnotationKind;
+use cargo_util_terminal::report::Group;
+use cargo_util_terminal::report::Level;
+use cargo_util_terminal::report::Patch;
+use cargo_util_terminal::report::Snippet;
+use semver::Comparator;
+use semver::Op;
+use semver::Version;
+use semver::VersionReq;
+use tracing::instrument;
+
+use super::PEDANTIC;
+use crate::CargoResult;
+use crate::GlobalContext;
+use crate::diagnostics::Lint;
+use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ScopedDiagnosticStats;
+use crate::diagnostics::get_key_value;
+use crate::diagnostics::passes::LockfilePass;
+use crate::diagnostics::workspace_rel_path;
+use crate::workspace::Package;
+use crate::workspace::Workspace;
+
+pub static LINT: &Lint = &Lint {
+    name: "loose_dependency_requirements",
+    desc: "dependency requirement is older than the locked version",
+    primary_group: &PEDANTIC,
+    msrv: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+
+Checks for registry dependencies that resolve to an older version than the one in `Cargo.lock`
+when the package is resolved on its own with the lowest compatible versions,
+like with `-Zminimal-versions`.
+
+### Why is this bad?
+
+The locked version is the one the package is built and tested with.
+Code relying on APIs added since the lower bound may fail to build for users that resolve an older
+version, like with `-Zminimal-versions`.
+
+### Drawbacks
+
+This does not build the package with the minimal versions,
+so the older version is not necessarily too old to build.
+A looser requirement gives users more freedom in resolving dependencies.
+
+Packages whose lower bounds do not resolve are not checked.
+
+### Example
+
+```toml
+[dependencies]
+serde = "1.0"
+```
+
+with `serde` locked to `1.0.190`, should be written as:
+
+```toml
+[dependencies]
+serde = "1.0.190"
+```
+"#,
+    ),
+};
+
+#[instrument(skip_all)]
+pub(crate) fn lint_locked_package(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    pass: &LockfilePass<'_, '_>,
+    manifest_path: &Path,
+    level: LintLevelProduct,
+    pkg_stats: &mut ScopedDiagnosticStats<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let LintLevelProduct {
+        level: lint_level,
+        source,
+    } = level;
+
+    let resolve = pass.resolve();
+    let manifest = pkg.manifest();
+    let Some(document) = manifest.document() else {
+        return Ok(());
+    };
+    let Some(contents) = manifest.contents() else {
+        return Ok(());
+    };
+    let Some(minimal_resolve) = pass.resolve_minimal_versions(pkg)? else {
+        return Ok(());
+    };
+    let rel_manifest_path = workspace_rel_path(ws, manifest_path);
+
+    let mut emitted = 0;
+    for dep in pkg.dependencies() {
+        if !dep.source_id().is_registry() {
+            continue;
+        }
+        let Some(locked) = resolve
+            .deps(pkg.package_id())
+            .map(|(id, _)| id)
+            .filter(|id| dep.matches_id(*id))
+            .min()
+        else {
+            continue;
+        };
+        let Some(minimal) = minimal_resolve
+            .deps(pkg.package_id())
+            .map(|(id, _)| id)
+            .filter(|id| dep.matches_id(*id))
+            .min()
+        else {
+            continue;
+        };
+        let (locked, minimal) = (locked.version(), minimal.version());
+        if minimal >= locked {
+            continue;
+        }
+
+        let mut dep_path = Vec::new();
+        if let Some(platform) = dep.platform() {
+            dep_path.push("target".to_owned());
+            dep_path.push(platform.to_string());
+        }
+        dep_path.push(dep.kind().kind_table().to_owned());
+        dep_path.push(dep.name_in_toml().to_string());
+        let Some((dep_key, dep_value)) = get_key_value(document, &dep_path) else {
+            continue;
+        };
+
+        // Inherited requirements are declared in the workspace root
+        let inherited = dep_value
+            .get_ref()
+            .get("workspace")
+            .and_then(|w| w.get_ref().as_bool())
+            == Some(true);
+        let (req_manifest_path, req_document, req_contents, mut req_path) = if inherited {
+            let root = ws.root_maybe();
+            let (Some(root_document), Some(root_contents)) = (root.document(), root.contents())
+            else {
+                continue;
+            };
+            (
+                ws.root_manifest(),
+                root_document,
+                root_contents,
+                vec![
+                    "workspace".to_owned(),
+                    "dependencies".to_owned(),
+                    dep.name_in_toml().to_string(),
+                ],
+            )
+        } else {
+            (manifest_path, document, contents, dep_path.clone())
+        };
+        let Some((_, req_value)) = get_key_value(req_document, &req_path) else {
+            continue;
+        };
+        if req_value.get_ref().as_str().is_none() {
+            req_path.push("version".to_owned());
+        }
+        let Some((_, req_value)) = get_key_value(req_document, &req_path) else {
+            continue;
+        };
+        let Some(req_text) = req_value.get_ref().as_str() else {
+            continue;
+        };
+        let Ok(req) = VersionReq::parse(req_text) else {
+            continue;
+        };
+
+        let level = lint_level.to_diagnostic_level();
+        let emitted_source = LINT.emitted_source(lint_level, source);
+
+        let mut primary = Group::with_title(level.primary_title(LINT.desc));
+        let mut snippet = Snippet::source(contents)
+            .path(&rel_manifest_path)
+            .annotation(AnnotationKind::Primary.span(dep_key.span()));
+        if !inherited {
+            snippet = snippet.annotation(
+                AnnotationKind::Context
+                    .span(req_value.span())
+                    .label(format!("minimal versions select `{minimal}`")),
+            );
+        }
+        primary = primary.element(snippet).element(
+            Level::NOTE.message(format!("`{}` is locked to `{locked}`", dep.package_name())),
+        );
+        if inherited {
+            primary = primary.element(Level::NOTE.message(format!(
+                "with minimal versions, `{}` resolves to `{minimal}`",
+                dep.package_name()
+            )));
+        }
+        if emitted == 0 {
+            primary = primary.element(Level::NOTE.message(emitted_source));
+        }
+        let mut report = vec![primary];
+
+        if let Some(raised) = raise_lower_bound(req_text, &req, locked) {
+            let mut help = Group::with_title(Level::HELP.secondary_title(
+                "to require the locked version, raise the lower bound of the requirement",
+            ));
+            help = help.element(
+                Snippet::source(req_contents)
+                    .path(workspace_rel_path(ws, req_manifest_path))
+                    .patch(Patch::new(req_value.span(), format!("\"{raised}\""))),
+            );
+            report.push(help);
+        } else if inherited {
+            report.push(Group::with_title(Level::HELP.secondary_title(format!(
+                "consider raising the lower bound of `workspace.dependencies.{}`",
+                dep.name_in_toml()
+            ))));
+        }
+
+        emitted += 1;
+        pkg_stats.record_lint(lint_level);
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
+    }
+
+    Ok(())
+}
+
+/// Raise the lower bound of `req` to `locked` without changing its upper bound
+///
+/// Returns `None` when the requirement cannot be written with the same upper bound.
+fn raise_lower_bound(req_text: &str, req: &VersionReq, locked: &Version) -> Option<String> {
+    let mut comparators = Vec::new();
+    for comparator in &req.comparators {
+        let keeps_upper_bound = match comparator.op {
+            Op::Caret => comparator.major != 0 || comparator.minor.is_some_and(|minor| minor != 0),
+            Op::Tilde => comparator.minor.is_some(),
+            Op::GreaterEq => true,
+            _ => {
+                comparators.push(comparator.clone());
+                continue;
+            }
+        };
+        if !keeps_upper_bound {
+            return None;
+        }
+        comparators.push(Comparator {
+            op: comparator.op,
+            major: locked.major,
+            minor: Some(locked.minor),
+            patch: Some(locked.patch),
+            pre: locked.pre.clone(),
+        });
+    }
+    let raised = VersionReq { comparators };
+    if !raised.matches(locked) {
+        return None;
+    }
+    let mut raised = raised.to_string();
+    if raised.starts_with('^') && !req_text.trim_start().starts_with('^') {
+        raised.remove(0);
+    }
+    Some(raised)
+}
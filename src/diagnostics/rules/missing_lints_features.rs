@@ -140,7 +140,7 @@ fn report_feature_not_enabled(
     )))];
 
     pkg_stats.record_error();
-    gctx.shell().print_report(&report, true)?;
+    pkg_stats.print_report(&report, true, gctx)?;
 
     Ok(())
 }
@@ -123,7 +123,7 @@ pub(crate) fn lint_package(
     }
 
     pkg_stats.record_lint(lint_level);
-    gctx.shell().print_report(&report, lint_level.force())?;
+    pkg_stats.print_report(&report, lint_level.force(), gctx)?;
 
     Ok(())
 }
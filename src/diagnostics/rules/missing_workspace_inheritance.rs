@@ -0,0 +1,450 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use cargo_util::paths;
+use cargo_util_schemas::manifest::InheritableDependency;
+use cargo_util_schemas::manifest::InheritableField;
+use cargo_util_schemas::manifest::InheritablePackage;
+use cargo_util_schemas::manifest::PackageName;
+use cargo_util_schemas::manifest::TomlDependency;
+use cargo_util_schemas::manifest::TomlDetailedDependency;
+use cargo_util_schemas::manifest::TomlManifest;
+use cargo_util_terminal::report::AnnotationKind;
+use cargo_util_terminal::report::Group;
+use cargo_util_terminal::report::Level;
+use cargo_util_terminal::report::Origin;
+use cargo_util_terminal::report::Snippet;
+use tracing::instrument;
+
+use super::PEDANTIC;
+use crate::CargoResult;
+use crate::GlobalContext;
+use crate::diagnostics::Lint;
+use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ManifestEdit;
+use crate::diagnostics::ManifestFix;
+use crate::diagnostics::ScopedDiagnosticStats;
+use crate::diagnostics::get_key_value_span;
+use crate::diagnostics::workspace_rel_path;
+use crate::workspace::Package;
+use crate::workspace::Workspace;
+
+pub static LINT: &Lint = &Lint {
+    name: "missing_workspace_inheritance",
+    desc: "value could be inherited from the workspace",
+    primary_group: &PEDANTIC,
+    msrv: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+
+Checks for workspace members that repeat a `[workspace.package]` field or a
+`[workspace.dependencies]` entry instead of inheriting it.
+
+Dependencies are only checked when they have the same source and requirement as the
+`[workspace.dependencies]` entry of the same name.
+
+### Why is this bad?
+
+The copies can drift out of sync when the workspace's value is updated.
+
+### Drawbacks
+
+A member may intentionally set its own value, like when it is expected to move out of the
+workspace.
+
+### Example
+
+```toml
+[workspace.package]
+edition = "2024"
+
+[workspace.dependencies]
+regex = "1.11"
+
+[package]
+name = "foo"
+edition = "2024"
+
+[dependencies]
+regex = { version = "1.11", features = ["logging"] }
+```
+
+Should be written as:
+
+```toml
+[workspace.package]
+edition = "2024"
+
+[workspace.dependencies]
+regex = "1.11"
+
+[package]
+name = "foo"
+edition.workspace = true
+
+[dependencies]
+regex = { workspace = true, features = ["logging"] }
+```
+"#,
+    ),
+};
+
+/// Keys that declare the source and requirement of a dependency, in the order to prefer replacing
+/// with `workspace = true`
+const DEP_SOURCE_KEYS: &[&str] = &[
+    "version",
+    "path",
+    "git",
+    "branch",
+    "tag",
+    "rev",
+    "registry",
+    "registry-index",
+    "package",
+];
+
+#[instrument(skip_all)]
+pub(crate) fn lint_package(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    manifest_path: &Path,
+    level: LintLevelProduct,
+    pkg_stats: &mut ScopedDiagnosticStats<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let LintLevelProduct {
+        level: lint_level,
+        source,
+    } = level;
+
+    let root = ws.root_maybe();
+    if pkg.manifest_path() == ws.root_manifest() {
+        // Inheriting from your own manifest doesn't reduce duplication
+        return Ok(());
+    }
+    let Some(ws_toml) = root.original_toml().and_then(|t| t.workspace.as_ref()) else {
+        return Ok(());
+    };
+    let manifest = pkg.manifest();
+    let Some(original_toml) = manifest.original_toml() else {
+        return Ok(());
+    };
+
+    let mut candidates = Vec::new();
+    if let Some(package) = &original_toml.package
+        && let Some(ws_package) = &ws_toml.package
+    {
+        for field in duplicated_package_fields(package, ws_package) {
+            candidates.push(Candidate {
+                key_path: vec!["package".to_owned(), field.to_owned()],
+                ws_key_path: vec![
+                    "workspace".to_owned(),
+                    "package".to_owned(),
+                    field.to_owned(),
+                ],
+                help: format!("to inherit `workspace.package.{field}`, set `workspace = true`"),
+                edits: vec![ManifestEdit::set(&["package", field], inherit_value())],
+            });
+        }
+    }
+    if let Some(ws_deps) = &ws_toml.dependencies {
+        for (table_path, deps) in dependency_tables(original_toml) {
+            for (name, dep) in deps {
+                let InheritableDependency::Value(dep) = dep else {
+                    continue;
+                };
+                let Some(ws_dep) = ws_deps.get(name) else {
+                    continue;
+                };
+                if !is_same_dependency(dep, pkg.root(), ws_dep, ws.root()) {
+                    continue;
+                }
+                let mut key_path = table_path.clone();
+                key_path.push(name.to_string());
+                let key_path_str = key_path.iter().map(|k| k.as_str()).collect::<Vec<_>>();
+                candidates.push(Candidate {
+                    key_path: key_path.clone(),
+                    ws_key_path: vec![
+                        "workspace".to_owned(),
+                        "dependencies".to_owned(),
+                        name.to_string(),
+                    ],
+                    help: format!(
+                        "to inherit `workspace.dependencies.{name}`, set `workspace = true`"
+                    ),
+                    edits: inherit_dependency_edits(&key_path_str, dep, ws_dep),
+                });
+            }
+        }
+    }
+
+    let document = manifest.document();
+    let contents = manifest.contents();
+    let rel_manifest_path = workspace_rel_path(ws, manifest_path);
+    for (i, candidate) in candidates.into_iter().enumerate() {
+        let level = lint_level.to_diagnostic_level();
+        let emitted_source = LINT.emitted_source(lint_level, source);
+
+        let mut primary = Group::with_title(level.primary_title(LINT.desc));
+        if let Some(document) = document
+            && let Some(contents) = contents
+            && let Some(span) = get_key_value_span(document, &candidate.key_path)
+        {
+            primary = primary.element(
+                Snippet::source(contents)
+                    .path(&rel_manifest_path)
+                    .annotation(AnnotationKind::Primary.span(span.key.start..span.value.end)),
+            );
+        } else {
+            primary = primary.element(Origin::path(&rel_manifest_path));
+        }
+        if let Some(ws_document) = root.document()
+            && let Some(ws_contents) = root.contents()
+            && let Some(span) = get_key_value_span(ws_document, &candidate.ws_key_path)
+        {
+            primary = primary.element(
+                Snippet::source(ws_contents)
+                    .path(workspace_rel_path(ws, ws.root_manifest()))
+                    .annotation(
+                        AnnotationKind::Context
+                            .span(span.key.start..span.value.end)
+                            .label("defined by the workspace"),
+                    ),
+            );
+        }
+        if i == 0 {
+            primary = primary.element(Level::NOTE.message(emitted_source));
+        }
+        let mut report = vec![primary];
+        report.push(Group::with_title(
+            Level::HELP.secondary_title(candidate.help),
+        ));
+
+        let mut fix = ManifestFix::new(pkg.manifest_path());
+        for edit in candidate.edits {
+            fix = fix.edit(edit);
+        }
+        pkg_stats.record_fix(fix);
+        pkg_stats.record_lint(lint_level);
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
+    }
+
+    Ok(())
+}
+
+struct Candidate {
+    key_path: Vec<String>,
+    ws_key_path: Vec<String>,
+    help: String,
+    edits: Vec<ManifestEdit>,
+}
+
+/// Fields set by the package to the same value as the workspace
+///
+/// Fields holding paths are skipped as those are relative to the manifest they are declared in.
+fn duplicated_package_fields(
+    package: &cargo_util_schemas::manifest::TomlPackage,
+    ws_package: &InheritablePackage,
+) -> Vec<&'static str> {
+    fn is_duplicate<T: PartialEq>(
+        field: &Option<InheritableField<T>>,
+        ws_field: &Option<T>,
+    ) -> bool {
+        matches!((field, ws_field), (Some(InheritableField::Value(v)), Some(ws_v)) if v == ws_v)
+    }
+
+    let mut fields = Vec::new();
+    if is_duplicate(&package.edition, &ws_package.edition) {
+        fields.push("edition");
+    }
+    if is_duplicate(&package.rust_version, &ws_package.rust_version) {
+        fields.push("rust-version");
+    }
+    if is_duplicate(&package.authors, &ws_package.authors) {
+        fields.push("authors");
+    }
+    if is_duplicate(&package.homepage, &ws_package.homepage) {
+        fields.push("homepage");
+    }
+    if is_duplicate(&package.documentation, &ws_package.documentation) {
+        fields.push("documentation");
+    }
+    if is_duplicate(&package.keywords, &ws_package.keywords) {
+        fields.push("keywords");
+    }
+    if is_duplicate(&package.categories, &ws_package.categories) {
+        fields.push("categories");
+    }
+    if is_duplicate(&package.license, &ws_package.license) {
+        fields.push("license");
+    }
+    if is_duplicate(&package.repository, &ws_package.repository) {
+        fields.push("repository");
+    }
+    fields
+}
+
+fn dependency_tables(
+    toml: &TomlManifest,
+) -> Vec<(Vec<String>, &BTreeMap<PackageName, InheritableDependency>)> {
+    let mut tables = vec![
+        (vec!["dependencies"], &toml.dependencies),
+        (vec!["dev-dependencies"], &toml.dev_dependencies),
+        (vec!["dev_dependencies"], &toml.dev_dependencies2),
+        (vec!["build-dependencies"], &toml.build_dependencies),
+        (vec!["build_dependencies"], &toml.build_dependencies2),
+    ];
+    for (platform, target) in toml.target.iter().flatten() {
+        let platform = platform.as_str();
+        tables.extend([
+            (
+                vec!["target", platform, "dependencies"],
+                &target.dependencies,
+            ),
+            (
+                vec!["target", platform, "dev-dependencies"],
+                &target.dev_dependencies,
+            ),
+            (
+                vec!["target", platform, "dev_dependencies"],
+                &target.dev_dependencies2,
+            ),
+            (
+                vec!["target", platform, "build-dependencies"],
+                &target.build_dependencies,
+            ),
+            (
+                vec!["target", platform, "build_dependencies"],
+                &target.build_dependencies2,
+            ),
+        ]);
+    }
+    tables
+        .into_iter()
+        .filter_map(|(table_path, deps)| {
+            let table_path = table_path.into_iter().map(String::from).collect();
+            deps.as_ref().map(|deps| (table_path, deps))
+        })
+        .collect()
+}
+
+/// Whether inheriting `ws_dep` would not change the source or requirement of `dep`
+fn is_same_dependency(
+    dep: &TomlDependency,
+    pkg_root: &Path,
+    ws_dep: &TomlDependency,
+    ws_root: &Path,
+) -> bool {
+    let detailed = to_detailed(dep);
+    let ws_detailed = to_detailed(ws_dep);
+    if !detailed._unused_keys.is_empty()
+        || detailed.artifact.is_some()
+        || detailed.lib.is_some()
+        || detailed.target.is_some()
+        || detailed.base.is_some()
+        || ws_detailed.base.is_some()
+    {
+        return false;
+    }
+    // Members can't opt out of default features the workspace enables
+    if detailed.default_features() == Some(false) && ws_detailed.default_features() != Some(false) {
+        return false;
+    }
+    let path = detailed
+        .path
+        .as_ref()
+        .map(|p| paths::normalize_path(&pkg_root.join(p)));
+    let ws_path = ws_detailed
+        .path
+        .as_ref()
+        .map(|p| paths::normalize_path(&ws_root.join(p)));
+    detailed.version == ws_detailed.version
+        && path == ws_path
+        && detailed.registry == ws_detailed.registry
+        && detailed.registry_index == ws_detailed.registry_index
+        && detailed.git == ws_detailed.git
+        && detailed.branch == ws_detailed.branch
+        && detailed.tag == ws_detailed.tag
+        && detailed.rev == ws_detailed.rev
+        && detailed.package == ws_detailed.package
+}
+
+fn to_detailed(dep: &TomlDependency) -> TomlDetailedDependency {
+    match dep {
+        TomlDependency::Simple(version) => TomlDetailedDependency {
+            version: Some(version.clone()),
+            ..Default::default()
+        },
+        TomlDependency::Detailed(detailed) => detailed.clone(),
+    }
+}
+
+fn inherit_dependency_edits(
+    key_path: &[&str],
+    dep: &TomlDependency,
+    ws_dep: &TomlDependency,
+) -> Vec<ManifestEdit> {
+    let TomlDependency::Detailed(detailed) = dep else {
+        return vec![ManifestEdit::set(key_path, inherit_value())];
+    };
+
+    // Reuse the place of the first source key for `workspace = true`, keeping the remaining keys
+    // like `features` as they are
+    let mut present_keys = DEP_SOURCE_KEYS
+        .iter()
+        .copied()
+        .filter(|key| match *key {
+            "version" => detailed.version.is_some(),
+            "path" => detailed.path.is_some(),
+            "git" => detailed.git.is_some(),
+            "branch" => detailed.branch.is_some(),
+            "tag" => detailed.tag.is_some(),
+            "rev" => detailed.rev.is_some(),
+            "registry" => detailed.registry.is_some(),
+            "registry-index" => detailed.registry_index.is_some(),
+            "package" => detailed.package.is_some(),
+            _ => unreachable!("unknown key `{key}`"),
+        })
+        .collect::<Vec<_>>();
+    if detailed.default_features() == to_detailed(ws_dep).default_features() {
+        if detailed.default_features.is_some() {
+            present_keys.push("default-features");
+        }
+        if detailed.default_features2.is_some() {
+            present_keys.push("default_features");
+        }
+    }
+    let Some((first, rest)) = present_keys.split_first() else {
+        return vec![ManifestEdit::set(key_path, inherit_value())];
+    };
+
+    let with_key = |key: &str| {
+        let mut path = key_path.to_vec();
+        path.push(key);
+        path.into_iter().map(String::from).collect::<Vec<_>>()
+    };
+    let mut edits = vec![
+        ManifestEdit::Rename {
+            key_path: with_key(first),
+            new: "workspace".to_owned(),
+        },
+        ManifestEdit::Set {
+            key_path: with_key("workspace"),
+            value: true.into(),
+        },
+    ];
+    edits.extend(rest.iter().map(|key| ManifestEdit::Remove {
+        key_path: with_key(key),
+    }));
+    edits
+}
+
+/// `workspace = true`, as a dotted key
+fn inherit_value() -> toml_edit::Value {
+    let mut table = toml_edit::InlineTable::default();
+    table.set_dotted(true);
+    table.insert("workspace", true.into());
+    toml_edit::Value::InlineTable(table)
+}
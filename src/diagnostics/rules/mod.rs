@@ -1,8 +1,11 @@
 mod blanket_hint_mostly_unused;
 mod deferred_parse_diagnostics;
 mod im_a_teapot;
+mod incompatible_rust_version;
+mod loose_dependency_requirements;
 mod missing_lints_features;
 mod missing_lints_inheritance;
+mod missing_workspace_inheritance;
 mod non_kebab_case_bins;
 mod non_kebab_case_features;
 mod non_kebab_case_packages;
@@ -14,11 +17,13 @@ mod text_direction_codepoint_in_comment;
 mod text_direction_codepoint_in_literal;
 mod unknown_lints;
 pub mod unused_dependencies;
+mod unused_features;
 mod unused_workspace_dependencies;
 mod unused_workspace_package_fields;
 
 use super::LintGroup;
 use super::LintLevel;
+use super::passes::LockfilePassRule;
 use super::passes::ParsePassRule;
 use crate::workspace::Feature;
 
@@ -41,6 +46,10 @@ pub const PARSE_PASS_RULES: &[ParsePassRule<'static>] = &[
         rule: unknown_lints::lint_manifest,
         lint: unknown_lints::LINT,
     },
+    ParsePassRule::LintManifest {
+        rule: incompatible_rust_version::lint_manifest,
+        lint: incompatible_rust_version::LINT,
+    },
     ParsePassRule::LintWorkspace {
         rule: blanket_hint_mostly_unused::lint_workspace,
         lint: blanket_hint_mostly_unused::LINT,
@@ -79,6 +88,10 @@ pub const PARSE_PASS_RULES: &[ParsePassRule<'static>] = &[
         lint: im_a_teapot::LINT,
     },
     // `allow`
+    ParsePassRule::LintPackage {
+        rule: missing_workspace_inheritance::lint_package,
+        lint: missing_workspace_inheritance::LINT,
+    },
     ParsePassRule::LintPackage {
         rule: non_kebab_case_features::lint_package,
         lint: non_kebab_case_features::LINT,
@@ -97,10 +110,28 @@ pub const PARSE_PASS_RULES: &[ParsePassRule<'static>] = &[
     },
 ];
 
+pub const LOCKFILE_PASS_RULES: &[LockfilePassRule<'static>] = &[
+    LockfilePassRule::LintPackage {
+        rule: incompatible_rust_version::lint_locked_package,
+        lint: incompatible_rust_version::LINT,
+    },
+    LockfilePassRule::LintPackage {
+        rule: loose_dependency_requirements::lint_locked_package,
+        lint: loose_dependency_requirements::LINT,
+    },
+    LockfilePassRule::LintPackage {
+        rule: unused_features::lint_locked_package,
+        lint: unused_features::LINT,
+    },
+];
+
 pub static LINTS: &[&crate::diagnostics::Lint] = &[
     blanket_hint_mostly_unused::LINT,
     im_a_teapot::LINT,
+    incompatible_rust_version::LINT,
+    loose_dependency_requirements::LINT,
     missing_lints_inheritance::LINT,
+    missing_workspace_inheritance::LINT,
     non_kebab_case_bins::LINT,
     non_kebab_case_features::LINT,
     non_kebab_case_packages::LINT,
@@ -112,6 +143,7 @@ pub static LINTS: &[&crate::diagnostics::Lint] = &[
     text_direction_codepoint_in_literal::LINT,
     unknown_lints::LINT,
     unused_dependencies::LINT,
+    unused_features::LINT,
     unused_workspace_dependencies::LINT,
     unused_workspace_package_fields::LINT,
 ];
@@ -433,6 +465,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ensure_lockfile_passed_in_lints() {
+        let lint_names = LINTS.iter().map(|l| l.name).collect::<HashSet<_>>();
+        let missing = LOCKFILE_PASS_RULES
+            .iter()
+            .map(|rule| match rule {
+                LockfilePassRule::LintPackage { lint, .. } => lint.name,
+            })
+            .filter(|name| !lint_names.contains(name))
+            .sorted()
+            .join("\n");
+        assert!(
+            missing.is_empty(),
+            "\n`LINTS` did not contain all `Lint`s found in `LOCKFILE_PASS_RULES`\n\
+            Please add the following to `LINTS`:\n\
+            {missing}\n",
+        );
+    }
+
     fn parse_pass_rule_names(rules: &[ParsePassRule<'_>]) -> Vec<&'static str> {
         rules
             .iter()
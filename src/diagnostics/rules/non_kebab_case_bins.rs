@@ -236,7 +236,7 @@ path = "src/main.rs""#
         }
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
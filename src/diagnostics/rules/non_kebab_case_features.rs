@@ -145,7 +145,7 @@ fn lint_package_inner(
         }
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
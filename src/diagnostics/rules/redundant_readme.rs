@@ -16,6 +16,8 @@ use crate::diagnostics::Lint;
 use crate::diagnostics::LintLevel;
 use crate::diagnostics::LintLevelProduct;
 use crate::diagnostics::LintLevelSource;
+use crate::diagnostics::ManifestEdit;
+use crate::diagnostics::ManifestFix;
 use crate::diagnostics::ScopedDiagnosticStats;
 use crate::diagnostics::get_key_value_span;
 use crate::diagnostics::workspace_rel_path;
@@ -137,8 +139,11 @@ fn lint_package_inner(
     let help = Group::with_title(Level::HELP.secondary_title("consider removing `package.readme`"));
     report.push(help);
 
+    pkg_stats.record_fix(
+        ManifestFix::new(pkg.manifest_path()).edit(ManifestEdit::remove(&["package", "readme"])),
+    );
     pkg_stats.record_lint(lint_level);
-    gctx.shell().print_report(&report, lint_level.force())?;
+    pkg_stats.print_report(&report, lint_level.force(), gctx)?;
 
     Ok(())
 }
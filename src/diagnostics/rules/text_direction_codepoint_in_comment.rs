@@ -116,7 +116,7 @@ pub(crate) fn lint_manifest(
         let report = [primary];
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
@@ -182,7 +182,7 @@ fn lint_manifest_inner(
         report.push(group);
 
         pkg_stats.record_lint(*lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
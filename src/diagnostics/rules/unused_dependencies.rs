@@ -155,7 +155,7 @@ pub(crate) fn lint_package(
         report.push(help);
 
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
@@ -338,11 +338,7 @@ fn lint_package_build_results(
                 }
 
                 pkg_stats.record_lint(lint_level);
-                build_runner
-                    .bcx
-                    .gctx
-                    .shell()
-                    .print_report(&report, lint_level.force())?;
+                pkg_stats.print_report(&report, lint_level.force(), build_runner.bcx.gctx)?;
             }
         }
     }
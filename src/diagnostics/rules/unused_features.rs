@@ -0,0 +1,424 @@
+use std::path::Path;
+
+use cargo_util_terminal::report::AnnotationKind;
+use cargo_util_terminal::report::Group;
+use cargo_util_terminal::report::Level;
+use cargo_util_terminal::report::Origin;
+use cargo_util_terminal::report::Snippet;
+use tracing::instrument;
+
+use super::PEDANTIC;
+use crate::CargoResult;
+use crate::GlobalContext;
+use crate::diagnostics::Lint;
+use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ScopedDiagnosticStats;
+use crate::diagnostics::get_key_value_span;
+use crate::diagnostics::passes::LockfilePass;
+use crate::diagnostics::workspace_rel_path;
+use crate::resolver::features::FeaturesFor;
+use crate::util::data_structures::HashSet;
+use crate::workspace::Package;
+use crate::workspace::Workspace;
+
+pub static LINT: &Lint = &Lint {
+    name: "unused_features",
+    desc: "unused feature",
+    primary_group: &PEDANTIC,
+    msrv: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+
+Checks for features and optional dependencies of workspace members that are never enabled by
+the workspace.
+
+A feature is enabled by the workspace when building any member on its own, with its `default`
+features and the `required-features` of its cargo targets, enables it.
+Features checked with `cfg(feature = "...")`, `cfg!` or `cfg_attr` in the package's targets are
+not reported.
+These are found in the module tree of each target by following its `mod` items,
+ignoring comments and string literals.
+
+### Why is this bad?
+
+A feature that is never enabled and that no code checks for does nothing,
+adding noise for users selecting features.
+An optional dependency that is never enabled is not built or tested,
+so it is easy for it to break unnoticed.
+
+### Drawbacks
+
+Features are often meant to be enabled by dependents outside of the workspace.
+
+### Example
+
+```toml
+[features]
+default = ["std"]
+std = []
+unused = []
+```
+
+Should be written as:
+
+```toml
+[features]
+default = ["std"]
+std = []
+```
+"#,
+    ),
+};
+
+#[instrument(skip_all)]
+pub(crate) fn lint_locked_package(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    pass: &LockfilePass<'_, '_>,
+    manifest_path: &Path,
+    level: LintLevelProduct,
+    pkg_stats: &mut ScopedDiagnosticStats<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let LintLevelProduct {
+        level: lint_level,
+        source,
+    } = level;
+
+    let member_features = pass.member_features()?;
+    let pkg_id = pkg.package_id();
+    let Some(activated) =
+        member_features.activated_features_unverified(pkg_id, FeaturesFor::default())
+    else {
+        return Ok(());
+    };
+    let cfg_features = cfg_features(pkg);
+
+    let manifest_path = workspace_rel_path(ws, manifest_path);
+    let manifest = pkg.manifest();
+    let document = manifest.document();
+    let contents = manifest.contents();
+
+    let mut unused = Vec::new();
+    let declared_features = manifest
+        .original_toml()
+        .and_then(|toml| toml.features.as_ref())
+        .into_iter()
+        .flat_map(|features| features.keys());
+    for feature in declared_features {
+        if activated.iter().any(|f| f.as_str() == feature.as_str())
+            || cfg_features.contains(feature.as_str())
+        {
+            continue;
+        }
+        unused.push((
+            LINT.desc,
+            vec!["features".to_owned(), feature.to_string()],
+            "consider removing the unused feature",
+        ));
+    }
+    let mut seen = HashSet::default();
+    for dep in pkg.dependencies() {
+        if !dep.is_optional()
+            || member_features.is_dep_activated(pkg_id, FeaturesFor::default(), dep.name_in_toml())
+            || !seen.insert(dep.name_in_toml())
+        {
+            continue;
+        }
+        let mut dep_path = Vec::new();
+        if let Some(platform) = dep.platform() {
+            dep_path.push("target".to_owned());
+            dep_path.push(platform.to_string());
+        }
+        dep_path.push(dep.kind().kind_table().to_owned());
+        dep_path.push(dep.name_in_toml().to_string());
+        unused.push((
+            "unused optional dependency",
+            dep_path,
+            "consider removing the unused dependency",
+        ));
+    }
+
+    for (i, (title, key_path, help)) in unused.into_iter().enumerate() {
+        let level = lint_level.to_diagnostic_level();
+        let emitted_source = LINT.emitted_source(lint_level, source);
+
+        let key_path = key_path.iter().map(|k| k.as_str()).collect::<Vec<_>>();
+        let mut primary = Group::with_title(level.primary_title(title));
+        if let Some(document) = document
+            && let Some(contents) = contents
+            && let Some(span) = get_key_value_span(document, &key_path)
+        {
+            primary = primary.element(
+                Snippet::source(contents)
+                    .path(&manifest_path)
+                    .annotation(AnnotationKind::Primary.span(span.key)),
+            );
+        } else {
+            primary = primary.element(Origin::path(&manifest_path));
+        }
+        if i == 0 {
+            primary = primary.element(Level::NOTE.message(emitted_source));
+        }
+        let report = [
+            primary,
+            Group::with_title(Level::HELP.secondary_title(help)),
+        ];
+
+        pkg_stats.record_lint(lint_level);
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
+    }
+
+    Ok(())
+}
+
+/// Feature names checked by `cfg(feature = "...")` in the module trees of the package's targets
+///
+/// Modules are followed from each target's root through `mod` items.
+fn cfg_features(pkg: &Package) -> HashSet<String> {
+    let mut features = HashSet::default();
+    let mut visited = HashSet::default();
+    // Source files along with whether their submodules are in their own directory
+    let mut pending = pkg
+        .targets()
+        .iter()
+        .filter_map(|target| target.src_path().path())
+        .map(|path| (path.to_path_buf(), true))
+        .collect::<Vec<_>>();
+    while let Some((path, owns_dir)) = pending.pop() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(dir) = path.parent() else {
+            continue;
+        };
+        // Submodules of `foo.rs` are in `foo/`, unlike those of crate roots and `mod.rs`
+        let module_dir = match path.file_stem() {
+            Some(stem) if !owns_dir && stem != "mod" => dir.join(stem),
+            _ => dir.to_path_buf(),
+        };
+
+        let tokens = tokenize(&contents);
+        features.extend(
+            cfg_feature_names(&tokens)
+                .into_iter()
+                .map(ToOwned::to_owned),
+        );
+        for decl in mod_decls(&tokens) {
+            let base = decl
+                .inline
+                .iter()
+                .fold(module_dir.clone(), |base, module| base.join(module));
+            if let Some(path) = decl.path {
+                let base = if decl.inline.is_empty() { dir } else { &base };
+                pending.push((base.join(path), true));
+                continue;
+            }
+            let file = base.join(format!("{}.rs", decl.name));
+            if file.exists() {
+                pending.push((file, false));
+            } else {
+                pending.push((base.join(decl.name).join("mod.rs"), true));
+            }
+        }
+    }
+    features
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Ident(&'a str),
+    /// The contents of a string literal, with escapes left as-is
+    Str(&'a str),
+    Punct(char),
+}
+
+/// Splits Rust source into identifiers, string literals and punctuation, dropping comments
+fn tokenize(contents: &str) -> Vec<Token<'_>> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tokens = Vec::new();
+    let mut rest = contents;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if rest.starts_with("/*") {
+            rest = skip_block_comment(rest);
+        } else if let Some((value, after)) = string_literal(rest) {
+            tokens.push(Token::Str(value));
+            rest = after;
+        } else if c == '\'' {
+            rest = skip_char_literal(rest);
+        } else if is_ident(c) {
+            let end = rest.find(|c| !is_ident(c)).unwrap_or(rest.len());
+            tokens.push(Token::Ident(&rest[..end]));
+            rest = &rest[end..];
+        } else {
+            tokens.push(Token::Punct(c));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+/// Skips a leading, possibly nested, `/* ... */` comment
+fn skip_block_comment(rest: &str) -> &str {
+    let bytes = rest.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return &rest[i..];
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    ""
+}
+
+/// Splits a leading string literal, like `"..."`, `b"..."` or `r#"..."#`, into its contents and
+/// the rest of the source
+fn string_literal(rest: &str) -> Option<(&str, &str)> {
+    let unprefixed = rest.strip_prefix(['b', 'c']).unwrap_or(rest);
+    if let Some(raw) = unprefixed.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let end = body.find(&terminator).unwrap_or(body.len());
+        return Some((
+            &body[..end],
+            body.get(end + terminator.len()..).unwrap_or(""),
+        ));
+    }
+    let body = unprefixed.strip_prefix('"')?;
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some((&body[..i], &body[i + 1..])),
+            _ => {}
+        }
+    }
+    Some((body, ""))
+}
+
+/// Skips a leading char literal, or only the quote of a lifetime or label
+fn skip_char_literal(rest: &str) -> &str {
+    let after_quote = &rest[1..];
+    let mut chars = after_quote.chars();
+    match (chars.next(), chars.next()) {
+        (Some('\\'), Some(escaped)) => {
+            let after = &after_quote[1 + escaped.len_utf8()..];
+            after.find('\'').map_or("", |end| &after[end + 1..])
+        }
+        (Some(c), Some('\'')) => &after_quote[c.len_utf8() + 1..],
+        _ => after_quote,
+    }
+}
+
+/// Values of `feature = "..."` predicates in `cfg(...)`, `cfg!(...)` and `cfg_attr(...)`
+fn cfg_feature_names<'a>(tokens: &[Token<'a>]) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    // Nesting of parentheses within a `cfg` predicate
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('(') if depth > 0 => depth += 1,
+            Token::Punct('(') => {
+                if let [.., Token::Ident("cfg" | "cfg_attr")]
+                | [.., Token::Ident("cfg"), Token::Punct('!')] = tokens[..i]
+                {
+                    depth = 1;
+                }
+            }
+            Token::Punct(')') if depth > 0 => depth -= 1,
+            Token::Ident("feature") if depth > 0 => {
+                if let [Token::Punct('='), Token::Str(name), ..] = tokens[i + 1..] {
+                    names.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// A `mod name;` item
+struct ModDecl<'a> {
+    /// Inline modules the item is declared in, like `a` for `mod a { mod b; }`
+    inline: Vec<&'a str>,
+    name: &'a str,
+    /// The value of the `#[path]` attribute of the item
+    path: Option<&'a str>,
+}
+
+fn mod_decls<'a>(tokens: &[Token<'a>]) -> Vec<ModDecl<'a>> {
+    let mut decls = Vec::new();
+    // Inline modules along with the brace depth of their body
+    let mut inline: Vec<(&str, usize)> = Vec::new();
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                if inline.last().is_some_and(|(_, body)| *body == depth) {
+                    inline.pop();
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Token::Ident("mod") => match tokens[i + 1..] {
+                [Token::Ident(name), Token::Punct(';'), ..] => decls.push(ModDecl {
+                    inline: inline.iter().map(|(module, _)| *module).collect(),
+                    name,
+                    path: path_attr(&tokens[..i]),
+                }),
+                [Token::Ident(name), Token::Punct('{'), ..] => inline.push((name, depth + 1)),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    decls
+}
+
+/// The `#[path = "..."]` among the attributes ending `tokens`, ignoring the item's visibility
+fn path_attr<'a>(mut tokens: &[Token<'a>]) -> Option<&'a str> {
+    loop {
+        match tokens {
+            [
+                ..,
+                Token::Punct('#'),
+                Token::Punct('['),
+                Token::Ident("path"),
+                Token::Punct('='),
+                Token::Str(path),
+                Token::Punct(']'),
+            ] => return Some(path),
+            [rest @ .., Token::Punct(']')] => {
+                let start = rest.iter().rposition(|t| *t == Token::Punct('['))?;
+                tokens = rest[..start].strip_suffix(&[Token::Punct('#')])?;
+            }
+            [
+                rest @ ..,
+                Token::Ident("pub" | "crate" | "super" | "self") | Token::Punct('(' | ')'),
+            ] => tokens = rest,
+            _ => return None,
+        }
+    }
+}
@@ -14,6 +14,8 @@ use crate::CargoResult;
 use crate::GlobalContext;
 use crate::diagnostics::Lint;
 use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ManifestEdit;
+use crate::diagnostics::ManifestFix;
 use crate::diagnostics::ScopedDiagnosticStats;
 use crate::diagnostics::get_key_value_span;
 use crate::diagnostics::workspace_rel_path;
@@ -154,8 +156,15 @@ pub(crate) fn lint_workspace(
         );
         report.push(help);
 
+        pkg_stats.record_fix(
+            ManifestFix::new(ws.root_manifest()).edit(ManifestEdit::remove(&[
+                "workspace",
+                "dependencies",
+                unused.as_str(),
+            ])),
+        );
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
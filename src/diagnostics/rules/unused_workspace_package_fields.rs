@@ -13,6 +13,8 @@ use crate::CargoResult;
 use crate::GlobalContext;
 use crate::diagnostics::Lint;
 use crate::diagnostics::LintLevelProduct;
+use crate::diagnostics::ManifestEdit;
+use crate::diagnostics::ManifestFix;
 use crate::diagnostics::ScopedDiagnosticStats;
 use crate::diagnostics::get_key_value_span;
 use crate::diagnostics::workspace_rel_path;
@@ -122,8 +124,15 @@ pub(crate) fn lint_workspace(
             Group::with_title(Level::HELP.secondary_title("consider removing the unused field"));
         report.push(help);
 
+        pkg_stats.record_fix(
+            ManifestFix::new(ws.root_manifest()).edit(ManifestEdit::remove(&[
+                "workspace",
+                "package",
+                unused.as_ref(),
+            ])),
+        );
         pkg_stats.record_lint(lint_level);
-        gctx.shell().print_report(&report, lint_level.force())?;
+        pkg_stats.print_report(&report, lint_level.force(), gctx)?;
     }
 
     Ok(())
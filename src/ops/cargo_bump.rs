@@ -0,0 +1,270 @@
+//! Core of cargo-bump command
+
+use std::str::FromStr;
+
+use anyhow::Context as _;
+use anyhow::bail;
+use cargo_util::paths;
+use semver::Prerelease;
+use semver::Version;
+use semver::VersionReq;
+
+use crate::CargoResult;
+use crate::GlobalContext;
+use crate::util::data_structures::HashSet;
+use crate::workspace::Package;
+use crate::workspace::Workspace;
+use crate::workspace::editor::dependency::Dependency;
+use crate::workspace::editor::dependency::Source;
+use crate::workspace::editor::manifest::LocalManifest;
+use crate::workspace::editor::upgrade::upgrade_requirement;
+
+/// The part of a version to bump.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BumpLevel {
+    Major,
+    Minor,
+    Patch,
+    Pre,
+}
+
+impl BumpLevel {
+    /// For clap.
+    pub const POSSIBLE_VALUES: [&'static str; 4] = ["major", "minor", "patch", "pre"];
+
+    /// The version following `version` at this level.
+    ///
+    /// Bumping a pre-release at the level it previews releases it, so
+    /// `1.1.0-alpha.2` becomes `1.1.0` with [`BumpLevel::Minor`]. A release
+    /// bumped with [`BumpLevel::Pre`] starts a pre-release of the next patch
+    /// version.
+    pub fn bump(self, version: &Version) -> CargoResult<Version> {
+        let pre_release = !version.pre.is_empty();
+        let mut next = Version::new(version.major, version.minor, version.patch);
+        match self {
+            BumpLevel::Major => {
+                if !(pre_release && version.minor == 0 && version.patch == 0) {
+                    next = Version::new(version.major + 1, 0, 0);
+                }
+            }
+            BumpLevel::Minor => {
+                if !(pre_release && version.patch == 0) {
+                    next = Version::new(version.major, version.minor + 1, 0);
+                }
+            }
+            BumpLevel::Patch => {
+                if !pre_release {
+                    next.patch += 1;
+                }
+            }
+            BumpLevel::Pre => {
+                let pre = if pre_release {
+                    let mut identifiers: Vec<_> =
+                        version.pre.split('.').map(str::to_owned).collect();
+                    match identifiers.last().and_then(|id| id.parse::<u64>().ok()) {
+                        Some(n) => *identifiers.last_mut().unwrap() = (n + 1).to_string(),
+                        None => identifiers.push("1".to_owned()),
+                    }
+                    identifiers.join(".")
+                } else {
+                    next.patch += 1;
+                    "alpha.1".to_owned()
+                };
+                next.pre = Prerelease::new(&pre)?;
+            }
+        }
+        Ok(next)
+    }
+}
+
+impl FromStr for BumpLevel {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> CargoResult<Self> {
+        match s {
+            "major" => Ok(BumpLevel::Major),
+            "minor" => Ok(BumpLevel::Minor),
+            "patch" => Ok(BumpLevel::Patch),
+            "pre" => Ok(BumpLevel::Pre),
+            l => bail!("unknown bump level `{}`", l),
+        }
+    }
+}
+
+/// Bump the version of packages.
+#[derive(Debug)]
+pub struct BumpOptions<'a> {
+    /// Configuration information for Cargo operations
+    pub gctx: &'a GlobalContext,
+    /// Packages to bump the version of
+    pub to_bump: Vec<&'a Package>,
+    /// Which part of the versions to bump
+    pub level: BumpLevel,
+    /// Whether or not to actually write the manifests
+    pub dry_run: bool,
+}
+
+/// Bumps the version of the packages, and updates the requirements of their
+/// dependents in the workspace.
+///
+/// A package inheriting its version bumps `workspace.package.version`, and so
+/// every package inheriting it.
+pub fn bump(ws: &Workspace<'_>, options: &BumpOptions<'_>) -> CargoResult<()> {
+    let gctx = options.gctx;
+
+    let mut workspace_version = None;
+    let mut bumped = Vec::new();
+    for pkg in &options.to_bump {
+        if inherits_version(pkg) {
+            if workspace_version.is_none() {
+                workspace_version = Some(options.level.bump(pkg.version())?);
+            }
+        } else {
+            bumped.push((*pkg, options.level.bump(pkg.version())?));
+        }
+    }
+    if let Some(version) = &workspace_version {
+        bumped.extend(
+            ws.members()
+                .filter(|member| inherits_version(member))
+                .map(|member| (member, version.clone())),
+        );
+    }
+    bumped.sort_by_key(|(pkg, _)| pkg.package_id());
+
+    for (pkg, version) in &bumped {
+        gctx.shell().status(
+            "Bumping",
+            format!("{} v{} -> v{}", pkg.name(), pkg.version(), version),
+        )?;
+    }
+
+    let items = std::iter::once((ws.root_manifest(), ws.unstable_features()))
+        .chain(
+            ws.members()
+                .filter(|member| member.manifest_path() != ws.root_manifest())
+                .map(|member| {
+                    (
+                        member.manifest_path(),
+                        member.manifest().unstable_features(),
+                    )
+                }),
+        )
+        .collect::<Vec<_>>();
+
+    let mut upgrade_messages = HashSet::default();
+    for (manifest_path, unstable_features) in items {
+        let crate_root = manifest_path
+            .parent()
+            .expect("manifest path is absolute")
+            .to_owned();
+
+        let mut manifest = LocalManifest::try_new(manifest_path)?;
+        let mut manifest_has_changed = false;
+
+        if let Some((_, version)) = bumped
+            .iter()
+            .find(|(pkg, _)| pkg.manifest_path() == manifest_path && !inherits_version(pkg))
+        {
+            if let Some(package) = manifest
+                .data
+                .get_mut("package")
+                .and_then(|p| p.as_table_like_mut())
+            {
+                overwrite_value(package, "version", version.to_string());
+                manifest_has_changed = true;
+            }
+        }
+        if manifest_path == ws.root_manifest()
+            && let Some(version) = &workspace_version
+            && let Some(package) = manifest
+                .data
+                .get_mut("workspace")
+                .and_then(|w| w.get_mut("package"))
+                .and_then(|p| p.as_table_like_mut())
+        {
+            overwrite_value(package, "version", version.to_string());
+            manifest_has_changed = true;
+        }
+
+        for dep_table in manifest.get_dependency_tables_mut() {
+            for (dep_key, dep_item) in dep_table.iter_mut() {
+                let dependency = Dependency::from_toml(
+                    gctx,
+                    ws.root(),
+                    &crate_root,
+                    unstable_features,
+                    dep_key.get(),
+                    dep_item,
+                )?;
+                let Some(Source::Path(source)) = dependency.source() else {
+                    continue;
+                };
+                let Some(current) = &source.version else {
+                    continue;
+                };
+                let path = paths::normalize_path(&source.path);
+                let Some((pkg, version)) = bumped.iter().find(|(pkg, _)| pkg.root() == path) else {
+                    continue;
+                };
+
+                let upgraded = match upgrade_requirement(current, version) {
+                    Ok(upgraded) => upgraded,
+                    // Leave requirements like `>=0.1` alone, as long as they still match.
+                    Err(_) if VersionReq::parse(current).is_ok_and(|req| req.matches(version)) => {
+                        None
+                    }
+                    Err(e) => {
+                        return Err(e).with_context(|| {
+                            format!(
+                                "failed to update the requirement on `{}` in `{}`",
+                                pkg.name(),
+                                manifest_path.display()
+                            )
+                        });
+                    }
+                };
+                let Some((new_req, _)) = upgraded else {
+                    continue;
+                };
+
+                let upgrade_message = format!("{} {current} -> {new_req}", pkg.name());
+                if upgrade_messages.insert(upgrade_message.clone()) {
+                    gctx.shell().status("Upgrading", upgrade_message)?;
+                }
+                let table = dep_item
+                    .as_table_like_mut()
+                    .expect("path dependencies are tables");
+                overwrite_value(table, "version", new_req);
+                manifest_has_changed = true;
+            }
+        }
+
+        if manifest_has_changed && !options.dry_run {
+            manifest.write()?;
+        }
+    }
+
+    if options.dry_run {
+        gctx.shell().warn("aborting bump due to dry run")?;
+    }
+
+    Ok(())
+}
+
+/// Whether the package has `version.workspace = true`.
+fn inherits_version(pkg: &Package) -> bool {
+    pkg.manifest()
+        .original_toml()
+        .and_then(|toml| toml.package())
+        .and_then(|package| package.version.as_ref())
+        .is_some_and(|version| version.is_inherited())
+}
+
+fn overwrite_value(table: &mut dyn toml_edit::TableLike, key: &str, value: String) {
+    let mut value = toml_edit::Value::from(value);
+    let existing = table.entry(key).or_insert_with(Default::default);
+    if let Some(existing_value) = existing.as_value() {
+        *value.decor_mut() = existing_value.decor().clone();
+    }
+    *existing = toml_edit::Item::Value(value);
+}
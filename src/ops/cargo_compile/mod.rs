@@ -52,10 +52,11 @@ use crate::drop_println;
 use crate::ops;
 use crate::ops::resolve::{SpecsAndResolvedFeatures, WorkspaceResolve};
 use crate::resolver::features::{self, CliFeatures, FeaturesFor};
-use crate::resolver::{ForceAllTargets, HasDevUnits, Resolve};
+use crate::resolver::{ForceAllTargets, HasDevUnits, Resolve, ResolveError};
 use crate::util::BuildLogger;
 use crate::util::interning::InternedString;
 use crate::util::log_message::LogMessage;
+use crate::util::machine_message::{self, Message as _};
 use crate::util::{CargoResult, StableHasher};
 use crate::workspace::profiles::Profiles;
 use crate::workspace::{PackageId, PackageSet, SourceId, TargetKind, Workspace};
@@ -195,7 +196,9 @@ fn compile_ws<'a>(
     }
     crate::workspace::gc::auto_gc(bcx.gctx);
     let build_runner = BuildRunner::new(&bcx)?;
-    if options.build_config.dry_run {
+    if let Some(spec) = &options.build_config.explain_fresh {
+        build_runner.explain_fresh(spec)
+    } else if options.build_config.dry_run {
         build_runner.dry_run()
     } else {
         build_runner.compile(exec)
@@ -317,7 +320,7 @@ pub fn create_bcx<'a, 'gctx>(
         logger.log(LogMessage::ResolutionStarted { elapsed });
     }
 
-    let resolve = ops::resolve_ws_with_opts(
+    let resolve = match ops::resolve_ws_with_opts(
         ws,
         &mut target_data,
         &build_config.requested_kinds,
@@ -326,7 +329,29 @@ pub fn create_bcx<'a, 'gctx>(
         has_dev_units,
         ForceAllTargets::No,
         dry_run,
-    )?;
+    ) {
+        Ok(resolve) => resolve,
+        Err(e) => {
+            if build_config.emit_json() {
+                emit_resolve_error(gctx, &e);
+            }
+            return Err(e);
+        }
+    };
+    let lockfile_pass_output = {
+        let pass = crate::diagnostics::passes::LockfilePass::new(
+            ws,
+            &resolve,
+            &mut target_data,
+            &build_config.requested_kinds,
+        );
+        crate::diagnostics::passes::emit_lockfile_diagnostics(
+            ws,
+            &pass,
+            crate::diagnostics::rules::LOCKFILE_PASS_RULES,
+        )?
+    };
+
     let WorkspaceResolve {
         mut pkg_set,
         workspace_resolve,
@@ -549,14 +574,16 @@ pub fn create_bcx<'a, 'gctx>(
 
         for (index, unit) in units.into_iter().enumerate() {
             let index = UnitIndex(index as u64);
-            let dependencies = unit_graph
-                .get(unit)
-                .map(|deps| {
-                    deps.iter()
-                        .filter_map(|dep| unit_to_index.get(&dep.unit).copied())
-                        .collect()
-                })
-                .unwrap_or_default();
+            let deps = unit_graph.get(unit).map(Vec::as_slice).unwrap_or_default();
+            let dependencies = deps
+                .iter()
+                .filter_map(|dep| unit_to_index.get(&dep.unit).copied())
+                .collect();
+            let rmeta_dependencies = deps
+                .iter()
+                .filter(|dep| unit.only_requires_rmeta(&dep.unit))
+                .filter_map(|dep| unit_to_index.get(&dep.unit).copied())
+                .collect();
             logger.log(LogMessage::UnitRegistered {
                 package_id: unit.pkg.package_id().to_spec(),
                 target: (&unit.target).into(),
@@ -570,6 +597,7 @@ pub fn create_bcx<'a, 'gctx>(
                     .collect(),
                 requested: root_unit_indexes.contains(&index),
                 dependencies,
+                rmeta_dependencies,
             });
         }
         let elapsed = ws.gctx().invocation_instant().elapsed().as_secs_f64();
@@ -682,7 +710,7 @@ where `<compatible-ver>` is the latest version supporting rustc {rustc_version}"
         }
     }
 
-    let bcx = BuildContext::new(
+    let mut bcx = BuildContext::new(
         ws,
         logger,
         pkg_set,
@@ -696,10 +724,29 @@ where `<compatible-ver>` is the latest version supporting rustc {rustc_version}"
         unit_to_index,
         scrape_units,
     )?;
+    bcx.lint_warning_count = lockfile_pass_output.lint_warning_count;
 
     Ok(bcx)
 }
 
+/// Emits the structured explanation of a dependency resolution failure as a
+/// `resolve-error` message, for `--message-format=json`.
+fn emit_resolve_error(gctx: &GlobalContext, error: &anyhow::Error) {
+    let Some((resolve_error, conflict)) = error
+        .chain()
+        .filter_map(|e| e.downcast_ref::<ResolveError>())
+        .find_map(|e| Some((e, e.conflict()?)))
+    else {
+        return;
+    };
+    let msg = machine_message::ResolveError {
+        message: resolve_error.to_string(),
+        conflict,
+    }
+    .to_json_string();
+    drop_println!(gctx, "{msg}");
+}
+
 // Checks if a target path exists and is a source file, not a directory
 fn validate_target_path_as_source_file(
     gctx: &GlobalContext,
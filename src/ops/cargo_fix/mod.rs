@@ -55,6 +55,7 @@ use tracing::{debug, trace, warn};
 pub use self::fix_edition::fix_edition;
 use crate::compiler::CompileKind;
 use crate::compiler::RustcTargetData;
+use crate::diagnostics;
 use crate::ops::resolve::WorkspaceResolve;
 use crate::ops::{self, CompileOptions};
 use crate::resolver::features::{DiffMap, FeatureOpts, FeatureResolver, FeaturesFor};
@@ -294,6 +295,35 @@ fn check_version_control(gctx: &GlobalContext, opts: &FixOptions) -> CargoResult
 }
 
 fn fix_manifests(ws: &Workspace<'_>, pkgs: &[&Package]) -> CargoResult<()> {
+    let lint_fixes =
+        diagnostics::passes::collect_parse_fixes(ws, diagnostics::rules::PARSE_PASS_RULES)?;
+    let lint_fixes_for = |manifest_path: &Path| {
+        lint_fixes
+            .iter()
+            .filter(|fix| fix.manifest_path == manifest_path)
+            .collect::<Vec<_>>()
+    };
+
+    if !pkgs.iter().any(|p| p.manifest_path() == ws.root_manifest()) {
+        // Only apply the workspace's fixes to the root manifest of an unselected package
+        let ws_fixes = lint_fixes_for(ws.root_manifest())
+            .into_iter()
+            .filter(|fix| {
+                fix.edits
+                    .iter()
+                    .all(|edit| edit.key_path().first().map(|k| k.as_str()) == Some("workspace"))
+            })
+            .collect::<Vec<_>>();
+        if !ws_fixes.is_empty() {
+            let mut manifest_mut = LocalManifest::try_new(ws.root_manifest())?;
+            let fixes = diagnostics::apply_fixes(&mut manifest_mut.data, &ws_fixes);
+            report_manifest_fixes(ws, ws.root_manifest(), fixes)?;
+            if 0 < fixes {
+                manifest_mut.write()?;
+            }
+        }
+    }
+
     for pkg in pkgs {
         let mut manifest_mut = LocalManifest::try_new(pkg.manifest_path())?;
         let mut fixes = 0;
@@ -302,14 +332,11 @@ fn fix_manifests(ws: &Workspace<'_>, pkgs: &[&Package]) -> CargoResult<()> {
             fixes += 1;
         }
 
-        if 0 < fixes {
-            let file = pkg.manifest_path();
-            let file = file.strip_prefix(ws.root()).unwrap_or(file);
-            let file = file.display();
-            let verb = if fixes == 1 { "fix" } else { "fixes" };
-            let msg = format!("{file} ({fixes} {verb})");
-            ws.gctx().shell().status("Fixed", msg)?;
+        fixes +=
+            diagnostics::apply_fixes(&mut manifest_mut.data, &lint_fixes_for(pkg.manifest_path()));
 
+        report_manifest_fixes(ws, pkg.manifest_path(), fixes)?;
+        if 0 < fixes {
             manifest_mut.write()?;
         }
     }
@@ -317,6 +344,17 @@ fn fix_manifests(ws: &Workspace<'_>, pkgs: &[&Package]) -> CargoResult<()> {
     Ok(())
 }
 
+fn report_manifest_fixes(ws: &Workspace<'_>, file: &Path, fixes: usize) -> CargoResult<()> {
+    if 0 < fixes {
+        let file = file.strip_prefix(ws.root()).unwrap_or(file);
+        let file = file.display();
+        let verb = if fixes == 1 { "fix" } else { "fixes" };
+        let msg = format!("{file} ({fixes} {verb})");
+        ws.gctx().shell().status("Fixed", msg)?;
+    }
+    Ok(())
+}
+
 fn migrate_manifests(
     ws: &Workspace<'_>,
     pkgs: &[&Package],
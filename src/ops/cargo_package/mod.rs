@@ -1121,7 +1121,7 @@ fn check_filename(file: &Path, shell: &mut Shell) -> CargoResult<()> {
 /// Manages a temporary local registry that we use to overlay our new packages on the
 /// upstream registry. This way we can build lockfiles that depend on the new packages even
 /// before they're published.
-struct TmpRegistry<'a> {
+pub(crate) struct TmpRegistry<'a> {
     gctx: &'a GlobalContext,
     upstream: SourceId,
     root: Filesystem,
@@ -1130,7 +1130,11 @@ struct TmpRegistry<'a> {
 }
 
 impl<'a> TmpRegistry<'a> {
-    fn new(gctx: &'a GlobalContext, root: Filesystem, upstream: SourceId) -> CargoResult<Self> {
+    pub(crate) fn new(
+        gctx: &'a GlobalContext,
+        root: Filesystem,
+        upstream: SourceId,
+    ) -> CargoResult<Self> {
         root.create_dir()?;
         let _lock = root.open_rw_exclusive_create(".cargo-lock", gctx, "temporary registry")?;
         let slf = Self {
@@ -1153,7 +1157,12 @@ impl<'a> TmpRegistry<'a> {
         self.root.join("index")
     }
 
-    fn add_package(
+    /// The source of this registry, for overlaying it on the upstream one.
+    pub(crate) fn source_id(&self) -> CargoResult<SourceId> {
+        SourceId::for_local_registry(self.root.as_path_unlocked())
+    }
+
+    pub(crate) fn add_package(
         &mut self,
         ws: &Workspace<'_>,
         package: &Package,
@@ -1,12 +1,13 @@
 //! The `cargo report rebuilds` command.
 
-use crate::util::data_structures::{HashMap, HashSet};
+use crate::util::data_structures::{HashMap, HashSet, IndexMap};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
 use anyhow::Context as _;
 use cargo_util_schemas::core::PackageIdSpec;
+use cargo_util_schemas::core::SourceKind;
 use cargo_util_terminal::report::Group;
 use cargo_util_terminal::report::Level;
 use itertools::Itertools as _;
@@ -19,12 +20,15 @@ use crate::compiler::UnitIndex;
 use crate::compiler::fingerprint::DirtyReason;
 use crate::compiler::fingerprint::FsStatus;
 use crate::compiler::fingerprint::StaleItem;
+use crate::compiler::timings::report::round_to_centisecond;
+use crate::ops::cargo_report::util::ReportMessageFormat;
 use crate::ops::cargo_report::util::find_log_file;
 use crate::ops::cargo_report::util::unit_target_description;
 use crate::util::log_message::FingerprintStatus;
 use crate::util::log_message::LogMessage;
 use crate::util::log_message::Target;
 use crate::util::logger::RunId;
+use crate::util::machine_message::{self, Message as _};
 use crate::util::style;
 use crate::workspace::Workspace;
 
@@ -32,6 +36,9 @@ const DEFAULT_DISPLAY_LIMIT: usize = 5;
 
 pub struct ReportRebuildsOptions {
     pub id: Option<RunId>,
+    /// Compare two sessions (base, new) instead of reporting on a single one.
+    pub compare: Option<(RunId, RunId)>,
+    pub fmt: ReportMessageFormat,
 }
 
 pub fn report_rebuilds(
@@ -39,39 +46,54 @@ pub fn report_rebuilds(
     ws: Option<&Workspace<'_>>,
     opts: ReportRebuildsOptions,
 ) -> CargoResult<()> {
+    if let Some((base_id, new_id)) = &opts.compare {
+        return report_comparison(gctx, ws, base_id, new_id, opts.fmt);
+    }
+
     let Some((log, run_id)) = find_log_file(gctx, ws, opts.id.as_ref())? else {
-        let context = if let Some(ws) = ws {
-            format!(" for workspace at `{}`", ws.root().display())
-        } else {
-            String::new()
-        };
-        let (title, note) = if let Some(id) = &opts.id {
-            (
-                format!("session `{id}` not found{context}"),
-                "run `cargo report sessions` to list available sessions",
-            )
-        } else {
-            (
-                format!("no sessions found{context}"),
-                "run command with `-Z build-analysis` to generate log files",
-            )
-        };
-        let report = [Level::ERROR
-            .primary_title(title)
-            .element(Level::NOTE.message(note))];
-        gctx.shell().print_report(&report, false)?;
-        return Err(AlreadyPrintedError::new(anyhow::anyhow!("")).into());
+        return session_not_found(gctx, ws, opts.id.as_ref());
     };
 
     let ctx = prepare_context(&log)
         .with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
     let ws_root = ws.map(|ws| ws.root()).unwrap_or(gctx.cwd());
 
-    display_report(gctx, ctx, &run_id, ws_root)?;
+    match opts.fmt {
+        ReportMessageFormat::Human => display_report(gctx, ctx, &run_id, ws_root)?,
+        ReportMessageFormat::Json => emit_json(gctx, ctx, &run_id)?,
+    }
 
     Ok(())
 }
 
+fn session_not_found(
+    gctx: &GlobalContext,
+    ws: Option<&Workspace<'_>>,
+    id: Option<&RunId>,
+) -> CargoResult<()> {
+    let context = if let Some(ws) = ws {
+        format!(" for workspace at `{}`", ws.root().display())
+    } else {
+        String::new()
+    };
+    let (title, note) = if let Some(id) = id {
+        (
+            format!("session `{id}` not found{context}"),
+            "run `cargo report sessions` to list available sessions",
+        )
+    } else {
+        (
+            format!("no sessions found{context}"),
+            "run command with `-Z build-analysis` to generate log files",
+        )
+    };
+    let report = [Level::ERROR
+        .primary_title(title)
+        .element(Level::NOTE.message(note))];
+    gctx.shell().print_report(&report, false)?;
+    Err(AlreadyPrintedError::new(anyhow::anyhow!("")).into())
+}
+
 struct Context {
     root_rebuilds: Vec<RootRebuild>,
     units: HashMap<UnitIndex, UnitInfo>,
@@ -371,6 +393,52 @@ fn display_report(
     Ok(())
 }
 
+/// Emits the rebuilds of a session as JSON messages.
+fn emit_json(gctx: &GlobalContext, ctx: Context, run_id: &RunId) -> CargoResult<()> {
+    let Context {
+        root_rebuilds,
+        units,
+        total_cached,
+        total_new,
+        total_rebuilt,
+    } = ctx;
+
+    let mut shell = gctx.shell();
+    for root_rebuild in &root_rebuilds {
+        let msg = machine_message::ReportRootRebuild {
+            session_id: run_id.to_string(),
+            unit: units[&root_rebuild.unit_index].to_report_unit(),
+            cause: &root_rebuild.reason,
+            affected_units: root_rebuild
+                .affected_units
+                .iter()
+                .filter_map(|index| units.get(index))
+                .map(UnitInfo::to_report_unit)
+                .collect(),
+        };
+        writeln!(shell.out(), "{}", msg.to_json_string())?;
+    }
+    let msg = machine_message::ReportRebuildsFinished {
+        session_id: run_id.to_string(),
+        rebuilt_units: total_rebuilt,
+        cached_units: total_cached,
+        new_units: total_new,
+    };
+    writeln!(shell.out(), "{}", msg.to_json_string())?;
+
+    Ok(())
+}
+
+impl UnitInfo {
+    fn to_report_unit(&self) -> machine_message::ReportUnit<'_> {
+        machine_message::ReportUnit {
+            package_id: &self.package_id,
+            target: &self.target,
+            mode: self.mode,
+        }
+    }
+}
+
 fn unit_description(unit: &UnitInfo) -> String {
     let name = unit.package_id.name();
     let version = unit
@@ -528,3 +596,341 @@ fn format_dirty_reason(
         DirtyReason::FreshBuild => "fresh build".to_string(),
     }
 }
+
+/// Identifies a unit across sessions.
+///
+/// Unit indices are only meaningful within a single session,
+/// so units are lined up by package, target, mode, platform, and profile instead.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct UnitKey {
+    name: String,
+    version: Option<String>,
+    /// Source URL of non-path packages.
+    ///
+    /// Path packages are matched by name and version only,
+    /// so that sessions from different checkouts can be lined up.
+    source: Option<String>,
+    target_name: String,
+    target_kind: String,
+    mode: CompileMode,
+    platform: String,
+    profile: String,
+}
+
+/// A unit of one session participating in a comparison.
+struct SessionUnit {
+    index: UnitIndex,
+    status: Option<FingerprintStatus>,
+    cause: Option<DirtyReason>,
+    start: Option<f64>,
+    duration: Option<f64>,
+}
+
+/// All units of one session, ready for comparison.
+struct Session {
+    run_id: RunId,
+    workspace_root: Option<std::path::PathBuf>,
+    infos: HashMap<UnitIndex, UnitInfo>,
+    units: IndexMap<UnitKey, SessionUnit>,
+}
+
+fn load_session(log: &Path, run_id: RunId) -> CargoResult<Session> {
+    let reader = BufReader::new(File::open(log)?);
+
+    let mut profile = String::new();
+    let mut workspace_root = None;
+    let mut infos: HashMap<UnitIndex, UnitInfo> = HashMap::default();
+    let mut keys: HashMap<UnitIndex, UnitKey> = HashMap::default();
+    let mut units: IndexMap<UnitKey, SessionUnit> = IndexMap::default();
+
+    for (log_index, result) in serde_json::Deserializer::from_reader(reader)
+        .into_iter::<LogMessage>()
+        .enumerate()
+    {
+        let msg = match result {
+            Ok(msg) => msg,
+            Err(e) => {
+                tracing::warn!("failed to parse log message at index {log_index}: {e}");
+                continue;
+            }
+        };
+
+        match msg {
+            LogMessage::BuildStarted {
+                profile: p,
+                workspace_root: root,
+                ..
+            } => {
+                profile = p;
+                workspace_root = Some(root);
+            }
+            LogMessage::UnitRegistered {
+                package_id,
+                target,
+                mode,
+                platform,
+                index,
+                ..
+            } => {
+                let source = match package_id.kind() {
+                    Some(SourceKind::Path) | None => None,
+                    Some(_) => package_id.url().map(|url| url.to_string()),
+                };
+                let key = UnitKey {
+                    name: package_id.name().to_owned(),
+                    version: package_id.version().map(|v| v.to_string()),
+                    source,
+                    target_name: target.name.clone(),
+                    target_kind: target.kind.to_string(),
+                    mode,
+                    platform,
+                    profile: profile.clone(),
+                };
+                units.insert(
+                    key.clone(),
+                    SessionUnit {
+                        index,
+                        status: None,
+                        cause: None,
+                        start: None,
+                        duration: None,
+                    },
+                );
+                keys.insert(index, key);
+                infos.insert(
+                    index,
+                    UnitInfo {
+                        package_id,
+                        target,
+                        mode,
+                    },
+                );
+            }
+            LogMessage::UnitFingerprint {
+                index,
+                status,
+                cause,
+            } => {
+                if let Some(unit) = keys.get(&index).and_then(|key| units.get_mut(key)) {
+                    unit.status = Some(status);
+                    unit.cause = cause;
+                }
+            }
+            LogMessage::UnitStarted { index, elapsed } => {
+                if let Some(unit) = keys.get(&index).and_then(|key| units.get_mut(key)) {
+                    unit.start = Some(elapsed);
+                }
+            }
+            LogMessage::UnitFinished { index, elapsed, .. } => {
+                if let Some(unit) = keys.get(&index).and_then(|key| units.get_mut(key)) {
+                    unit.duration = unit.start.map(|start| f64::max(elapsed - start, 0.0));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Session {
+        run_id,
+        workspace_root,
+        infos,
+        units,
+    })
+}
+
+struct Regression {
+    desc: String,
+    base: f64,
+    new: f64,
+}
+
+fn report_comparison(
+    gctx: &GlobalContext,
+    ws: Option<&Workspace<'_>>,
+    base_id: &RunId,
+    new_id: &RunId,
+    fmt: ReportMessageFormat,
+) -> CargoResult<()> {
+    let mut sessions = Vec::with_capacity(2);
+    for id in [base_id, new_id] {
+        let Some((log, run_id)) = find_log_file(gctx, ws, Some(id))? else {
+            return session_not_found(gctx, ws, Some(id));
+        };
+        let session = load_session(&log, run_id)
+            .with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
+        sessions.push(session);
+    }
+    let new = sessions.pop().expect("two sessions loaded");
+    let base = sessions.pop().expect("two sessions loaded");
+
+    if fmt == ReportMessageFormat::Json {
+        return emit_comparison_json(gctx, &base, &new);
+    }
+
+    let describe = |session: &Session, unit: &SessionUnit| {
+        session
+            .infos
+            .get(&unit.index)
+            .map(unit_description)
+            .expect("must have the unit")
+    };
+
+    let only_in_base: Vec<_> = base
+        .units
+        .iter()
+        .filter(|(key, _)| !new.units.contains_key(*key))
+        .map(|(_, unit)| describe(&base, unit))
+        .collect();
+    let only_in_new: Vec<_> = new
+        .units
+        .iter()
+        .filter(|(key, _)| !base.units.contains_key(*key))
+        .map(|(_, unit)| describe(&new, unit))
+        .collect();
+
+    let mut in_both = 0;
+    let mut status_changes = Vec::new();
+    let mut regressions = Vec::new();
+    for (key, new_unit) in &new.units {
+        let Some(base_unit) = base.units.get(key) else {
+            continue;
+        };
+        in_both += 1;
+        let desc = describe(&new, new_unit);
+
+        if base_unit.status != new_unit.status {
+            let base_status = status_description(base_unit.status.as_ref());
+            let new_status = status_description(new_unit.status.as_ref());
+            let mut change = format!("{desc}: {base_status} -> {new_status}");
+            if let Some(cause) = &new_unit.cause {
+                let ws_root = new.workspace_root.as_deref().unwrap_or(gctx.cwd());
+                let reason = format_dirty_reason(cause, &new.infos, ws_root);
+                change.push_str(&format!(" ({reason})"));
+            }
+            status_changes.push(change);
+        }
+
+        if let (Some(base_duration), Some(new_duration)) = (base_unit.duration, new_unit.duration) {
+            let base_duration = round_to_centisecond(base_duration);
+            let new_duration = round_to_centisecond(new_duration);
+            if new_duration > base_duration {
+                regressions.push(Regression {
+                    desc,
+                    base: base_duration,
+                    new: new_duration,
+                });
+            }
+        }
+    }
+    regressions.sort_by(|a, b| (b.new - b.base).total_cmp(&(a.new - a.base)));
+    let regressions: Vec<_> = regressions
+        .into_iter()
+        .map(|Regression { desc, base, new }| {
+            let delta = new - base;
+            format!("{desc}: {base:.2}s -> {new:.2}s (+{delta:.2}s)")
+        })
+        .collect();
+
+    let verbose = gctx.shell().verbosity() == cargo_util_terminal::Verbosity::Verbose;
+    let header = style::HEADER;
+    let mut shell = gctx.shell();
+    let stderr = shell.err();
+
+    writeln!(stderr, "{header}Base session:{header:#} {}", base.run_id)?;
+    writeln!(stderr, "{header}New session:{header:#}  {}", new.run_id)?;
+    let only_in_base_count = only_in_base.len();
+    let only_in_new_count = only_in_new.len();
+    writeln!(
+        stderr,
+        "{header}Status:{header:#} {in_both} unit{} in both, {only_in_base_count} only in base, {only_in_new_count} only in new",
+        plural(in_both),
+    )?;
+
+    let mut truncated = false;
+    for (title, items) in [
+        ("Only in base", &only_in_base),
+        ("Only in new", &only_in_new),
+        ("Fingerprint changes", &status_changes),
+        ("Duration regressions", &regressions),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        let display_limit = if verbose {
+            items.len()
+        } else {
+            DEFAULT_DISPLAY_LIMIT.min(items.len())
+        };
+        writeln!(stderr)?;
+        if display_limit < items.len() {
+            truncated = true;
+            let count = items.len();
+            writeln!(
+                stderr,
+                "{header}{title}:{header:#} (first {display_limit} of {count})"
+            )?;
+        } else {
+            writeln!(stderr, "{header}{title}:{header:#}")?;
+        }
+        for item in items.iter().take(display_limit) {
+            writeln!(stderr, "  - {item}")?;
+        }
+    }
+
+    drop(shell);
+    if truncated {
+        writeln!(gctx.shell().err())?;
+        let note = "pass `--verbose` to show all differences";
+        gctx.shell().print_report(
+            &[Group::with_title(Level::NOTE.secondary_title(note))],
+            false,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Emits one JSON message for every unit in either session.
+fn emit_comparison_json(gctx: &GlobalContext, base: &Session, new: &Session) -> CargoResult<()> {
+    fn state(unit: &SessionUnit) -> machine_message::ReportUnitState<'_> {
+        machine_message::ReportUnitState {
+            status: unit.status,
+            cause: unit.cause.as_ref(),
+            duration: unit.duration.map(round_to_centisecond),
+        }
+    }
+
+    let mut shell = gctx.shell();
+    let only_in_base = base
+        .units
+        .iter()
+        .filter(|(key, _)| !new.units.contains_key(*key));
+    for (key, unit) in new.units.iter().chain(only_in_base) {
+        let base_unit = base.units.get(key);
+        let new_unit = new.units.get(key);
+        let info = match new_unit {
+            Some(unit) => &new.infos[&unit.index],
+            None => &base.infos[&unit.index],
+        };
+        let msg = machine_message::ReportUnitComparison {
+            base_session_id: base.run_id.to_string(),
+            new_session_id: new.run_id.to_string(),
+            unit: info.to_report_unit(),
+            platform: &key.platform,
+            base: base_unit.map(state),
+            new: new_unit.map(state),
+        };
+        writeln!(shell.out(), "{}", msg.to_json_string())?;
+    }
+
+    Ok(())
+}
+
+fn status_description(status: Option<&FingerprintStatus>) -> &'static str {
+    match status {
+        Some(FingerprintStatus::New) => "new",
+        Some(FingerprintStatus::Dirty) => "dirty",
+        Some(FingerprintStatus::Fresh) => "fresh",
+        None => "unknown",
+    }
+}
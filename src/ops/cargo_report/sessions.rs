@@ -4,12 +4,15 @@ use cargo_util_terminal::report::Level;
 
 use crate::CargoResult;
 use crate::GlobalContext;
+use crate::ops::cargo_report::util::ReportMessageFormat;
 use crate::ops::cargo_report::util::list_log_files;
 use crate::util::BuildLogger;
+use crate::util::machine_message::{self, Message as _};
 use crate::workspace::Workspace;
 
 pub struct ReportSessionsOptions {
     pub limit: usize,
+    pub fmt: ReportMessageFormat,
 }
 
 pub fn report_sessions(
@@ -49,6 +52,18 @@ pub fn report_sessions(
     let truncated = sessions.len() > opts.limit;
     let display_count = opts.limit.min(sessions.len());
 
+    if opts.fmt == ReportMessageFormat::Json {
+        let mut shell = gctx.shell();
+        for (_path, run_id) in sessions.iter().take(display_count) {
+            let msg = machine_message::ReportSession {
+                id: run_id.to_string(),
+                timestamp: *run_id.timestamp(),
+            };
+            writeln!(shell.out(), "{}", msg.to_json_string())?;
+        }
+        return Ok(());
+    }
+
     let mut shell = gctx.shell();
     let stderr = shell.err();
 
@@ -1,8 +1,10 @@
 //! The `cargo report timings` command.
 
+use crate::util::data_structures::HashMap;
 use crate::util::data_structures::HashSet;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::Path;
 use std::path::PathBuf;
 
 use crate::util::data_structures::IndexMap;
@@ -19,17 +21,25 @@ use crate::GlobalContext;
 use crate::compiler::UnitIndex;
 use crate::compiler::timings::CompilationSection;
 use crate::compiler::timings::UnitData;
+use crate::compiler::timings::chrome_trace::Phase;
+use crate::compiler::timings::chrome_trace::write_chrome_trace;
 use crate::compiler::timings::report::RenderContext;
+use crate::compiler::timings::report::Trends;
 use crate::compiler::timings::report::aggregate_sections;
 use crate::compiler::timings::report::compute_concurrency;
+use crate::compiler::timings::report::compute_critical_path;
+use crate::compiler::timings::report::frontend_end;
 use crate::compiler::timings::report::round_to_centisecond;
 use crate::compiler::timings::report::write_html;
+use crate::ops::cargo_report::util::ReportMessageFormat;
 use crate::ops::cargo_report::util::find_log_file;
+use crate::ops::cargo_report::util::list_log_files;
 use crate::ops::cargo_report::util::unit_target_description;
 use crate::util::log_message::FingerprintStatus;
 use crate::util::log_message::LogMessage;
 use crate::util::log_message::Target;
 use crate::util::logger::RunId;
+use crate::util::machine_message::{self, Message as _};
 use crate::util::style;
 use crate::workspace::Workspace;
 
@@ -38,6 +48,40 @@ pub struct ReportTimingsOptions<'gctx> {
     pub open_result: bool,
     pub gctx: &'gctx GlobalContext,
     pub id: Option<RunId>,
+    /// Aggregate timing trends across this many most recent sessions.
+    pub last: Option<usize>,
+    pub fmt: ReportTimingsFormat,
+}
+
+/// Output format of `cargo report timings`.
+///
+/// Extends [`ReportMessageFormat`] with the Chrome trace file format.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReportTimingsFormat {
+    /// Writes an HTML timing report.
+    Human,
+    /// Prints JSON messages to stdout.
+    Json,
+    /// Writes the session in the Trace Event Format.
+    ChromeTrace,
+}
+
+impl ReportTimingsFormat {
+    pub const POSSIBLE_VALUES: [&str; 3] = ["human", "json", "chrome-trace"];
+}
+
+impl std::str::FromStr for ReportTimingsFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<ReportTimingsFormat, anyhow::Error> {
+        match s {
+            "chrome-trace" => Ok(ReportTimingsFormat::ChromeTrace),
+            s => match s.parse()? {
+                ReportMessageFormat::Human => Ok(ReportTimingsFormat::Human),
+                ReportMessageFormat::Json => Ok(ReportTimingsFormat::Json),
+            },
+        }
+    }
 }
 
 /// Collects sections data for later post-processing through [`aggregate_sections`].
@@ -79,20 +123,48 @@ pub fn report_timings(
         return Err(AlreadyPrintedError::new(anyhow::anyhow!("")).into());
     };
 
-    let reader = BufReader::new(File::open(&log)?);
-    let iter = serde_json::Deserializer::from_reader(reader)
-        .into_iter::<LogMessage>()
-        .enumerate()
-        .filter_map(|(idx, msg)| match msg {
-            Ok(msg) => Some(msg),
-            Err(e) => {
-                tracing::warn!("failed to parse log message at index {idx}: {e}");
-                None
-            }
-        });
-    let ctx = prepare_context(iter, &run_id, true)
+    if opts.fmt != ReportTimingsFormat::Human {
+        if opts.last.is_some() {
+            anyhow::bail!("`--last` is only supported with `--message-format=human`");
+        }
+        if opts.open_result {
+            anyhow::bail!("`--open` is only supported with `--message-format=human`");
+        }
+    }
+
+    if opts.fmt == ReportTimingsFormat::Json {
+        return emit_json(gctx, &log, &run_id);
+    }
+
+    let messages =
+        read_log(&log).with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
+    let phases = build_phases(&messages);
+    let mut ctx = prepare_context(messages.into_iter(), &run_id, true)
         .with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
 
+    if let Some(last) = opts.last {
+        // The reported session is always the newest data point of the trends.
+        let mut sessions: Vec<_> = list_log_files(gctx, ws)?
+            .skip_while(|(path, _)| *path != log)
+            .skip(1)
+            .filter_map(|(path, run_id)| match load_context(&path, &run_id) {
+                // Sessions with all units fresh would only skew the trends.
+                Ok(ctx) if !ctx.unit_data.is_empty() => Some(ctx),
+                Ok(_) => None,
+                Err(e) => {
+                    tracing::warn!("skipping session `{run_id}` for trends: {e:#}");
+                    None
+                }
+            })
+            .take(last.saturating_sub(1))
+            .collect();
+        sessions.reverse();
+        sessions.push(ctx);
+        let trends = Trends::new(&sessions);
+        ctx = sessions.pop().unwrap();
+        ctx.trends = Some(trends);
+    }
+
     // If we are in a workspace,
     // put timing reports in <target-dir>/cargo-timings/` as usual for easy access.
     // Otherwise in a temporary directory
@@ -109,7 +181,12 @@ pub fn report_timings(
         TempDir::with_prefix("cargo-timings-")?.keep()
     };
 
-    let timing_path = reports_dir.join(format!("cargo-timing-{run_id}.html"));
+    let extension = match opts.fmt {
+        ReportTimingsFormat::Human => "html",
+        ReportTimingsFormat::Json => unreachable!("JSON messages are printed to stdout"),
+        ReportTimingsFormat::ChromeTrace => "json",
+    };
+    let timing_path = reports_dir.join(format!("cargo-timing-{run_id}.{extension}"));
 
     let mut out_file = std::fs::OpenOptions::new()
         .write(true)
@@ -118,7 +195,11 @@ pub fn report_timings(
         .open(&timing_path)
         .with_context(|| format!("failed to open `{}`", timing_path.display()))?;
 
-    write_html(ctx, &mut out_file)?;
+    match opts.fmt {
+        ReportTimingsFormat::Human => write_html(ctx, &mut out_file)?,
+        ReportTimingsFormat::Json => unreachable!("JSON messages are printed to stdout"),
+        ReportTimingsFormat::ChromeTrace => write_chrome_trace(&ctx, &phases, &mut out_file)?,
+    }
 
     let link = gctx.shell().err_file_hyperlink(&timing_path);
     let msg = format!("report saved to {link}{}{link:#}", timing_path.display());
@@ -132,11 +213,115 @@ pub fn report_timings(
     Ok(())
 }
 
-pub(crate) fn prepare_context<I>(
+fn read_log(log: &Path) -> CargoResult<Vec<LogMessage>> {
+    let reader = BufReader::new(File::open(log)?);
+    let messages = serde_json::Deserializer::from_reader(reader)
+        .into_iter::<LogMessage>()
+        .enumerate()
+        .filter_map(|(idx, msg)| match msg {
+            Ok(msg) => Some(msg),
+            Err(e) => {
+                tracing::warn!("failed to parse log message at index {idx}: {e}");
+                None
+            }
+        })
+        .collect();
+    Ok(messages)
+}
+
+fn load_context<'a>(log: &Path, run_id: &RunId) -> CargoResult<RenderContext<'a>> {
+    read_log(log)
+        .and_then(|messages| prepare_context(messages.into_iter(), run_id, true))
+        .with_context(|| format!("failed to analyze log at `{}`", log.display()))
+}
+
+/// Collects the phases of a build outside of any unit.
+fn build_phases(messages: &[LogMessage]) -> Vec<Phase> {
+    let mut phases = Vec::new();
+    let mut resolution_start = None;
+    let mut unit_graph_start = None;
+    for msg in messages {
+        match msg {
+            LogMessage::ResolutionStarted { elapsed } => resolution_start = Some(*elapsed),
+            LogMessage::ResolutionFinished { elapsed } => {
+                if let Some(start) = resolution_start.take() {
+                    phases.push(Phase {
+                        name: "resolution",
+                        start,
+                        end: *elapsed,
+                    });
+                }
+            }
+            LogMessage::UnitGraphStarted { elapsed } => unit_graph_start = Some(*elapsed),
+            LogMessage::UnitGraphFinished { elapsed } => {
+                if let Some(start) = unit_graph_start.take() {
+                    phases.push(Phase {
+                        name: "unit graph",
+                        start,
+                        end: *elapsed,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    phases
+}
+
+/// Emits the timings of a session as JSON messages.
+fn emit_json(gctx: &GlobalContext, log: &Path, run_id: &RunId) -> CargoResult<()> {
+    let messages =
+        read_log(log).with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
+    let units: HashMap<_, _> = messages
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMessage::UnitRegistered {
+                package_id,
+                target,
+                mode,
+                index,
+                ..
+            } => Some((*index, (package_id.clone(), target.clone(), *mode))),
+            _ => None,
+        })
+        .collect();
+    let ctx = prepare_context(messages.into_iter(), run_id, true)
+        .with_context(|| format!("failed to analyze log at `{}`", log.display()))?;
+
+    let mut shell = gctx.shell();
+    for unit in &ctx.unit_data {
+        let (package_id, target, mode) = &units[&unit.i];
+        let msg = machine_message::ReportUnitTiming {
+            session_id: run_id.to_string(),
+            unit: machine_message::ReportUnit {
+                package_id,
+                target,
+                mode: *mode,
+            },
+            start: unit.start,
+            duration: unit.duration,
+            rmeta_time: frontend_end(unit),
+            critical: unit.critical,
+            slack: unit.slack,
+        };
+        writeln!(shell.out(), "{}", msg.to_json_string())?;
+    }
+    let msg = machine_message::ReportTimingsFinished {
+        session_id: run_id.to_string(),
+        total_time: ctx.duration(),
+        fresh_units: ctx.total_fresh,
+        dirty_units: ctx.total_dirty,
+    };
+    writeln!(shell.out(), "{}", msg.to_json_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn prepare_context<'a, I>(
     log: I,
     run_id: &RunId,
     error_if_no_units: bool,
-) -> CargoResult<RenderContext<'_>>
+) -> CargoResult<RenderContext<'a>>
 where
     I: Iterator<Item = LogMessage>,
 {
@@ -155,6 +340,7 @@ where
         jobs: 0,
         num_cpus: None,
         error: &None,
+        trends: None,
     };
     let mut units: IndexMap<_, UnitEntry> = IndexMap::default();
 
@@ -162,6 +348,8 @@ where
 
     let mut requested_units: HashSet<UnitIndex> = HashSet::default();
 
+    let mut dependencies: HashMap<UnitIndex, Vec<(UnitIndex, bool)>> = HashMap::default();
+
     for msg in log {
         match msg {
             LogMessage::BuildStarted {
@@ -195,8 +383,15 @@ where
                 index,
                 features,
                 requested,
-                dependencies: _,
+                dependencies: deps,
+                rmeta_dependencies,
             } => {
+                let deps = deps
+                    .into_iter()
+                    .map(|dep| (dep, rmeta_dependencies.contains(&dep)))
+                    .collect();
+                dependencies.insert(index, deps);
+
                 if requested {
                     requested_units.insert(index);
                 }
@@ -227,6 +422,8 @@ where
                     unblocked_units: Vec::new(),
                     unblocked_rmeta_units: Vec::new(),
                     sections: None,
+                    critical: false,
+                    slack: 0.0,
                 };
 
                 units.insert(
@@ -415,6 +612,7 @@ where
         .collect();
 
     ctx.unit_data = unit_data;
+    compute_critical_path(&mut ctx.unit_data, &dependencies);
     ctx.concurrency = compute_concurrency(&ctx.unit_data);
     ctx.requested_targets = platform_targets.into_iter().sorted_unstable().collect();
 
@@ -11,6 +11,29 @@ use crate::util::log_message::Target;
 use crate::util::logger::RunId;
 use crate::workspace::Workspace;
 
+/// Output format of `cargo report` commands.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ReportMessageFormat {
+    Human,
+    Json,
+}
+
+impl ReportMessageFormat {
+    pub const POSSIBLE_VALUES: [&str; 2] = ["human", "json"];
+}
+
+impl std::str::FromStr for ReportMessageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<ReportMessageFormat, anyhow::Error> {
+        match s {
+            "human" => Ok(ReportMessageFormat::Human),
+            "json" => Ok(ReportMessageFormat::Json),
+            f => anyhow::bail!("unknown message format `{f}`"),
+        }
+    }
+}
+
 /// Lists log files by calling a callback for each valid log file.
 ///
 /// * Yield log files from new to old
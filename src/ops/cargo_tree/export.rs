@@ -0,0 +1,249 @@
+//! Exports the graph of `cargo tree` for other tools, see [`OutputFormat`].
+//!
+//! The same parts of the graph as the text output are exported: the edge
+//! kinds and targets are already filtered when building the graph, and
+//! `--prune` and `--depth` are applied while walking it from the roots.
+//! Shared dependencies are always exported once, as a graph doesn't need
+//! `--no-dedupe`.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+
+use anyhow::Context as _;
+use serde::Serialize;
+
+use super::format::Pattern;
+use super::graph::{Edge, EdgeKind, Graph, Node, NodeId};
+use super::{DisplayDepth, EDGE_KINDS, OutputFormat, TreeOptions, is_displayed};
+use crate::drop_print;
+use crate::util::CargoResult;
+use crate::util::data_structures::HashMap;
+use crate::util::interning::InternedString;
+use crate::workspace::dependency::DepKind;
+use crate::workspace::{PackageIdSpec, Workspace};
+
+/// The nodes and edges reachable from the roots.
+struct Export {
+    roots: Vec<usize>,
+    nodes: Vec<NodeId>,
+    edges: Vec<(usize, usize, Edge)>,
+}
+
+/// Prints the part of `graph` reachable from `roots` in `opts.output_format`.
+pub fn print(
+    ws: &Workspace<'_>,
+    opts: &TreeOptions,
+    roots: &[NodeId],
+    pkgs_to_prune: &[PackageIdSpec],
+    graph: &Graph<'_>,
+) -> CargoResult<()> {
+    let format = Pattern::new(&opts.format)
+        .with_context(|| format!("tree format `{}` not valid", opts.format))?;
+    let export = collect(ws, opts, roots, pkgs_to_prune, graph);
+    let label = |node: NodeId| format.display(graph, node).to_string();
+    match opts.output_format {
+        OutputFormat::Text => unreachable!("text is printed as a tree"),
+        OutputFormat::Dot => drop_print!(ws.gctx(), "{}", to_dot(&export, graph, label)),
+        OutputFormat::Mermaid => drop_print!(ws.gctx(), "{}", to_mermaid(&export, graph, label)),
+        OutputFormat::Json => ws
+            .gctx()
+            .shell()
+            .print_json(&to_json(&export, graph, label))?,
+    }
+    Ok(())
+}
+
+fn collect(
+    ws: &Workspace<'_>,
+    opts: &TreeOptions,
+    roots: &[NodeId],
+    pkgs_to_prune: &[PackageIdSpec],
+    graph: &Graph<'_>,
+) -> Export {
+    let (max_display_depth, filter_non_workspace_member) = match opts.display_depth {
+        DisplayDepth::MaxDisplayDepth(max) => (max, false),
+        DisplayDepth::Workspace => (u32::MAX, true),
+    };
+    let mut export = Export {
+        roots: Vec::new(),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut indexes = HashMap::default();
+    let mut queue = VecDeque::new();
+    for &root in roots {
+        if !indexes.contains_key(&root) {
+            indexes.insert(root, export.nodes.len());
+            export.roots.push(export.nodes.len());
+            export.nodes.push(root);
+            queue.push_back((root, 0));
+        }
+    }
+    // Walking breadth-first gives each node its smallest depth.
+    while let Some((node, depth)) = queue.pop_front() {
+        if depth >= max_display_depth {
+            continue;
+        }
+        let from = indexes[&node];
+        for kind in EDGE_KINDS {
+            for dep in graph.edges_of_kind(node, kind) {
+                if !is_displayed(
+                    ws,
+                    graph,
+                    dep.node(),
+                    pkgs_to_prune,
+                    filter_non_workspace_member,
+                ) {
+                    continue;
+                }
+                let to = *indexes.entry(dep.node()).or_insert_with(|| {
+                    export.nodes.push(dep.node());
+                    queue.push_back((dep.node(), depth + 1));
+                    export.nodes.len() - 1
+                });
+                export.edges.push((from, to, dep));
+            }
+        }
+    }
+    export
+}
+
+fn edge_kind_name(kind: EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Dep(DepKind::Normal) => "normal",
+        EdgeKind::Dep(DepKind::Build) => "build",
+        EdgeKind::Dep(DepKind::Development) => "dev",
+        EdgeKind::Feature => "feature",
+    }
+}
+
+fn is_feature(graph: &Graph<'_>, node: NodeId) -> bool {
+    matches!(graph.node(node), Node::Feature { .. })
+}
+
+fn to_dot(export: &Export, graph: &Graph<'_>, label: impl Fn(NodeId) -> String) -> String {
+    let escape = |s: String| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut out = String::from("digraph {\n");
+    for (i, &node) in export.nodes.iter().enumerate() {
+        let shape = if is_feature(graph, node) {
+            ", shape=box"
+        } else {
+            ""
+        };
+        let _ = writeln!(out, "    n{i} [label=\"{}\"{shape}]", escape(label(node)));
+    }
+    for (from, to, edge) in &export.edges {
+        let attrs = match edge.kind() {
+            EdgeKind::Dep(DepKind::Normal) => "",
+            EdgeKind::Dep(DepKind::Build) => " [label=\"build\"]",
+            EdgeKind::Dep(DepKind::Development) => " [label=\"dev\"]",
+            EdgeKind::Feature => " [style=dashed]",
+        };
+        let _ = writeln!(out, "    n{from} -> n{to}{attrs}");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn to_mermaid(export: &Export, graph: &Graph<'_>, label: impl Fn(NodeId) -> String) -> String {
+    let escape = |s: String| s.replace('"', "#quot;");
+    let mut out = String::from("flowchart TD\n");
+    for (i, &node) in export.nodes.iter().enumerate() {
+        let label = escape(label(node));
+        if is_feature(graph, node) {
+            let _ = writeln!(out, "    n{i}([\"{label}\"])");
+        } else {
+            let _ = writeln!(out, "    n{i}[\"{label}\"]");
+        }
+    }
+    for (from, to, edge) in &export.edges {
+        let arrow = match edge.kind() {
+            EdgeKind::Dep(DepKind::Normal) => "-->",
+            EdgeKind::Dep(DepKind::Build) => "-- build -->",
+            EdgeKind::Dep(DepKind::Development) => "-- dev -->",
+            EdgeKind::Feature => "-.->",
+        };
+        let _ = writeln!(out, "    n{from} {arrow} n{to}");
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct JsonGraph {
+    /// Indexes of the root nodes, which come first.
+    roots: Vec<usize>,
+    nodes: Vec<JsonNode>,
+    edges: Vec<JsonEdge>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum JsonNode {
+    Package {
+        id: usize,
+        package_id: String,
+        features: Vec<InternedString>,
+        label: String,
+    },
+    Feature {
+        id: usize,
+        /// The package the feature belongs to.
+        package_id: String,
+        name: InternedString,
+        label: String,
+    },
+}
+
+#[derive(Serialize)]
+struct JsonEdge {
+    from: usize,
+    to: usize,
+    kind: &'static str,
+    public: bool,
+}
+
+fn to_json(export: &Export, graph: &Graph<'_>, label: impl Fn(NodeId) -> String) -> JsonGraph {
+    let nodes = export
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(id, &node)| match graph.node(node) {
+            Node::Package {
+                package_id,
+                features,
+                ..
+            } => JsonNode::Package {
+                id,
+                package_id: package_id.to_spec().to_string(),
+                features: features.clone(),
+                label: label(node),
+            },
+            Node::Feature { node_index, name } => {
+                let Node::Package { package_id, .. } = graph.node(*node_index) else {
+                    unreachable!("features belong to packages");
+                };
+                JsonNode::Feature {
+                    id,
+                    package_id: package_id.to_spec().to_string(),
+                    name: *name,
+                    label: label(node),
+                }
+            }
+        })
+        .collect();
+    let edges = export
+        .edges
+        .iter()
+        .map(|(from, to, edge)| JsonEdge {
+            from: *from,
+            to: *to,
+            kind: edge_kind_name(edge.kind()),
+            public: edge.public(),
+        })
+        .collect();
+    JsonGraph {
+        roots: export.roots.clone(),
+        nodes,
+        edges,
+    }
+}
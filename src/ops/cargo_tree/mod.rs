@@ -4,7 +4,7 @@ use self::format::Pattern;
 use crate::compiler::{CompileKind, RustcTargetData};
 use crate::ops::resolve::SpecsAndResolvedFeatures;
 use crate::ops::{self, Packages};
-use crate::resolver::{ForceAllTargets, HasDevUnits, features::CliFeatures};
+use crate::resolver::{AdvisoryDb, ForceAllTargets, HasDevUnits, features::CliFeatures};
 use crate::util::CargoResult;
 use crate::util::data_structures::{HashMap, HashSet};
 use crate::util::style;
@@ -15,6 +15,7 @@ use anyhow::Context as _;
 use graph::Graph;
 use std::str::FromStr;
 
+mod export;
 mod format;
 mod graph;
 
@@ -53,6 +54,8 @@ pub struct TreeOptions {
     pub no_proc_macro: bool,
     /// Include only public dependencies.
     pub public: bool,
+    /// How the dependency graph is printed.
+    pub output_format: OutputFormat,
 }
 
 #[derive(PartialEq)]
@@ -92,6 +95,33 @@ impl FromStr for Prefix {
     }
 }
 
+/// The output format of `cargo tree`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// An indented tree.
+    Text,
+    /// A Graphviz DOT graph.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+    /// A JSON list of nodes and edges.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<OutputFormat, &'static str> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "dot" => Ok(OutputFormat::Dot),
+            "mermaid" => Ok(OutputFormat::Mermaid),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err("invalid output format"),
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum DisplayDepth {
     MaxDisplayDepth(u32),
@@ -118,6 +148,14 @@ impl FromStr for DisplayDepth {
     }
 }
 
+/// The kinds of edges, in the order they are displayed.
+const EDGE_KINDS: &[EdgeKind] = &[
+    EdgeKind::Dep(DepKind::Normal),
+    EdgeKind::Dep(DepKind::Build),
+    EdgeKind::Dep(DepKind::Development),
+    EdgeKind::Feature,
+];
+
 struct Symbols {
     down: &'static str,
     tee: &'static str,
@@ -175,6 +213,8 @@ pub fn build_and_print(ws: &Workspace<'_>, opts: &TreeOptions) -> CargoResult<()
         dry_run,
     )?;
 
+    let advisories = AdvisoryDb::for_report(ws.gctx())?;
+
     let package_map: HashMap<PackageId, &Package> = ws_resolve
         .pkg_set
         .packages()
@@ -239,8 +279,10 @@ pub fn build_and_print(ws: &Workspace<'_>, opts: &TreeOptions) -> CargoResult<()
             To find dependencies that require specific target platforms, \
             try to use option `--target all` first, and then narrow your search scope accordingly.",
             )?;
+        } else if opts.output_format == OutputFormat::Text {
+            print(ws, opts, root_indexes, &pkgs_to_prune, advisories, &graph)?;
         } else {
-            print(ws, opts, root_indexes, &pkgs_to_prune, &graph)?;
+            export::print(ws, opts, &root_indexes, &pkgs_to_prune, &graph)?;
         }
     }
     Ok(())
@@ -252,6 +294,7 @@ fn print(
     opts: &TreeOptions,
     roots: Vec<NodeId>,
     pkgs_to_prune: &[PackageIdSpec],
+    advisories: Option<&AdvisoryDb>,
     graph: &Graph<'_>,
 ) -> CargoResult<()> {
     let format = Pattern::new(&opts.format)
@@ -286,6 +329,7 @@ fn print(
             &format,
             symbols,
             pkgs_to_prune,
+            advisories,
             opts.prefix,
             opts.no_dedupe,
             opts.display_depth,
@@ -306,6 +350,7 @@ fn print_node<'a>(
     format: &Pattern,
     symbols: &Symbols,
     pkgs_to_prune: &[PackageIdSpec],
+    advisories: Option<&AdvisoryDb>,
     prefix: Prefix,
     no_dedupe: bool,
     display_depth: DisplayDepth,
@@ -350,19 +395,32 @@ fn print_node<'a>(
     } else {
         color_print::cstr!(" <yellow,dim>(*)</>")
     };
-    drop_println!(ws.gctx(), "{}{}", format.display(graph, node_index), star);
+    let advisory = match (advisories, graph.node(node_index)) {
+        (Some(db), Node::Package { package_id, .. }) => {
+            let ids: Vec<_> = db.affecting(*package_id).map(|a| a.id.as_str()).collect();
+            if ids.is_empty() {
+                String::new()
+            } else {
+                let style = style::ERROR;
+                format!(" {style}({}){style:#}", ids.join(", "))
+            }
+        }
+        _ => String::new(),
+    };
+    drop_println!(
+        ws.gctx(),
+        "{}{}{}",
+        format.display(graph, node_index),
+        advisory,
+        star
+    );
 
     if !new || in_cycle {
         return Ok(());
     }
     print_stack.push(node_index);
 
-    for kind in &[
-        EdgeKind::Dep(DepKind::Normal),
-        EdgeKind::Dep(DepKind::Build),
-        EdgeKind::Dep(DepKind::Development),
-        EdgeKind::Feature,
-    ] {
+    for kind in EDGE_KINDS {
         print_dependencies(
             ws,
             graph,
@@ -370,6 +428,7 @@ fn print_node<'a>(
             format,
             symbols,
             pkgs_to_prune,
+            advisories,
             prefix,
             no_dedupe,
             display_depth,
@@ -392,6 +451,7 @@ fn print_dependencies<'a>(
     format: &Pattern,
     symbols: &Symbols,
     pkgs_to_prune: &[PackageIdSpec],
+    advisories: Option<&AdvisoryDb>,
     prefix: Prefix,
     no_dedupe: bool,
     display_depth: DisplayDepth,
@@ -440,16 +500,13 @@ fn print_dependencies<'a>(
     let mut it = deps
         .iter()
         .filter(|dep| {
-            // Filter out packages to prune.
-            match graph.node(dep.node()) {
-                Node::Package { package_id, .. } => {
-                    if filter_non_workspace_member && !ws.is_member_id(*package_id) {
-                        return false;
-                    }
-                    !pkgs_to_prune.iter().any(|spec| spec.matches(*package_id))
-                }
-                Node::Feature { .. } => true,
-            }
+            is_displayed(
+                ws,
+                graph,
+                dep.node(),
+                pkgs_to_prune,
+                filter_non_workspace_member,
+            )
         })
         .peekable();
 
@@ -463,6 +520,7 @@ fn print_dependencies<'a>(
             format,
             symbols,
             pkgs_to_prune,
+            advisories,
             prefix,
             no_dedupe,
             display_depth,
@@ -476,6 +534,25 @@ fn print_dependencies<'a>(
     Ok(())
 }
 
+/// Whether a dependency is kept by `--prune` and `--depth workspace`.
+fn is_displayed(
+    ws: &Workspace<'_>,
+    graph: &Graph<'_>,
+    node_index: NodeId,
+    pkgs_to_prune: &[PackageIdSpec],
+    filter_non_workspace_member: bool,
+) -> bool {
+    match graph.node(node_index) {
+        Node::Package { package_id, .. } => {
+            if filter_non_workspace_member && !ws.is_member_id(*package_id) {
+                return false;
+            }
+            !pkgs_to_prune.iter().any(|spec| spec.matches(*package_id))
+        }
+        Node::Feature { .. } => true,
+    }
+}
+
 fn edge_line_color(kind: EdgeKind) -> anstyle::Style {
     match kind {
         EdgeKind::Dep(DepKind::Normal) => style::DEP_NORMAL,
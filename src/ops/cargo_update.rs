@@ -1,11 +1,14 @@
 use crate::context::GlobalContext;
+use crate::drop_println;
 use crate::ops;
 use crate::resolver::PublishAgePolicy;
 use crate::resolver::Resolve;
+use crate::resolver::VersionHolds;
 use crate::resolver::features::{CliFeatures, HasDevUnits};
 use crate::sources::IndexSummary;
 use crate::sources::source::QueryKind;
 use crate::util::cache_lock::CacheLockMode;
+use crate::util::interning::InternedString;
 use crate::util::{CargoResult, VersionExt};
 use crate::util::{OptVersionReq, style};
 use crate::workspace::Registry as _;
@@ -20,16 +23,36 @@ use crate::workspace::{SourceId, Workspace};
 use crate::util::data_structures::{HashMap, HashSet};
 use crate::util::data_structures::{IndexMap, IndexSet};
 use anyhow::Context as _;
+use cargo_util::paths;
 use cargo_util_schemas::core::PartialVersion;
+use cargo_util_schemas::manifest::TomlManifest;
 use cargo_util_terminal::Verbosity;
 use itertools::Itertools;
 use semver::{Op, Version, VersionReq};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::path::Path;
 use tracing::{debug, trace};
 
 pub type UpgradeMap = HashMap<(String, SourceId), Version>;
 
+/// The requirements changed by [`upgrade_manifests`], grouped by package, for
+/// `cargo update --breaking --report`.
+#[derive(Default)]
+pub struct UpgradeReport {
+    requirements: BTreeMap<(String, SourceId), Vec<UpgradedRequirement>>,
+}
+
+/// A requirement of a workspace member upgraded to a breaking version.
+struct UpgradedRequirement {
+    member: InternedString,
+    from: String,
+    to: String,
+    /// Whether the requirement comes from `[workspace.dependencies]`.
+    inherited: bool,
+}
+
 pub struct UpdateOptions<'a> {
     pub gctx: &'a GlobalContext,
     pub to_update: Vec<String>,
@@ -37,6 +60,9 @@ pub struct UpdateOptions<'a> {
     pub recursive: bool,
     pub dry_run: bool,
     pub workspace: bool,
+    /// Writes the changes to this file as an [`UpdatePlan`], instead of
+    /// updating the lockfile.
+    pub plan: Option<&'a Path>,
 }
 
 pub fn generate_lockfile(ws: &Workspace<'_>) -> CargoResult<()> {
@@ -57,6 +83,31 @@ pub fn generate_lockfile(ws: &Workspace<'_>) -> CargoResult<()> {
     Ok(())
 }
 
+/// Resolves a lockfile with git merge conflicts, keeping the versions locked
+/// by either side of the conflicts where possible.
+pub fn merge_lockfile(ws: &Workspace<'_>) -> CargoResult<()> {
+    let Some((ours, merged)) = ops::load_conflicted_lockfile(ws)? else {
+        anyhow::bail!(
+            "cannot merge a lockfile that does not exist\n\
+             help: run `cargo generate-lockfile` to create one"
+        )
+    };
+    let mut registry = ws.package_registry()?;
+    let mut resolve = ops::resolve_with_previous(
+        &mut registry,
+        ws,
+        &CliFeatures::new_all(true),
+        HasDevUnits::Yes,
+        Some(&merged),
+        None,
+        &[],
+        true,
+    )?;
+    ops::write_pkg_lockfile(ws, &mut resolve)?;
+    print_lockfile_changes(ws, Some(&ours), &resolve, &mut registry)?;
+    Ok(())
+}
+
 pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoResult<()> {
     if opts.recursive && opts.precise.is_some() {
         anyhow::bail!("cannot specify both recursive and precise simultaneously")
@@ -74,6 +125,12 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
 
     let previous_resolve = match ops::load_pkg_lockfile(ws)? {
         Some(resolve) => resolve,
+        None if opts.plan.is_some() => {
+            anyhow::bail!(
+                "cannot plan an update without a lockfile\n\
+                 help: run `cargo generate-lockfile` to create one"
+            )
+        }
         None => {
             match opts.precise {
                 None => return generate_lockfile(ws),
@@ -195,7 +252,13 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
         opts.precise.is_some(),
         &mut registry,
     )?;
-    if opts.dry_run {
+    if let Some(plan) = opts.plan {
+        write_update_plan(ws, &previous_resolve, &resolve, plan)?;
+        opts.gctx.shell().note(format!(
+            "not updating lockfile, wrote the changes to `{}`",
+            plan.display()
+        ))?;
+    } else if opts.dry_run {
         opts.gctx
             .shell()
             .warn("not updating lockfile due to dry run")?;
@@ -205,6 +268,471 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
     Ok(())
 }
 
+/// A reviewable set of lockfile changes, written by `cargo update --plan`
+/// and applied by `cargo update --apply`.
+#[derive(Serialize, Deserialize)]
+struct UpdatePlan {
+    /// The version of the format.
+    version: u32,
+    changes: Vec<PlannedChange>,
+}
+
+/// A package added, removed, upgraded, or downgraded in the lockfile.
+#[derive(Serialize, Deserialize)]
+struct PlannedChange {
+    name: InternedString,
+    /// The source of the package, without the precise revision.
+    source: String,
+    kind: PlannedChangeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<Version>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<Version>,
+    /// The git commit the package changes to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_rev: Option<String>,
+    /// Whether `to` is SemVer compatible with `from`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    semver_compatible: Option<bool>,
+    /// When `to` was published to its registry, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    published: Option<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum PlannedChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+}
+
+impl PlannedChange {
+    fn matches(&self, package_id: PackageId) -> bool {
+        self.name == package_id.name() && self.source == package_id.source_id().as_url().to_string()
+    }
+}
+
+const UPDATE_PLAN_VERSION: u32 = 1;
+
+fn write_update_plan(
+    ws: &Workspace<'_>,
+    previous_resolve: &Resolve,
+    resolve: &Resolve,
+    path: &Path,
+) -> CargoResult<()> {
+    let changes = PackageChange::diff(ws, previous_resolve, resolve)
+        .into_values()
+        .filter_map(|change| {
+            let kind = match change.kind {
+                PackageChangeKind::Added => PlannedChangeKind::Added,
+                PackageChangeKind::Removed => PlannedChangeKind::Removed,
+                PackageChangeKind::Upgraded => PlannedChangeKind::Upgraded,
+                PackageChangeKind::Downgraded => PlannedChangeKind::Downgraded,
+                PackageChangeKind::Unchanged => return None,
+            };
+            let package_id = change.package_id;
+            let source_id = package_id.source_id();
+            let (from, to) = match kind {
+                PlannedChangeKind::Removed => (Some(package_id), None),
+                _ => (change.previous_id, Some(package_id)),
+            };
+            let semver_compatible = from.zip(to).map(|(from, to)| {
+                let (lower, higher) = if from.version() < to.version() {
+                    (from, to)
+                } else {
+                    (to, from)
+                };
+                lower.version().to_caret_req().matches(higher.version())
+            });
+            let published = to
+                .and_then(|to| resolve.summary(to).pubtime())
+                .map(|time| time.to_string());
+            Some(PlannedChange {
+                name: package_id.name(),
+                source: source_id.as_url().to_string(),
+                kind,
+                from: from.map(|id| id.version().clone()),
+                to: to.map(|id| id.version().clone()),
+                to_rev: to
+                    .and_then(|id| id.source_id().precise_git_fragment())
+                    .map(str::to_string),
+                semver_compatible,
+                published,
+            })
+        })
+        .collect();
+    let plan = UpdatePlan {
+        version: UPDATE_PLAN_VERSION,
+        changes,
+    };
+    let mut contents = serde_json::to_string_pretty(&plan)?;
+    contents.push('\n');
+    paths::write(path, contents)
+}
+
+/// Applies the upgrades and downgrades of the [`UpdatePlan`] at `path` to the
+/// lockfile, or only those matching `to_apply` if not empty.
+///
+/// Each change is applied like `cargo update <name>@<from> --precise <to>`,
+/// keeping every other package locked. Changes that aren't applied, and
+/// packages added or removed as a consequence, are left out. It is an error
+/// for the result to upgrade or downgrade packages not in the plan, so the
+/// lockfile is only changed in the reviewed way.
+pub fn apply_update_plan(
+    ws: &Workspace<'_>,
+    path: &Path,
+    to_apply: &[String],
+    dry_run: bool,
+) -> CargoResult<()> {
+    let _lock = ws
+        .gctx()
+        .acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+
+    let plan: UpdatePlan = serde_json::from_str(&paths::read(path)?)
+        .with_context(|| format!("failed to parse update plan `{}`", path.display()))?;
+    if plan.version != UPDATE_PLAN_VERSION {
+        anyhow::bail!(
+            "unsupported update plan version {} in `{}`, expected {UPDATE_PLAN_VERSION}",
+            plan.version,
+            path.display()
+        );
+    }
+    let Some(original_resolve) = ops::load_pkg_lockfile(ws)? else {
+        anyhow::bail!("cannot apply an update plan without a lockfile");
+    };
+
+    let specs = to_apply
+        .iter()
+        .map(|spec| PackageIdSpec::parse(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+    let spec_matches = |spec: &PackageIdSpec, change: &PlannedChange| {
+        spec.name() == change.name.as_str()
+            && spec
+                .partial_version()
+                .zip(change.to.as_ref())
+                .is_none_or(|(version, to)| version.matches(to))
+    };
+    for spec in &specs {
+        if !plan.changes.iter().any(|change| spec_matches(spec, change)) {
+            anyhow::bail!(
+                "package ID specification `{spec}` did not match any change in `{}`",
+                path.display()
+            );
+        }
+    }
+    let accepted: Vec<_> = plan
+        .changes
+        .iter()
+        .filter(|change| {
+            matches!(
+                change.kind,
+                PlannedChangeKind::Upgraded | PlannedChangeKind::Downgraded
+            )
+        })
+        .filter(|change| specs.is_empty() || specs.iter().any(|spec| spec_matches(spec, change)))
+        .collect();
+
+    // The registry of the last resolve, which has its patches locked.
+    let mut last: Option<(Resolve, PackageRegistry<'_>)> = None;
+    for change in &accepted {
+        let previous_resolve = last
+            .as_ref()
+            .map_or(&original_resolve, |(resolve, _)| resolve);
+        let (Some(from), Some(to)) = (&change.from, &change.to) else {
+            anyhow::bail!(
+                "invalid change of `{}` in `{}`, expected `from` and `to` versions",
+                change.name,
+                path.display()
+            );
+        };
+        let Some(current) = previous_resolve
+            .iter()
+            .filter(|id| change.matches(*id))
+            .find(|id| id.version() == from || id.version() == to)
+        else {
+            anyhow::bail!(
+                "`{} v{from}` is not in the lockfile\n\
+                 help: the lockfile changed since `{}` was written",
+                change.name,
+                path.display()
+            );
+        };
+        let source_id = current.source_id();
+        let updated = if source_id.is_registry() {
+            if current.version() == to {
+                continue;
+            }
+            source_id.with_precise_registry_version(
+                current.name(),
+                from.clone(),
+                &to.to_string(),
+            )?
+        } else if source_id.is_git() {
+            let Some(rev) = &change.to_rev else {
+                anyhow::bail!(
+                    "invalid change of `{}` in `{}`, expected a `to_rev`",
+                    change.name,
+                    path.display()
+                );
+            };
+            if source_id.precise_git_fragment() == Some(rev.as_str()) {
+                continue;
+            }
+            source_id.with_git_precise(Some(rev.clone()))
+        } else {
+            continue;
+        };
+
+        let mut registry = ws.package_registry()?;
+        registry.add_sources([updated])?;
+        // As in `update_lockfile`, all packages from a non-registry source
+        // are updated in step.
+        let keep = |p: &PackageId| {
+            *p != current && (source_id.is_registry() || p.source_id() != source_id)
+        };
+        let resolve = ops::resolve_with_previous(
+            &mut registry,
+            ws,
+            &CliFeatures::new_all(true),
+            HasDevUnits::Yes,
+            Some(previous_resolve),
+            Some(&keep),
+            &[],
+            true,
+        )?;
+        last = Some((resolve, registry));
+    }
+    let Some((mut resolve, mut registry)) = last else {
+        ws.gctx().shell().status(
+            "Skipping",
+            "no changes of the update plan need to be applied",
+        )?;
+        return Ok(());
+    };
+
+    let unplanned: Vec<_> = PackageChange::diff(ws, &original_resolve, &resolve)
+        .into_values()
+        .filter(|change| {
+            matches!(
+                change.kind,
+                PackageChangeKind::Upgraded | PackageChangeKind::Downgraded
+            )
+        })
+        .filter(|change| {
+            !accepted.iter().any(|planned| {
+                planned.matches(change.package_id)
+                    && planned.to.as_ref() == Some(change.package_id.version())
+            })
+        })
+        .map(|change| format!("  {change}"))
+        .collect();
+    if !unplanned.is_empty() {
+        anyhow::bail!(
+            "applying `{}` would also change packages not in the plan:\n{}\n\
+             help: plan the update again with `cargo update --plan`",
+            path.display(),
+            unplanned.join("\n")
+        );
+    }
+
+    print_lockfile_updates(ws, &original_resolve, &resolve, true, &mut registry)?;
+    if dry_run {
+        ws.gctx()
+            .shell()
+            .warn("not updating lockfile due to dry run")?;
+    } else {
+        ops::write_pkg_lockfile(ws, &mut resolve)?;
+    }
+    Ok(())
+}
+
+/// Unifies duplicate versions of registry packages in the lockfile, without
+/// changing any version requirement.
+///
+/// For each package locked at several versions, the highest of them allowed
+/// by every requirement on the package is selected, and the others are
+/// updated to it like `cargo update <name>@<from> --precise <to>`. The
+/// requirements preventing the remaining duplicates from being unified are
+/// reported.
+pub fn dedupe_lockfile(ws: &Workspace<'_>, dry_run: bool) -> CargoResult<()> {
+    let _lock = ws
+        .gctx()
+        .acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+
+    let Some(original_resolve) = ops::load_pkg_lockfile(ws)? else {
+        anyhow::bail!(
+            "cannot dedupe without a lockfile\n\
+             help: run `cargo generate-lockfile` to create one"
+        )
+    };
+
+    // Resolve once with everything locked, as the requirements between
+    // packages aren't recorded in the lockfile.
+    let mut registry = ws.package_registry()?;
+    let mut resolve = ops::resolve_with_previous(
+        &mut registry,
+        ws,
+        &CliFeatures::new_all(true),
+        HasDevUnits::Yes,
+        Some(&original_resolve),
+        None,
+        &[],
+        true,
+    )?;
+
+    let mut visited = HashSet::default();
+    let mut failures = HashMap::default();
+    while let Some(duplicate) = Duplicate::find(&resolve)
+        .into_iter()
+        .find(|d| !visited.contains(&d.key()))
+    {
+        visited.insert(duplicate.key());
+        let Some(target) = duplicate.unified_version() else {
+            continue;
+        };
+        for &current in &duplicate.versions {
+            if current.version() == target {
+                continue;
+            }
+            let updated = current.source_id().with_precise_registry_version(
+                current.name(),
+                current.version().clone(),
+                &target.to_string(),
+            )?;
+            let mut next_registry = ws.package_registry()?;
+            next_registry.add_sources([updated])?;
+            let keep = |p: &PackageId| *p != current;
+            match ops::resolve_with_previous(
+                &mut next_registry,
+                ws,
+                &CliFeatures::new_all(true),
+                HasDevUnits::Yes,
+                Some(&resolve),
+                Some(&keep),
+                &[],
+                true,
+            ) {
+                Ok(next) => {
+                    resolve = next;
+                    registry = next_registry;
+                }
+                Err(e) => {
+                    debug!("failed to update {current} to {target}: {e:?}");
+                    failures.insert(duplicate.key(), e);
+                    break;
+                }
+            }
+        }
+    }
+
+    print_lockfile_updates(ws, &original_resolve, &resolve, true, &mut registry)?;
+
+    for duplicate in Duplicate::find(&resolve) {
+        let versions = duplicate
+            .versions
+            .iter()
+            .map(|id| format!("v{}", id.version()))
+            .join(", ");
+        let mut msg = format!("cannot unify `{}` {versions}", duplicate.name());
+        if let Some(e) = failures.get(&duplicate.key()) {
+            msg.push_str(&format!(": {e:#}"));
+        } else {
+            let highest = duplicate.versions.last().expect("duplicates").version();
+            msg.push_str(&format!(" into v{highest}, blocked by"));
+            for (parent, req) in &duplicate.requirements {
+                if !allows(req, highest) {
+                    msg.push_str(&format!("\n  {parent} requiring `{req}`"));
+                }
+            }
+        }
+        ws.gctx().shell().note(msg)?;
+    }
+
+    if dry_run {
+        ws.gctx()
+            .shell()
+            .warn("not updating lockfile due to dry run")?;
+    } else {
+        ops::write_pkg_lockfile(ws, &mut resolve)?;
+    }
+    Ok(())
+}
+
+/// A registry package locked at several versions, see [`dedupe_lockfile`].
+struct Duplicate {
+    /// The locked versions, from lowest to highest.
+    versions: Vec<PackageId>,
+    /// The packages depending on any of the versions, with their requirement.
+    requirements: Vec<(PackageId, OptVersionReq)>,
+}
+
+impl Duplicate {
+    /// Finds the duplicate packages of `resolve`, sorted by name.
+    fn find(resolve: &Resolve) -> Vec<Duplicate> {
+        let mut versions: BTreeMap<_, Vec<PackageId>> = BTreeMap::new();
+        for id in resolve.iter().filter(|id| id.source_id().is_registry()) {
+            versions
+                .entry((id.name(), id.source_id()))
+                .or_default()
+                .push(id);
+        }
+        versions
+            .into_values()
+            .filter(|versions| versions.len() > 1)
+            .map(|mut versions| {
+                versions.sort();
+                let mut requirements: Vec<_> = resolve
+                    .iter()
+                    .flat_map(|parent| {
+                        resolve
+                            .deps(parent)
+                            .filter(|(id, _)| versions.contains(id))
+                            .flat_map(move |(_, deps)| {
+                                deps.iter()
+                                    .map(move |dep| (parent, dep.version_req().clone()))
+                            })
+                    })
+                    .collect();
+                requirements.sort_by(|(a, _), (b, _)| a.cmp(b));
+                requirements.dedup();
+                Duplicate {
+                    versions,
+                    requirements,
+                }
+            })
+            .collect()
+    }
+
+    fn key(&self) -> (InternedString, SourceId) {
+        (self.name(), self.versions[0].source_id())
+    }
+
+    fn name(&self) -> InternedString {
+        self.versions[0].name()
+    }
+
+    /// The highest locked version allowed by all requirements, if any.
+    fn unified_version(&self) -> Option<&Version> {
+        self.versions
+            .iter()
+            .rev()
+            .map(|id| id.version())
+            .find(|v| self.requirements.iter().all(|(_, req)| allows(req, v)))
+    }
+}
+
+/// Whether the requirement, ignoring what it is locked to, allows `version`.
+fn allows(req: &OptVersionReq, version: &Version) -> bool {
+    match req {
+        OptVersionReq::Any => true,
+        OptVersionReq::Req(req)
+        | OptVersionReq::Locked(_, req)
+        | OptVersionReq::Precise(_, req) => req.matches(version),
+    }
+}
+
 /// Prints lockfile change statuses.
 ///
 /// This would acquire the package-cache lock, as it may update the index to
@@ -227,7 +755,7 @@ pub fn print_lockfile_changes(
 pub fn upgrade_manifests(
     ws: &mut Workspace<'_>,
     to_update: &Vec<String>,
-) -> CargoResult<UpgradeMap> {
+) -> CargoResult<(UpgradeMap, UpgradeReport)> {
     let gctx = ws.gctx();
     let mut upgrades = HashMap::default();
     let mut upgrade_messages = HashSet::default();
@@ -248,16 +776,20 @@ pub fn upgrade_manifests(
     registry.lock_patches();
 
     let mut remaining_specs: IndexSet<_> = to_update.iter().cloned().collect();
+    let mut report = UpgradeReport::default();
 
     for member in ws.members_mut().sorted() {
         debug!("upgrading manifest for `{}`", member.name());
 
+        let member_name = member.name();
+        let inherited = inherited_dependencies(member.manifest().original_toml());
         *member.manifest_mut().summary_mut() = member
             .manifest()
             .summary()
             .clone()
             .try_map_dependencies(|d| {
-                upgrade_dependency(
+                let from = d.version_req().to_string();
+                let dep = upgrade_dependency(
                     &gctx,
                     &to_update,
                     &mut registry,
@@ -265,7 +797,27 @@ pub fn upgrade_manifests(
                     &mut upgrade_messages,
                     &mut remaining_specs,
                     d,
-                )
+                )?;
+                let latest = upgrades.get(&(dep.package_name().to_string(), dep.source_id()));
+                if let Some(latest) = latest
+                    && dep.version_req().to_string() != from
+                {
+                    let to = match upgrade_requirement(&from, latest)? {
+                        Some((to, _)) => to,
+                        None => dep.version_req().to_string(),
+                    };
+                    report
+                        .requirements
+                        .entry((dep.package_name().to_string(), dep.source_id()))
+                        .or_default()
+                        .push(UpgradedRequirement {
+                            member: member_name,
+                            from,
+                            to,
+                            inherited: inherited.contains(dep.name_in_toml().as_str()),
+                        });
+                }
+                Ok(dep)
             })?;
     }
 
@@ -315,7 +867,89 @@ pub fn upgrade_manifests(
         anyhow::bail!("{error_msg}");
     }
 
-    Ok(upgrades)
+    Ok((upgrades, report))
+}
+
+/// The names of the dependencies a manifest inherits from `[workspace.dependencies]`.
+fn inherited_dependencies(toml: Option<&TomlManifest>) -> HashSet<&str> {
+    let Some(toml) = toml else {
+        return HashSet::default();
+    };
+    let platforms = toml.target.iter().flat_map(|targets| targets.values());
+    [
+        toml.dependencies.as_ref(),
+        toml.dev_dependencies(),
+        toml.build_dependencies(),
+    ]
+    .into_iter()
+    .chain(platforms.flat_map(|platform| {
+        [
+            platform.dependencies.as_ref(),
+            platform.dev_dependencies(),
+            platform.build_dependencies(),
+        ]
+    }))
+    .flatten()
+    .flatten()
+    .filter(|(_, dep)| dep.is_inherited())
+    .map(|(name, _)| name.as_str())
+    .collect()
+}
+
+/// Prints the upgrades of [`upgrade_manifests`] grouped by package, with the
+/// members whose requirements changed, and the versions locked before and
+/// after the upgrade to show whether it unifies duplicate versions.
+pub fn print_upgrade_report(
+    ws: &Workspace<'_>,
+    report: &UpgradeReport,
+    previous_resolve: Option<&Resolve>,
+    resolve: &Resolve,
+) -> CargoResult<()> {
+    let locked = |resolve: &Resolve, name: &str, source_id: SourceId| {
+        resolve
+            .iter()
+            .filter(|id| id.name() == name && id.source_id() == source_id)
+            .map(|id| id.version().clone())
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+    let versions = |versions: &[Version]| versions.iter().map(|v| format!("v{v}")).join(", ");
+
+    for ((name, source_id), requirements) in &report.requirements {
+        let before = previous_resolve
+            .map(|r| locked(r, name, *source_id))
+            .unwrap_or_default();
+        let after = locked(resolve, name, *source_id);
+
+        let mut line = name.clone();
+        if !source_id.is_crates_io() {
+            line.push_str(&format!(" ({source_id})"));
+        }
+        if !before.is_empty() {
+            line.push_str(&format!(" {}", versions(&before)));
+        }
+        line.push_str(&format!(" -> {}", versions(&after)));
+        if before.len() > 1 && after.len() == 1 {
+            line.push_str(&format!(" (unifies {} versions)", before.len()));
+        }
+        drop_println!(ws.gctx(), "{line}");
+
+        for requirement in requirements {
+            let UpgradedRequirement {
+                member,
+                from,
+                to,
+                inherited,
+            } = requirement;
+            let inherited = if *inherited {
+                " (from `workspace.dependencies`)"
+            } else {
+                ""
+            };
+            drop_println!(ws.gctx(), "  {member}: {from} -> {to}{inherited}");
+        }
+    }
+    Ok(())
 }
 
 fn upgrade_dependency(
@@ -574,6 +1208,7 @@ fn print_lockfile_generation(
     }
     annotate_required_rust_version(ws, resolve, &mut changes);
     let publish_age = publish_age_policy_for_report(ws);
+    let holds = VersionHolds::new(ws.gctx())?;
 
     status_locking(ws, num_pkgs)?;
     for change in changes.values() {
@@ -582,11 +1217,7 @@ fn print_lockfile_generation(
         };
         match change.kind {
             PackageChangeKind::Added => {
-                let possibilities = if let Some(query) = change.alternatives_query() {
-                    crate::util::block_on(registry.query_vec(&query, QueryKind::Exact))?
-                } else {
-                    vec![]
-                };
+                let possibilities = query_alternatives(registry, change, holds.as_ref())?;
 
                 let required_rust_version = report_required_rust_version(resolve, change);
                 let too_new = report_too_new(resolve, change, publish_age.as_ref());
@@ -630,6 +1261,7 @@ fn print_lockfile_sync(
     }
     annotate_required_rust_version(ws, resolve, &mut changes);
     let publish_age = publish_age_policy_for_report(ws);
+    let holds = VersionHolds::new(ws.gctx())?;
 
     status_locking(ws, num_pkgs)?;
     for change in changes.values() {
@@ -640,11 +1272,7 @@ fn print_lockfile_sync(
             PackageChangeKind::Added
             | PackageChangeKind::Upgraded
             | PackageChangeKind::Downgraded => {
-                let possibilities = if let Some(query) = change.alternatives_query() {
-                    crate::util::block_on(registry.query_vec(&query, QueryKind::Exact))?
-                } else {
-                    vec![]
-                };
+                let possibilities = query_alternatives(registry, change, holds.as_ref())?;
 
                 let required_rust_version = report_required_rust_version(resolve, change);
                 let too_new = report_too_new(resolve, change, publish_age.as_ref());
@@ -681,17 +1309,23 @@ fn print_lockfile_updates(
         .count();
     annotate_required_rust_version(ws, resolve, &mut changes);
     let publish_age = publish_age_policy_for_report(ws);
+    let holds = VersionHolds::new(ws.gctx())?;
 
     if !precise {
         status_locking(ws, num_pkgs)?;
     }
     let mut unchanged_behind = 0;
+    let mut held_back = Vec::new();
     for change in changes.values() {
-        let possibilities = if let Some(query) = change.alternatives_query() {
-            crate::util::block_on(registry.query_vec(&query, QueryKind::Exact))?
-        } else {
-            vec![]
-        };
+        let mut possibilities = query_alternatives(registry, change, None)?;
+        if let Some(holds) = &holds {
+            if !matches!(change.kind, PackageChangeKind::Removed)
+                && let Some(held) = report_held(&possibilities, change, holds)
+            {
+                held_back.push(held);
+            }
+            retain_held(&mut possibilities, holds);
+        }
 
         match change.kind {
             PackageChangeKind::Added
@@ -754,6 +1388,12 @@ fn print_lockfile_updates(
             ))?;
         }
     }
+    for (package_id, hold, version) in held_back {
+        ws.gctx().shell().note(format!(
+            "`resolver.hold` keeps `{}` at `{hold}`, v{version} is available",
+            package_id.name(),
+        ))?;
+    }
 
     Ok(())
 }
@@ -839,6 +1479,55 @@ fn report_too_new(
     Some(format!(" {warn}({note}){warn:#}"))
 }
 
+/// Queries the other versions of a changed registry package, to report
+/// whether it is behind.
+///
+/// Versions outside of a `resolver.hold` are left out, as they are not
+/// available to updates.
+fn query_alternatives(
+    registry: &mut PackageRegistry<'_>,
+    change: &PackageChange,
+    holds: Option<&VersionHolds>,
+) -> CargoResult<Vec<IndexSummary>> {
+    let Some(query) = change.alternatives_query() else {
+        return Ok(vec![]);
+    };
+    let mut possibilities = crate::util::block_on(registry.query_vec(&query, QueryKind::Exact))?;
+    if let Some(holds) = holds {
+        retain_held(&mut possibilities, holds);
+    }
+    Ok(possibilities)
+}
+
+fn retain_held(possibilities: &mut Vec<IndexSummary>, holds: &VersionHolds) {
+    possibilities.retain(|s| holds.held(s.package_id()).is_none());
+}
+
+/// Returns the newest compatible version a `resolver.hold` keeps the package
+/// from updating to, along with the hold.
+fn report_held<'h>(
+    possibilities: &[IndexSummary],
+    change: &PackageChange,
+    holds: &'h VersionHolds,
+) -> Option<(PackageId, &'h VersionReq, Version)> {
+    let package_id = change.package_id;
+    if !package_id.source_id().is_registry() {
+        return None;
+    }
+    let hold = holds.get(package_id.name())?;
+    let version_req = package_id.version().to_caret_req();
+    let version = possibilities
+        .iter()
+        .filter_map(|s| match s {
+            IndexSummary::Candidate(s) => Some(s),
+            _ => None,
+        })
+        .map(|s| s.version())
+        .filter(|v| package_id.version() < *v && version_req.matches(v) && !hold.matches(v))
+        .max()?;
+    Some((package_id, hold, version.clone()))
+}
+
 fn report_latest(
     possibilities: &[IndexSummary],
     change: &PackageChange,
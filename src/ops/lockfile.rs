@@ -1,6 +1,6 @@
 use std::io::prelude::*;
 
-use crate::resolver::encode::into_resolve;
+use crate::resolver::encode::{into_resolve, merge_lockfiles};
 use crate::resolver::{Resolve, ResolveVersion};
 use crate::util::Filesystem;
 use crate::util::errors::CargoResult;
@@ -32,6 +32,88 @@ pub fn load_pkg_lockfile(ws: &Workspace<'_>) -> CargoResult<Option<Resolve>> {
     Ok(resolve)
 }
 
+/// Loads a lockfile with git merge conflicts.
+///
+/// Returns our side of the conflicts, along with a resolve locking the
+/// packages of both sides, see [`merge_lockfiles`]. Both are the same when
+/// there are no conflicts.
+#[tracing::instrument(skip_all)]
+pub fn load_conflicted_lockfile(ws: &Workspace<'_>) -> CargoResult<Option<(Resolve, Resolve)>> {
+    let lock_root = ws.lock_root();
+    if !lock_root.as_path_unlocked().join(LOCKFILE_NAME).exists() {
+        return Ok(None);
+    }
+
+    let mut f = lock_root.open_ro_shared(LOCKFILE_NAME, ws.gctx(), "Cargo.lock file")?;
+
+    let mut s = String::new();
+    f.read_to_string(&mut s)
+        .with_context(|| format!("failed to read file: {}", f.path().display()))?;
+
+    let resolves = (|| -> CargoResult<(Resolve, Resolve)> {
+        let (ours, theirs) = split_merge_conflicts(&s)?.unwrap_or_else(|| (s.clone(), s.clone()));
+        let ours_lockfile: TomlLockfile = toml::from_str(&ours)?;
+        let theirs_lockfile: TomlLockfile = toml::from_str(&theirs)?;
+        let merged = merge_lockfiles(ours_lockfile, theirs_lockfile);
+        let merged = into_resolve(merged, &ours, ws)?;
+        let ours = into_resolve(toml::from_str(&ours)?, &ours, ws)?;
+        Ok((ours, merged))
+    })()
+    .with_context(|| format!("failed to parse lock file at: {}", f.path().display()))?;
+    Ok(Some(resolves))
+}
+
+/// Splits a file with git merge conflict markers into our side and their
+/// side of the conflicts.
+///
+/// The common ancestor of `diff3` style conflicts is left out. Returns `None`
+/// when there are no conflicts.
+fn split_merge_conflicts(contents: &str) -> CargoResult<Option<(String, String)>> {
+    #[derive(PartialEq)]
+    enum Section {
+        Both,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut ours = String::new();
+    let mut theirs = String::new();
+    let mut section = Section::Both;
+    let mut conflicts = false;
+    for line in contents.split_inclusive('\n') {
+        let marker = |m: &str| {
+            line.strip_prefix(m)
+                .is_some_and(|rest| rest.trim_end().is_empty() || rest.starts_with(' '))
+        };
+        section = match section {
+            Section::Both if marker("<<<<<<<") => {
+                conflicts = true;
+                Section::Ours
+            }
+            Section::Ours if marker("|||||||") => Section::Base,
+            Section::Ours | Section::Base if marker("=======") => Section::Theirs,
+            Section::Theirs if marker(">>>>>>>") => Section::Both,
+            section => {
+                match section {
+                    Section::Both => {
+                        ours.push_str(line);
+                        theirs.push_str(line);
+                    }
+                    Section::Ours => ours.push_str(line),
+                    Section::Base => {}
+                    Section::Theirs => theirs.push_str(line),
+                }
+                section
+            }
+        };
+    }
+    if section != Section::Both {
+        anyhow::bail!("unterminated merge conflict");
+    }
+    Ok(conflicts.then_some((ours, theirs)))
+}
+
 /// Generate a toml String of Cargo.lock from a Resolve.
 pub fn resolve_to_string(ws: &Workspace<'_>, resolve: &Resolve) -> CargoResult<String> {
     let (_orig, out, _lock_root) = resolve_to_string_orig(ws, resolve);
@@ -24,20 +24,28 @@ pub use self::cargo_report::rebuilds::ReportRebuildsOptions;
 pub use self::cargo_report::rebuilds::report_rebuilds;
 pub use self::cargo_report::sessions::ReportSessionsOptions;
 pub use self::cargo_report::sessions::report_sessions;
+pub use self::cargo_report::timings::ReportTimingsFormat;
 pub use self::cargo_report::timings::ReportTimingsOptions;
 pub use self::cargo_report::timings::report_timings;
+pub use self::cargo_report::util::ReportMessageFormat;
 pub use self::cargo_run::run;
 pub use self::cargo_test::{TestOptions, run_benches, run_tests};
 pub use self::cargo_uninstall::uninstall;
 pub use self::cargo_update::UpdateOptions;
+pub use self::cargo_update::apply_update_plan;
+pub use self::cargo_update::dedupe_lockfile;
 pub use self::cargo_update::generate_lockfile;
+pub use self::cargo_update::merge_lockfile;
 pub use self::cargo_update::print_lockfile_changes;
+pub use self::cargo_update::print_upgrade_report;
 pub use self::cargo_update::update_lockfile;
 pub use self::cargo_update::upgrade_manifests;
 pub use self::cargo_update::write_manifest_upgrades;
 pub use self::cargo_vendor::{VendorOptions, vendor};
 pub use self::common_for_install_and_uninstall::{InstallTracker, resolve_root};
-pub use self::lockfile::{load_pkg_lockfile, resolve_to_string, write_pkg_lockfile};
+pub use self::lockfile::{
+    load_conflicted_lockfile, load_pkg_lockfile, resolve_to_string, write_pkg_lockfile,
+};
 pub use self::registry::OwnersOptions;
 pub use self::registry::PublishOpts;
 pub use self::registry::RegistryCredentialConfig;
@@ -55,6 +63,7 @@ pub use self::resolve::{
 };
 
 pub mod cargo_add;
+pub mod cargo_bump;
 mod cargo_clean;
 pub(crate) mod cargo_compile;
 pub mod cargo_config;
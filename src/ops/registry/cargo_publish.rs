@@ -8,6 +8,8 @@ use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::Seek;
 use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -20,6 +22,8 @@ use crates_io::NewCrate;
 use crates_io::NewCrateDependency;
 use crates_io::Registry;
 use itertools::Itertools;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::CargoResult;
 use crate::GlobalContext;
@@ -28,14 +32,22 @@ use crate::ops;
 use crate::ops::PackageOpts;
 use crate::ops::Packages;
 use crate::ops::RegistryOrIndex;
+use crate::ops::cargo_package::LocalDependencies;
+use crate::ops::cargo_package::TmpRegistry;
 use crate::ops::registry::RegistryClient;
 use crate::ops::registry::RegistrySourceIds;
+use crate::resolver;
 use crate::resolver::CliFeatures;
+use crate::resolver::ResolveOpts;
+use crate::resolver::ResolveVersion;
+use crate::resolver::VersionPreferences;
+use crate::resolver::features::RequestedFeatures;
 use crate::sources::CRATES_IO_REGISTRY;
 use crate::sources::RegistrySource;
 use crate::sources::SourceConfigMap;
 use crate::sources::source::QueryKind;
 use crate::sources::source::Source;
+use crate::util::FileLock;
 use crate::util::Graph;
 use crate::util::Progress;
 use crate::util::ProgressStyle;
@@ -48,10 +60,12 @@ use crate::workspace::Package;
 use crate::workspace::PackageId;
 use crate::workspace::PackageIdSpecQuery;
 use crate::workspace::SourceId;
+use crate::workspace::Summary;
 use crate::workspace::Workspace;
 use crate::workspace::dependency::DepKind;
 use crate::workspace::manifest::ManifestMetadata;
 use crate::workspace::parser::prepare_for_publish;
+use crate::workspace::registry::PackageRegistry;
 
 use super::super::check_dep_has_version;
 
@@ -67,6 +81,12 @@ pub struct PublishOpts<'gctx> {
     pub targets: Vec<String>,
     pub dry_run: bool,
     pub cli_features: CliFeatures,
+    /// Skips the packages already published, or uploaded by a previous
+    /// `cargo publish` recorded in its [`PublishState`].
+    pub resume: bool,
+    /// Resolves the packages from a local stand-in for the registry before
+    /// uploading them, see [`verify_index`].
+    pub verify_index: bool,
 }
 
 pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
@@ -140,13 +160,38 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
         Some(Operation::Read).filter(|_| !opts.dry_run),
     )?;
 
+    // Progress is only recorded with `-Zunstable-options`, for `--resume`.
+    let track_progress = opts.gctx.cli_unstable().unstable_options && !opts.dry_run;
+    let state_path = PublishState::path(ws);
+    let mut state = PublishState::new(source_ids.original);
+    if opts.resume {
+        if let Some(previous) = PublishState::load(&state_path)? {
+            if previous.registry == state.registry {
+                state = previous;
+            } else {
+                opts.gctx.shell().warn(format!(
+                    "ignoring the progress of publishing to {}, now publishing to {}",
+                    previous.registry, state.registry
+                ))?;
+            }
+        }
+    }
+
+    let mut pkgs = pkgs;
     {
         let _lock = opts
             .gctx
             .acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
 
+        let mut published = Vec::new();
         for (pkg, _) in &pkgs {
-            verify_unpublished(pkg, &mut source, &source_ids, opts.dry_run, opts.gctx)?;
+            if opts.resume && is_published(pkg, &mut source, &source_ids)? {
+                published.push(pkg.package_id());
+                continue;
+            }
+            if state.uploaded(pkg.package_id()).is_none() {
+                verify_unpublished(pkg, &mut source, &source_ids, opts.dry_run, opts.gctx)?;
+            }
             verify_dependencies(pkg, &registry, source_ids.original).map_err(|err| {
                 ManifestError::new(
                     err.context(format!(
@@ -157,6 +202,31 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
                 )
             })?;
         }
+
+        if !published.is_empty() {
+            for pkg_id in &published {
+                state.record_confirmed(*pkg_id);
+                opts.gctx.shell().status(
+                    "Skipping",
+                    format!(
+                        "{} v{}, already published to {}",
+                        pkg_id.name(),
+                        pkg_id.version(),
+                        source_ids.original
+                    ),
+                )?;
+            }
+            pkgs.retain(|(pkg, _)| !published.contains(&pkg.package_id()));
+            if pkgs.is_empty() {
+                if track_progress {
+                    state.remove(&state_path)?;
+                }
+                opts.gctx
+                    .shell()
+                    .note("all packages have already been published")?;
+                return Ok(());
+            }
+        }
     }
 
     let pkg_dep_graph = ops::cargo_package::package_with_dep_graph(
@@ -202,6 +272,72 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
         );
     }
 
+    if opts.verify_index {
+        verify_index(ws, &pkg_dep_graph, &source_ids)?;
+    }
+
+    // Packages uploaded by a previous `cargo publish` only need to be confirmed.
+    for (pkg, _) in pkg_dep_graph.packages.values() {
+        if state.uploaded(pkg.package_id()) == Some(false) {
+            plan.take(pkg.package_id());
+            to_confirm.insert(pkg.package_id());
+        }
+    }
+
+    let result = publish_plan(
+        ws,
+        opts,
+        &pkg_dep_graph,
+        plan,
+        to_confirm,
+        &mut registry,
+        &source,
+        &source_ids,
+        &mut state,
+        track_progress.then_some(state_path.as_path()),
+    );
+
+    if track_progress {
+        match &result {
+            Ok(()) => state.remove(&state_path)?,
+            Err(_) => {
+                state.save(&state_path)?;
+                let remaining: Vec<_> = pkg_dep_graph
+                    .packages
+                    .keys()
+                    .filter(|id| state.uploaded(**id).is_none())
+                    .copied()
+                    .collect();
+                opts.gctx.shell().print_report(
+                    &[Level::NOTE
+                        .secondary_title(state.summary(&remaining))
+                        .element(Level::HELP.message(
+                            "to publish the remaining packages, \
+                             run `cargo publish --resume -Zunstable-options`",
+                        ))],
+                    false,
+                )?;
+            }
+        }
+    }
+    result
+}
+
+/// Uploads the packages in `plan` once their dependencies are confirmed to be available
+///
+/// Progress is recorded in `state`, and saved to `state_path` when given.
+fn publish_plan(
+    ws: &Workspace<'_>,
+    opts: &PublishOpts<'_>,
+    pkg_dep_graph: &LocalDependencies<(CliFeatures, FileLock)>,
+    mut plan: PublishPlan,
+    mut to_confirm: BTreeSet<PackageId>,
+    registry: &mut Registry<RegistryClient<'_>>,
+    source: &RegistrySource<'_>,
+    source_ids: &RegistrySourceIds,
+    state: &mut PublishState,
+    state_path: Option<&Path>,
+) -> CargoResult<()> {
     while !plan.is_empty() {
         // There might not be any ready package, if the previous confirmations
         // didn't unlock a new one. For example, if `c` depends on `a` and
@@ -264,12 +400,16 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
                 ws,
                 pkg,
                 tarball.file(),
-                &mut registry,
+                registry,
                 source_ids.original,
                 opts.dry_run,
                 workspace_context,
             )?;
             to_confirm.insert(pkg_id);
+            if let Some(state_path) = state_path {
+                state.record_uploaded(pkg_id);
+                state.save(state_path)?;
+            }
 
             if !opts.dry_run {
                 // Short does not include the registry name.
@@ -371,6 +511,10 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
         }
         for id in &confirmed {
             to_confirm.remove(id);
+            state.record_confirmed(*id);
+        }
+        if let Some(state_path) = state_path {
+            state.save(state_path)?;
         }
         plan.mark_confirmed(confirmed);
     }
@@ -447,20 +591,29 @@ fn poll_one_package(
     Ok(!summaries.is_empty())
 }
 
-fn verify_unpublished(
+/// Whether the version of `pkg` is already in the registry.
+fn is_published(
     pkg: &Package,
     source: &mut RegistrySource<'_>,
     source_ids: &RegistrySourceIds,
-    dry_run: bool,
-    gctx: &GlobalContext,
-) -> CargoResult<()> {
+) -> CargoResult<bool> {
     let query = Dependency::parse(
         pkg.name(),
         Some(&pkg.version().to_exact_req().to_string()),
         source_ids.replacement,
     )?;
     let duplicate_query = crate::util::block_on(source.query_vec(&query, QueryKind::Exact))?;
-    if !duplicate_query.is_empty() {
+    Ok(!duplicate_query.is_empty())
+}
+
+fn verify_unpublished(
+    pkg: &Package,
+    source: &mut RegistrySource<'_>,
+    source_ids: &RegistrySourceIds,
+    dry_run: bool,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    if is_published(pkg, source, source_ids)? {
         // Move the registry error earlier in the publish process.
         // Since dry-run wouldn't talk to the registry to get the error, we downgrade it to a
         // warning.
@@ -521,6 +674,97 @@ fn verify_dependencies(
     Ok(())
 }
 
+/// Publishes the packages into a throwaway local registry overlaid on the
+/// registry, then resolves each of them from its index entry the way a
+/// dependent would.
+///
+/// This catches a published set that isn't self-consistent, like a feature
+/// enabling one that a published dependency doesn't have, before anything is
+/// uploaded.
+fn verify_index(
+    ws: &Workspace<'_>,
+    pkg_dep_graph: &LocalDependencies<(CliFeatures, FileLock)>,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<()> {
+    let gctx = ws.gctx();
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+
+    let reg_dir = ws.build_dir().join("package").join("verify-registry");
+    let mut local_reg = TmpRegistry::new(gctx, reg_dir, source_ids.replacement)?;
+    for (pkg, (_features, tarball)) in pkg_dep_graph.packages.values() {
+        local_reg.add_package(ws, pkg, tarball)?;
+    }
+
+    let source_config = SourceConfigMap::new_with_overlays(
+        gctx,
+        [(source_ids.replacement, local_reg.source_id()?)],
+    )?;
+    let mut registry = PackageRegistry::new_with_source_config(gctx, source_config)?;
+    registry.lock_patches();
+
+    let root_source = SourceId::for_path(ws.root())?;
+    let version_prefs = VersionPreferences::default();
+    let mut pkg_ids: Vec<_> = pkg_dep_graph.packages.keys().copied().collect();
+    pkg_ids.sort();
+    for pkg_id in &pkg_ids {
+        let (pkg, (_features, tarball)) = &pkg_dep_graph.packages[pkg_id];
+        gctx.shell().status(
+            "Validating",
+            format!("{} v{} against the index", pkg.name(), pkg.version()),
+        )?;
+
+        // A dependent enabling every feature, so every entry of the
+        // published feature table gets resolved.
+        let mut dep = Dependency::parse(
+            pkg.name(),
+            Some(&format!("={}", pkg.version())),
+            source_ids.original,
+        )?;
+        dep.set_features(pkg.summary().features().keys().copied());
+        let root_id = PackageId::try_new("verify-index", "0.0.0", root_source)?;
+        let root = Summary::new(root_id, vec![dep], &BTreeMap::new(), None::<&str>, None)?;
+        let resolve_opts = ResolveOpts::new(
+            false,
+            RequestedFeatures::CliFeatures(CliFeatures::new_all(false)),
+        );
+        let resolve = resolver::resolve(
+            &[(root, resolve_opts)],
+            &[],
+            &registry,
+            &version_prefs,
+            ResolveVersion::with_rust_version(None),
+            Some(gctx),
+        )
+        .with_context(|| {
+            format!(
+                "failed to resolve {} v{} from the index of the packages to publish",
+                pkg.name(),
+                pkg.version()
+            )
+        })?;
+
+        // The tarball only needs to match the index checksum, so it is not unpacked.
+        let published_id = pkg_id.with_source_id(source_ids.original);
+        let checksum = resolve
+            .iter()
+            .find(|id| *id == published_id)
+            .and_then(|id| resolve.summary(id).checksum());
+        tarball.file().seek(SeekFrom::Start(0))?;
+        let actual = cargo_util::Sha256::new()
+            .update_file(tarball.file())?
+            .finish_hex();
+        if checksum != Some(actual.as_str()) {
+            bail!(
+                "checksum of {} v{} in the index does not match its `.crate` file",
+                pkg.name(),
+                pkg.version()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 pub(crate) fn prepare_transmit(
     gctx: &GlobalContext,
     ws: &Workspace<'_>,
@@ -719,6 +963,121 @@ fn transmit(
     Ok(())
 }
 
+/// Progress of publishing packages to a registry, saved in the target
+/// directory after each upload and confirmation so an interrupted
+/// `cargo publish` can be resumed with `--resume`.
+#[derive(Debug, Serialize, Deserialize)]
+struct PublishState {
+    /// The registry the packages are published to.
+    registry: String,
+    /// The packages uploaded to the registry.
+    uploaded: Vec<UploadedPackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UploadedPackage {
+    name: String,
+    version: semver::Version,
+    /// Whether the package was confirmed to be available in the index.
+    confirmed: bool,
+}
+
+impl PublishState {
+    fn new(registry: SourceId) -> Self {
+        Self {
+            registry: registry.to_string(),
+            uploaded: Vec::new(),
+        }
+    }
+
+    fn path(ws: &Workspace<'_>) -> PathBuf {
+        ws.target_dir()
+            .join("package")
+            .into_path_unlocked()
+            .join("publish-state.json")
+    }
+
+    fn load(path: &Path) -> CargoResult<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = paths::read(path)?;
+        let state = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse publish state `{}`", path.display()))?;
+        Ok(Some(state))
+    }
+
+    fn save(&self, path: &Path) -> CargoResult<()> {
+        if let Some(parent) = path.parent() {
+            paths::create_dir_all(parent)?;
+        }
+        paths::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    fn remove(&self, path: &Path) -> CargoResult<()> {
+        if path.exists() {
+            paths::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the package was confirmed to be available, if it was
+    /// uploaded.
+    fn uploaded(&self, pkg_id: PackageId) -> Option<bool> {
+        self.uploaded
+            .iter()
+            .find(|p| p.name == pkg_id.name().as_str() && &p.version == pkg_id.version())
+            .map(|p| p.confirmed)
+    }
+
+    fn record_uploaded(&mut self, pkg_id: PackageId) {
+        if self.uploaded(pkg_id).is_none() {
+            self.uploaded.push(UploadedPackage {
+                name: pkg_id.name().to_string(),
+                version: pkg_id.version().clone(),
+                confirmed: false,
+            });
+        }
+    }
+
+    fn record_confirmed(&mut self, pkg_id: PackageId) {
+        self.record_uploaded(pkg_id);
+        for p in &mut self.uploaded {
+            if p.name == pkg_id.name().as_str() && &p.version == pkg_id.version() {
+                p.confirmed = true;
+            }
+        }
+    }
+
+    /// Describes what was published and what `remaining` packages are left.
+    fn summary(&self, remaining: &[PackageId]) -> String {
+        let list = |confirmed: bool| {
+            self.uploaded
+                .iter()
+                .filter(|p| p.confirmed == confirmed)
+                .map(|p| format!("{} v{}", p.name, p.version))
+                .join(", ")
+        };
+        let mut summary = String::from("publishing was interrupted");
+        for (title, list) in [
+            ("published", list(true)),
+            ("uploaded, not yet available", list(false)),
+            (
+                "not yet uploaded",
+                remaining
+                    .iter()
+                    .map(|id| format!("{} v{}", id.name(), id.version()))
+                    .join(", "),
+            ),
+        ] {
+            if !list.is_empty() {
+                summary.push_str(&format!("\n  {title}: {list}"));
+            }
+        }
+        summary
+    }
+}
+
 /// State for tracking dependencies during upload.
 struct PublishPlan {
     /// Graph of publishable packages where the edges are `(dependency -> dependent)`
@@ -785,6 +1144,11 @@ impl PublishPlan {
         remaining.into_iter().collect()
     }
 
+    /// Removes a package already uploaded from the plan, leaving it to be confirmed.
+    fn take(&mut self, pkg_id: PackageId) {
+        self.dependencies_count.remove(&pkg_id);
+    }
+
     /// Returns the set of packages that are ready for publishing (i.e. have no outstanding dependencies).
     ///
     /// These will not be returned in future calls.
@@ -58,7 +58,9 @@
 use crate::compiler::{CompileKind, RustcTargetData};
 use crate::context::FeatureUnification;
 use crate::ops;
+use crate::resolver::AdvisoryDb;
 use crate::resolver::PublishAgePolicy;
+use crate::resolver::VersionHolds;
 use crate::resolver::features::{
     CliFeatures, FeatureOpts, FeatureResolver, ForceAllTargets, RequestedFeatures, ResolvedFeatures,
 };
@@ -454,7 +456,7 @@ pub fn resolve_with_previous<'gctx>(
     // While registering patches, we will record preferences for particular versions
     // of various packages.
     let mut version_prefs = VersionPreferences::default();
-    if ws.gctx().cli_unstable().minimal_versions {
+    if ws.resolve_minimal_versions() {
         version_prefs.version_ordering(VersionOrdering::MinimumVersionsFirst)
     }
     if ws.resolve_honors_rust_version() {
@@ -477,6 +479,12 @@ pub fn resolve_with_previous<'gctx>(
             version_prefs.publish_age(policy);
         }
     }
+    if let Some((db, deny)) = AdvisoryDb::load(ws.gctx())? {
+        version_prefs.advisories(db, deny);
+    }
+    if let Some(holds) = VersionHolds::new(ws.gctx())? {
+        version_prefs.holds(holds);
+    }
 
     let avoid_patch_ids = if register_patches {
         register_patch_entries(registry, ws, previous, &mut version_prefs, keep_previous)?
@@ -0,0 +1,191 @@
+//! Support for avoiding versions affected by security advisories.
+//!
+//! With `-Zadvisories`, `resolver.advisory-db` points to a local checkout of
+//! an advisory database in the [RustSec format], such as
+//! <https://github.com/rustsec/advisory-db>. Cargo never fetches it, keeping
+//! it up to date is left to the user.
+//!
+//! Each advisory lists the versions of a crates.io package that are
+//! `patched` or `unaffected`, all other versions being vulnerable. Depending
+//! on `resolver.incompatible-advisories`, the resolver either prefers
+//! versions not affected by any advisory (`fallback`, the default), or
+//! rejects affected versions like yanked ones (`deny`).
+//!
+//! [RustSec format]: https://github.com/rustsec/advisory-db#advisory-format
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use cargo_util::paths;
+use semver::VersionReq;
+use serde::Deserialize;
+
+use crate::context::{CargoResolverConfig, IncompatibleAdvisories};
+use crate::util::GlobalContext;
+use crate::util::data_structures::HashMap;
+use crate::util::errors::CargoResult;
+use crate::util::interning::InternedString;
+use crate::workspace::PackageId;
+
+/// The advisories of a local advisory database, by package name.
+#[derive(Debug, Default)]
+pub struct AdvisoryDb {
+    advisories: HashMap<InternedString, Vec<Advisory>>,
+}
+
+/// An advisory against the versions of a package.
+#[derive(Debug)]
+pub struct Advisory {
+    /// The identifier of the advisory, like `RUSTSEC-2019-0001`.
+    pub id: String,
+    patched: Vec<VersionReq>,
+    unaffected: Vec<VersionReq>,
+}
+
+/// The TOML front matter of an advisory.
+#[derive(Deserialize)]
+struct AdvisoryFile {
+    advisory: AdvisoryMetadata,
+    #[serde(default)]
+    versions: AdvisoryVersions,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryMetadata {
+    id: String,
+    package: String,
+    /// Informational advisories, like unmaintained crates, don't make
+    /// versions vulnerable.
+    informational: Option<String>,
+    /// When the advisory was withdrawn, if it was.
+    withdrawn: Option<toml::Value>,
+}
+
+#[derive(Default, Deserialize)]
+struct AdvisoryVersions {
+    #[serde(default)]
+    patched: Vec<VersionReq>,
+    #[serde(default)]
+    unaffected: Vec<VersionReq>,
+}
+
+impl Advisory {
+    fn affects(&self, version: &semver::Version) -> bool {
+        !self
+            .patched
+            .iter()
+            .chain(&self.unaffected)
+            .any(|req| req.matches(version))
+    }
+}
+
+impl AdvisoryDb {
+    /// Loads the database configured with `resolver.advisory-db`, along with
+    /// whether affected versions are denied rather than deprioritized.
+    ///
+    /// Returns `None` without `-Zadvisories`, when no database is configured,
+    /// or when `resolver.incompatible-advisories` is `allow`.
+    pub fn load(gctx: &GlobalContext) -> CargoResult<Option<(Arc<AdvisoryDb>, bool)>> {
+        let config = gctx.get::<Option<CargoResolverConfig>>("resolver")?;
+        let deny = match config
+            .and_then(|c| c.incompatible_advisories)
+            .unwrap_or(IncompatibleAdvisories::Fallback)
+        {
+            IncompatibleAdvisories::Allow => return Ok(None),
+            IncompatibleAdvisories::Fallback => false,
+            IncompatibleAdvisories::Deny => true,
+        };
+        Ok(gctx.advisory_db()?.map(|db| (Arc::clone(db), deny)))
+    }
+
+    /// Like [`AdvisoryDb::load`] but ignore `resolver.incompatible-advisories`,
+    /// to report affected packages regardless of whether they are allowed.
+    pub fn for_report(gctx: &GlobalContext) -> CargoResult<Option<&AdvisoryDb>> {
+        Ok(gctx.advisory_db()?.map(|db| &**db))
+    }
+
+    /// Reads the database configured with `resolver.advisory-db`.
+    ///
+    /// This parses the whole database, prefer [`GlobalContext::advisory_db`]
+    /// which caches it.
+    pub(crate) fn read(gctx: &GlobalContext) -> CargoResult<Option<AdvisoryDb>> {
+        if !gctx.cli_unstable().advisories {
+            return Ok(None);
+        }
+        let config = gctx.get::<Option<CargoResolverConfig>>("resolver")?;
+        let Some(path) = config.and_then(|c| c.advisory_db) else {
+            return Ok(None);
+        };
+        let path = path.resolve_path(gctx);
+        let db = AdvisoryDb::from_dir(&path).with_context(|| {
+            format!(
+                "failed to load the advisory database at `{}`",
+                path.display()
+            )
+        })?;
+        Ok(Some(db))
+    }
+
+    /// Reads the advisories of a database checked out at `path`.
+    ///
+    /// Advisories of crates.io packages are under `crates/<name>/`, as
+    /// Markdown files starting with a TOML block, or as TOML files.
+    pub fn from_dir(path: &Path) -> CargoResult<AdvisoryDb> {
+        let crates = path.join("crates");
+        if !crates.is_dir() {
+            anyhow::bail!("expected a `crates` directory in the advisory database");
+        }
+        let mut db = AdvisoryDb::default();
+        for entry in walkdir::WalkDir::new(&crates).sort_by_file_name() {
+            let entry = entry?;
+            let path = entry.path();
+            let toml = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("md") => {
+                    let contents = paths::read(path)?;
+                    let Some(toml) = front_matter(&contents) else {
+                        continue;
+                    };
+                    toml.to_string()
+                }
+                Some("toml") => paths::read(path)?,
+                _ => continue,
+            };
+            let file: AdvisoryFile = toml::from_str(&toml)
+                .with_context(|| format!("failed to parse advisory `{}`", path.display()))?;
+            if file.advisory.informational.is_some() || file.advisory.withdrawn.is_some() {
+                continue;
+            }
+            db.advisories
+                .entry(InternedString::new(&file.advisory.package))
+                .or_default()
+                .push(Advisory {
+                    id: file.advisory.id,
+                    patched: file.versions.patched,
+                    unaffected: file.versions.unaffected,
+                });
+        }
+        Ok(db)
+    }
+
+    /// Returns the advisories affecting a package.
+    ///
+    /// The database only covers crates.io packages.
+    pub fn affecting(&self, pkg_id: PackageId) -> impl Iterator<Item = &Advisory> {
+        pkg_id
+            .source_id()
+            .is_crates_io()
+            .then(|| self.advisories.get(&pkg_id.name()))
+            .flatten()
+            .into_iter()
+            .flatten()
+            .filter(move |advisory| advisory.affects(pkg_id.version()))
+    }
+}
+
+/// Extracts the TOML block at the start of a Markdown advisory.
+fn front_matter(contents: &str) -> Option<&str> {
+    let rest = contents.trim_start().strip_prefix("```toml")?;
+    let end = rest.find("\n```")?;
+    Some(&rest[..end])
+}
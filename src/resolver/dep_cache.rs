@@ -78,7 +78,14 @@ impl<'a, T: Registry> RegistryQueryerAsync<'a, T> {
                     // Unlike yanked, `cargo update --precise` does not opt in here
                     // unless `resolver.incompatible-publish-age = "allow"` is set.
                     let too_new = self.version_prefs.too_new(&summary).is_some();
-                    if !too_new || self.version_prefs.should_prefer(&summary.package_id()) {
+                    // Likewise for versions affected by an advisory, when denied.
+                    let denied = self.version_prefs.denies_advisories()
+                        && self.version_prefs.advisory(&summary).is_some();
+                    // Likewise for versions outside of a `resolver.hold`.
+                    let held = self.version_prefs.held(&summary).is_some();
+                    if !(too_new || denied || held)
+                        || self.version_prefs.should_prefer(&summary.package_id())
+                    {
                         summaries.push(summary);
                     }
                 }
@@ -112,7 +112,7 @@
 //!   format.
 
 use super::{Resolve, ResolveVersion};
-use crate::util::data_structures::{HashMap, HashSet};
+use crate::util::data_structures::{HashMap, HashSet, IndexMap};
 use crate::util::errors::CargoResult;
 use crate::util::interning::InternedString;
 use crate::util::{Graph, internal};
@@ -427,6 +427,170 @@ pub fn into_resolve(
     }
 }
 
+/// Merges both sides of a `Cargo.lock` with merge conflicts, for example
+/// after merging two branches which both changed dependencies.
+///
+/// The result locks the union of the packages of both sides, and is meant to
+/// be decoded with [`into_resolve`] as the previous resolve of a new
+/// resolution. When each side has its own version of a package that could
+/// have been unified, like a dependency upgraded on only one branch, only
+/// the highest of those versions is kept.
+pub fn merge_lockfiles(ours: TomlLockfile, theirs: TomlLockfile) -> TomlLockfile {
+    let mut packages: IndexMap<TomlLockfilePackageId, TomlLockfileDependency> = IndexMap::default();
+    // Which of ours and theirs locked each package.
+    let mut sides: HashMap<TomlLockfilePackageId, (bool, bool)> = HashMap::default();
+    for (pkgs, is_ours) in [
+        (qualified_packages(ours.package, ours.root), true),
+        (qualified_packages(theirs.package, theirs.root), false),
+    ] {
+        for pkg in pkgs {
+            let id = lockfile_package_id(&pkg);
+            let side = sides.entry(id.clone()).or_default();
+            if is_ours {
+                side.0 = true;
+            } else {
+                side.1 = true;
+            }
+            match packages.get_mut(&id) {
+                Some(existing) => {
+                    // Keep the edges of both sides.
+                    if let Some(deps) = pkg.dependencies {
+                        let existing = existing.dependencies.get_or_insert_default();
+                        for dep in deps {
+                            if !existing.contains(&dep) {
+                                existing.push(dep);
+                            }
+                        }
+                    }
+                }
+                None => {
+                    packages.insert(id, pkg);
+                }
+            }
+        }
+    }
+
+    // Group the versions only one side locked by the versions they could be
+    // unified to, to replace those of a group diverging between both sides
+    // with the highest one.
+    let mut groups: IndexMap<_, Vec<(semver::Version, TomlLockfilePackageId)>> =
+        IndexMap::default();
+    for (id, side) in sides.iter() {
+        if side.0 && side.1 {
+            continue;
+        }
+        let Some(version) = id.version.as_ref().and_then(|v| v.parse().ok()) else {
+            continue;
+        };
+        let key = (
+            id.name.clone(),
+            id.source.clone(),
+            compatible_range(&version),
+        );
+        groups.entry(key).or_default().push((version, id.clone()));
+    }
+    let mut replaced = HashMap::default();
+    for (_, mut versions) in groups {
+        let diverged = versions.iter().any(|(_, id)| sides[id].0)
+            && versions.iter().any(|(_, id)| sides[id].1);
+        if !diverged {
+            continue;
+        }
+        versions.sort_by(|a, b| a.0.cmp(&b.0));
+        let (_, highest) = versions.pop().unwrap();
+        for (_, id) in versions {
+            debug!("merging lockfiles: replacing {id} with {highest}");
+            packages.shift_remove(&id);
+            replaced.insert(id, highest.clone());
+        }
+    }
+
+    let replace = |id: TomlLockfilePackageId| replaced.get(&id).cloned().unwrap_or(id);
+    let package = packages
+        .into_values()
+        .map(|mut pkg| {
+            if let Some(deps) = pkg.dependencies.take() {
+                let mut merged = Vec::with_capacity(deps.len());
+                for dep in deps.into_iter().map(replace) {
+                    if !merged.contains(&dep) {
+                        merged.push(dep);
+                    }
+                }
+                pkg.dependencies = Some(merged);
+            }
+            pkg.replace = pkg.replace.take().map(replace);
+            pkg
+        })
+        .collect();
+
+    let mut metadata = theirs.metadata.unwrap_or_default();
+    metadata.extend(ours.metadata.unwrap_or_default());
+
+    let mut unused = ours.patch.unused;
+    for pkg in theirs.patch.unused {
+        if !unused.contains(&pkg) {
+            unused.push(pkg);
+        }
+    }
+
+    TomlLockfile {
+        version: ours.version.max(theirs.version),
+        package: Some(package),
+        root: None,
+        metadata: (!metadata.is_empty()).then_some(metadata),
+        patch: TomlLockfilePatch { unused },
+    }
+}
+
+/// Lists the packages of one side of a lockfile, with their edges spelled
+/// out fully so they stay unambiguous once merged with the other side.
+fn qualified_packages(
+    packages: Option<Vec<TomlLockfileDependency>>,
+    root: Option<TomlLockfileDependency>,
+) -> Vec<TomlLockfileDependency> {
+    let mut packages = packages.unwrap_or_default();
+    if let Some(root) = root {
+        packages.insert(0, root);
+    }
+    let ids: Vec<_> = packages.iter().map(lockfile_package_id).collect();
+    let qualify = |dep: TomlLockfilePackageId| {
+        let mut candidates = ids.iter().filter(|id| {
+            id.name == dep.name
+                && (dep.version.is_none() || id.version == dep.version)
+                && (dep.source.is_none() || id.source == dep.source)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(id), None) => id.clone(),
+            _ => dep,
+        }
+    };
+    for pkg in packages.iter_mut() {
+        if let Some(deps) = pkg.dependencies.take() {
+            pkg.dependencies = Some(deps.into_iter().map(qualify).collect());
+        }
+        pkg.replace = pkg.replace.take().map(qualify);
+    }
+    packages
+}
+
+fn lockfile_package_id(pkg: &TomlLockfileDependency) -> TomlLockfilePackageId {
+    TomlLockfilePackageId {
+        name: pkg.name.clone(),
+        version: Some(pkg.version.clone()),
+        source: pkg.source.clone(),
+    }
+}
+
+/// The versions SemVer considers compatible with `version`, identified by
+/// their leftmost non-zero component.
+fn compatible_range(version: &semver::Version) -> (u64, u64, u64) {
+    match (version.major, version.minor) {
+        (0, 0) => (0, 0, version.patch),
+        (0, minor) => (0, minor, 0),
+        (major, _) => (major, 0, 0),
+    }
+}
+
 fn build_path_deps(
     ws: &Workspace<'_>,
 ) -> CargoResult<HashMap<String, HashMap<semver::Version, SourceId>>> {
@@ -7,9 +7,12 @@ use crate::sources::path::RecursivePathSource;
 use crate::sources::source::QueryKind;
 use crate::util::edit_distance::{closest, edit_distance};
 use crate::util::errors::CargoResult;
+use crate::util::interning::InternedString;
 use crate::util::{GlobalContext, OptVersionReq, VersionExt};
-use crate::workspace::{Dependency, PackageId, Registry, Summary};
+use crate::workspace::{Dependency, PackageId, PackageIdSpec, Registry, Summary};
 use anyhow::Error;
+use semver::Version;
+use serde::Serialize;
 
 use super::VersionPreferences;
 use super::context::ResolverContext;
@@ -19,6 +22,7 @@ use super::types::{ConflictMap, ConflictReason};
 pub struct ResolveError {
     cause: Error,
     package_path: Vec<PackageId>,
+    conflict: Option<Box<ResolveConflict>>,
 }
 
 impl ResolveError {
@@ -26,14 +30,127 @@ impl ResolveError {
         Self {
             cause: cause.into(),
             package_path,
+            conflict: None,
         }
     }
 
+    fn with_conflict(mut self, conflict: ResolveConflict) -> Self {
+        self.conflict = Some(Box::new(conflict));
+        self
+    }
+
     /// Returns a path of packages from the package whose requirements could not be resolved up to
     /// the root.
     pub fn package_path(&self) -> &[PackageId] {
         &self.package_path
     }
+
+    /// Returns the structured explanation of why no version could be
+    /// selected, if the error is about a dependency.
+    pub fn conflict(&self) -> Option<&ResolveConflict> {
+        self.conflict.as_deref()
+    }
+}
+
+/// Why no version of a dependency could be selected, as emitted with
+/// `--message-format=json`.
+#[derive(Debug, Serialize)]
+pub struct ResolveConflict {
+    /// The name of the package depended on.
+    package_name: InternedString,
+    /// The version requirement of the dependency.
+    requirement: String,
+    /// The version the dependency is locked to, if any.
+    locked_version: Option<Version>,
+    /// The source searched for the package.
+    source: String,
+    /// The package with the dependency, followed by the packages depending
+    /// on it up to a workspace member.
+    required_by: Vec<RequiredBy>,
+    /// The versions considered, and why each was rejected.
+    candidates: Vec<RejectedCandidate>,
+    /// The previously selected packages the candidates conflict with.
+    conflicts: Vec<ConflictingActivation>,
+}
+
+/// A package in a chain of dependencies.
+#[derive(Debug, Serialize)]
+struct RequiredBy {
+    package_id: PackageIdSpec,
+    /// The requirement on the previous package of the chain.
+    requirement: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RejectedCandidate {
+    version: Version,
+    #[serde(flatten)]
+    reason: Rejection,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum Rejection {
+    /// Conflicts with the previously selected packages in `conflicts`.
+    Conflict,
+    /// Doesn't match the version requirement.
+    Mismatch,
+    Yanked,
+    TooNew {
+        published: String,
+        min_publish_age: String,
+    },
+    Advisory {
+        id: String,
+    },
+    Held {
+        hold: String,
+    },
+    RustVersion {
+        rust_version: String,
+    },
+    IndexVersion {
+        index_version: u32,
+    },
+    NotCached,
+    Invalid,
+    Unavailable,
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictingActivation {
+    package_id: PackageIdSpec,
+    #[serde(flatten)]
+    reason: ConflictKind,
+    required_by: Vec<RequiredBy>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum ConflictKind {
+    Semver,
+    Links { links: InternedString },
+    MissingFeature { feature: InternedString },
+    RequiredDependencyAsFeature { feature: InternedString },
+    NonImplicitDependencyAsFeature { feature: InternedString },
+}
+
+impl From<&ConflictReason> for ConflictKind {
+    fn from(reason: &ConflictReason) -> Self {
+        match reason {
+            ConflictReason::Semver => ConflictKind::Semver,
+            ConflictReason::Links(links) => ConflictKind::Links { links: *links },
+            ConflictReason::MissingFeature(feature) => {
+                ConflictKind::MissingFeature { feature: *feature }
+            }
+            ConflictReason::RequiredDependencyAsFeature(feature) => {
+                ConflictKind::RequiredDependencyAsFeature { feature: *feature }
+            }
+            ConflictReason::NonImplicitDependencyAsFeature(feature) => {
+                ConflictKind::NonImplicitDependencyAsFeature { feature: *feature }
+            }
+        }
+    }
 }
 
 impl std::error::Error for ResolveError {
@@ -97,6 +214,16 @@ pub(super) fn activation_error(
         )
     };
 
+    let mut conflict = ResolveConflict {
+        package_name: dep.package_name(),
+        requirement: dep.version_req().to_string(),
+        locked_version: dep.version_req().locked_version().cloned(),
+        source: dep.source_id().as_url().to_string(),
+        required_by: required_by_in_context(resolver_ctx, &parent.package_id()),
+        candidates: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
     if !candidates.is_empty() {
         let mut msg = format!("failed to select a version for `{}`.", dep.package_name());
         msg.push_str("\n    ... required by ");
@@ -134,6 +261,22 @@ pub(super) fn activation_error(
         // Flag used for grouping all semver errors together.
         let mut has_semver = false;
 
+        conflict.candidates = candidates
+            .iter()
+            .map(|c| RejectedCandidate {
+                version: c.version().clone(),
+                reason: Rejection::Conflict,
+            })
+            .collect();
+        conflict.conflicts = conflicting_activations
+            .iter()
+            .map(|(p, r)| ConflictingActivation {
+                package_id: p.to_spec(),
+                reason: (*r).into(),
+                required_by: required_by_in_context(resolver_ctx, p),
+            })
+            .collect();
+
         for (p, r) in &conflicting_activations {
             match r {
                 ConflictReason::Semver => {
@@ -231,7 +374,7 @@ pub(super) fn activation_error(
         msg.push_str(&*dep.package_name());
         msg.push_str("` which could resolve this conflict");
 
-        return to_resolve_err(anyhow::format_err!("{}", msg));
+        return to_resolve_err(anyhow::format_err!("{}", msg)).with_conflict(conflict);
     }
 
     // We didn't actually find any candidates, so we need to
@@ -240,6 +383,10 @@ pub(super) fn activation_error(
     let mut hints = String::new();
     // Whether any candidate was rejected for being newer than `min-publish-age`,
     let mut has_too_new = false;
+    // Whether any candidate was rejected for being affected by an advisory.
+    let mut has_advisory = false;
+    // Whether any candidate was rejected for being outside of a `resolver.hold`.
+    let mut has_held = false;
     if let Some(version_candidates) = rejected_versions(registry, dep) {
         let version_candidates = match version_candidates {
             Ok(c) => c,
@@ -259,6 +406,44 @@ pub(super) fn activation_error(
             locked_version
         );
         for candidate in version_candidates {
+            let reason = match &candidate {
+                IndexSummary::Candidate(summary) => {
+                    if let Some(violation) = version_prefs.too_new(summary) {
+                        Rejection::TooNew {
+                            published: violation.age_label(),
+                            min_publish_age: violation.config().to_owned(),
+                        }
+                    } else if let Some(advisory) = version_prefs
+                        .advisory(summary)
+                        .filter(|_| version_prefs.denies_advisories())
+                    {
+                        Rejection::Advisory {
+                            id: advisory.id.clone(),
+                        }
+                    } else if let Some(hold) = version_prefs.held(summary) {
+                        Rejection::Held {
+                            hold: hold.to_string(),
+                        }
+                    } else {
+                        Rejection::Unavailable
+                    }
+                }
+                IndexSummary::Yanked(_) => Rejection::Yanked,
+                IndexSummary::Offline(_) => Rejection::NotCached,
+                IndexSummary::Unsupported(summary, index_version) => match summary.rust_version() {
+                    Some(rust_version) => Rejection::RustVersion {
+                        rust_version: rust_version.to_string(),
+                    },
+                    None => Rejection::IndexVersion {
+                        index_version: *index_version,
+                    },
+                },
+                IndexSummary::Invalid(_) => Rejection::Invalid,
+            };
+            conflict.candidates.push(RejectedCandidate {
+                version: candidate.package_id().version().clone(),
+                reason,
+            });
             match candidate {
                 IndexSummary::Candidate(summary) => {
                     if let Some(violation) = version_prefs.too_new(&summary) {
@@ -269,6 +454,24 @@ pub(super) fn activation_error(
                             "  version {} is too new ({note})",
                             summary.version(),
                         );
+                    } else if let Some(advisory) = version_prefs
+                        .advisory(&summary)
+                        .filter(|_| version_prefs.denies_advisories())
+                    {
+                        has_advisory = true;
+                        let _ = writeln!(
+                            &mut msg,
+                            "  version {} is affected by {}",
+                            summary.version(),
+                            advisory.id,
+                        );
+                    } else if let Some(hold) = version_prefs.held(&summary) {
+                        has_held = true;
+                        let _ = writeln!(
+                            &mut msg,
+                            "  version {} is held back by `resolver.hold` (`{hold}`)",
+                            summary.version(),
+                        );
                     } else {
                         // HACK: If this was a real candidate, we wouldn't hit this case.
                         // so it must be a patch which get normalized to being a candidate
@@ -315,6 +518,13 @@ pub(super) fn activation_error(
             Ok(c) => c,
             Err(e) => return to_resolve_err(e),
         };
+        conflict.candidates = candidates
+            .iter()
+            .map(|c| RejectedCandidate {
+                version: c.version().clone(),
+                reason: Rejection::Mismatch,
+            })
+            .collect();
         let versions = {
             let mut versions = candidates
                 .iter()
@@ -497,6 +707,22 @@ pub(super) fn activation_error(
         );
     }
 
+    if has_advisory {
+        let _ = write!(
+            &mut hints,
+            "\nhelp: to use versions affected by advisories when no other version matches, \
+             re-resolve with `CARGO_RESOLVER_INCOMPATIBLE_ADVISORIES=fallback`",
+        );
+    }
+
+    if has_held {
+        let _ = write!(
+            &mut hints,
+            "\nhelp: to use newer versions, update `resolver.hold.{}` in your config",
+            dep.package_name(),
+        );
+    }
+
     if let Some(gctx) = gctx {
         if let Some(offline_flag) = gctx.offline_flag() {
             let _ = write!(
@@ -509,7 +735,7 @@ pub(super) fn activation_error(
         }
     }
 
-    to_resolve_err(anyhow::format_err!("{msg}{hints}"))
+    to_resolve_err(anyhow::format_err!("{msg}{hints}")).with_conflict(conflict)
 }
 
 // Maybe the user mistyped the ver_req? Like `dep="2"` when `dep="0.2"`
@@ -620,6 +846,21 @@ fn alt_paths(
     }
 }
 
+/// Returns the dependency chain for a particular `pkgid` within given context,
+/// as described by [`describe_path_in_context`].
+fn required_by_in_context(cx: &ResolverContext, id: &PackageId) -> Vec<RequiredBy> {
+    cx.parents
+        .path_to_bottom(id)
+        .into_iter()
+        .map(|(p, d)| RequiredBy {
+            package_id: p.to_spec(),
+            requirement: d
+                .and_then(|d| d.iter().next())
+                .map(|d| d.version_req().to_string()),
+        })
+        .collect()
+}
+
 /// Returns String representation of dependency chain for a particular `pkgid`
 /// within given context.
 pub(super) fn describe_path_in_context(cx: &ResolverContext, id: &PackageId) -> String {
@@ -77,15 +77,18 @@ use self::features::RequestedFeatures;
 use self::types::{ConflictMap, ConflictReason, DepsFrame};
 use self::types::{FeaturesSet, RcVecIter, RemainingDeps, ResolverProgress};
 
-pub use self::errors::{ActivateError, ActivateResult, ResolveError};
+pub use self::advisories::{Advisory, AdvisoryDb};
+pub use self::errors::{ActivateError, ActivateResult, ResolveConflict, ResolveError};
 pub use self::features::{CliFeatures, ForceAllTargets, HasDevUnits};
 pub use self::resolve::{Resolve, ResolveVersion};
 pub use self::types::{ResolveBehavior, ResolveOpts};
 pub use self::version_prefs::PublishAgePolicy;
 pub use self::version_prefs::PublishAgeViolation;
+pub use self::version_prefs::VersionHolds;
 pub use self::version_prefs::VersionOrdering;
 pub use self::version_prefs::VersionPreferences;
 
+mod advisories;
 mod conflict_cache;
 mod context;
 mod dep_cache;
@@ -3,9 +3,11 @@
 
 use crate::util::data_structures::{HashMap, HashSet};
 use std::cmp::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use cargo_util_schemas::core::PartialVersion;
+use semver::VersionReq;
 
 use crate::context::CargoResolverConfig;
 use crate::context::GlobalRegistryConfig;
@@ -20,6 +22,8 @@ use crate::workspace::PackageId;
 use crate::workspace::SourceId;
 use crate::workspace::Summary;
 
+use super::advisories::{Advisory, AdvisoryDb};
+
 /// A collection of preferences for particular package versions.
 ///
 /// This is built up with [`Self::prefer_package_id`] and [`Self::prefer_dependency`], then used to sort the set of
@@ -35,6 +39,9 @@ pub struct VersionPreferences {
     rust_versions: Vec<PartialVersion>,
     publish_time: Option<jiff::Timestamp>,
     publish_age: Option<PublishAgePolicy>,
+    advisory_db: Option<Arc<AdvisoryDb>>,
+    deny_advisories: bool,
+    holds: Option<VersionHolds>,
 }
 
 #[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
@@ -80,6 +87,36 @@ impl VersionPreferences {
         self.publish_age.as_ref()?.too_new(summary)
     }
 
+    /// Avoids versions affected by an advisory of `db`, rejecting them when
+    /// `deny` is set.
+    pub fn advisories(&mut self, db: Arc<AdvisoryDb>, deny: bool) {
+        self.advisory_db = Some(db);
+        self.deny_advisories = deny;
+    }
+
+    /// Returns the first advisory affecting the version, if any.
+    pub fn advisory(&self, summary: &Summary) -> Option<&Advisory> {
+        self.advisory_db
+            .as_ref()?
+            .affecting(summary.package_id())
+            .next()
+    }
+
+    /// Whether versions affected by an advisory are rejected rather than
+    /// deprioritized.
+    pub fn denies_advisories(&self) -> bool {
+        self.deny_advisories
+    }
+
+    pub fn holds(&mut self, holds: VersionHolds) {
+        self.holds = Some(holds);
+    }
+
+    /// Returns the `resolver.hold` requirement the version is outside of, if any.
+    pub fn held(&self, summary: &Summary) -> Option<&VersionReq> {
+        self.holds.as_ref()?.held(summary.package_id())
+    }
+
     /// Whether the given package is preferred.
     pub fn should_prefer(&self, pkg_id: &PackageId) -> bool {
         self.try_to_use.contains(pkg_id)
@@ -96,8 +133,9 @@ impl VersionPreferences {
     ///
     /// Sort order:
     /// 1. Preferred packages
-    /// 2. Most compatible [`VersionPreferences::rust_versions`]
-    /// 3. `first_version`, falling back to [`VersionPreferences::version_ordering`] when `None`
+    /// 2. Versions not affected by an advisory
+    /// 3. Most compatible [`VersionPreferences::rust_versions`]
+    /// 4. `first_version`, falling back to [`VersionPreferences::version_ordering`] when `None`
     ///
     /// Filtering:
     /// - `publish_time`
@@ -124,6 +162,14 @@ impl VersionPreferences {
                 return previous_cmp;
             }
 
+            if self.advisory_db.is_some() {
+                let affected_a = self.advisory(a).is_some();
+                let affected_b = self.advisory(b).is_some();
+                if affected_a != affected_b {
+                    return affected_a.cmp(&affected_b);
+                }
+            }
+
             if !self.rust_versions.is_empty() {
                 let a_compat_count = self.msrv_compat_count(a);
                 let b_compat_count = self.msrv_compat_count(b);
@@ -155,6 +201,62 @@ impl VersionPreferences {
     }
 }
 
+/// Version requirements from `resolver.hold`, by package name.
+///
+/// A hold constrains the versions of a registry package the resolver may
+/// select, on top of the requirements of its dependents, without editing
+/// any manifest. Versions already locked are kept.
+#[derive(Debug)]
+pub struct VersionHolds {
+    holds: HashMap<InternedString, VersionReq>,
+}
+
+impl VersionHolds {
+    /// Reads `resolver.hold`.
+    ///
+    /// Returns `None` without `-Zresolver-hold` or when nothing is held.
+    pub fn new(gctx: &GlobalContext) -> CargoResult<Option<Self>> {
+        if !gctx.cli_unstable().resolver_hold {
+            return Ok(None);
+        }
+        let Some(config) = gctx
+            .get::<Option<CargoResolverConfig>>("resolver")?
+            .and_then(|c| c.hold)
+        else {
+            return Ok(None);
+        };
+        if config.is_empty() {
+            return Ok(None);
+        }
+
+        let mut holds = HashMap::default();
+        for (name, req) in config {
+            let req = req.parse::<VersionReq>().map_err(|e| {
+                anyhow::format_err!("invalid value for `resolver.hold.{name}`: {e}")
+            })?;
+            holds.insert(InternedString::new(&name), req);
+        }
+        Ok(Some(Self { holds }))
+    }
+
+    /// Returns the requirement a package is held to, if any.
+    pub fn get(&self, name: InternedString) -> Option<&VersionReq> {
+        self.holds.get(&name)
+    }
+
+    /// Returns the requirement the package is held to when its version is
+    /// outside of it.
+    ///
+    /// Only registry packages are held.
+    pub fn held(&self, pkg_id: PackageId) -> Option<&VersionReq> {
+        if !pkg_id.source_id().is_registry() {
+            return None;
+        }
+        self.get(pkg_id.name())
+            .filter(|req| !req.matches(pkg_id.version()))
+    }
+}
+
 /// Snapshot of the `min-publish-age` configuration before resolution started.
 #[derive(Debug)]
 pub struct PublishAgePolicy {
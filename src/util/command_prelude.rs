@@ -505,6 +505,17 @@ pub trait CommandExt: Sized {
         )
     }
 
+    fn arg_explain_fresh(self) -> Self {
+        self._arg(
+            opt(
+                "explain-fresh",
+                "Explain why a package would be rebuilt, without compiling (unstable)",
+            )
+            .value_name("SPEC")
+            .help_heading(heading::COMPILATION_OPTIONS),
+        )
+    }
+
     fn arg_artifact_dir(self) -> Self {
         let unsupported_short_arg = {
             let value_parser = UnknownArgumentValueParser::suggest_arg("--artifact-dir");
@@ -92,6 +92,10 @@ pub enum LogMessage {
         /// Unit indices that this unit depends on.
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         dependencies: Vec<UnitIndex>,
+        /// Subset of `dependencies` whose metadata (rmeta) is sufficient
+        /// for this unit to start, allowing them to be pipelined.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rmeta_dependencies: Vec<UnitIndex>,
     },
     /// Emitted when a compilation unit starts.
     UnitStarted {
@@ -164,7 +168,7 @@ pub struct Target {
 }
 
 /// Status of the rebuild detection fingerprint.
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum FingerprintStatus {
     /// There is no previous fingerprints for this unit.
@@ -5,6 +5,11 @@ use serde::Serialize;
 use serde::ser;
 use serde_json::value::RawValue;
 
+use crate::compiler::CompileMode;
+use crate::compiler::fingerprint::DirtyReason;
+use crate::resolver::ResolveConflict;
+use crate::util::log_message;
+use crate::util::log_message::FingerprintStatus;
 use crate::workspace::Target;
 
 pub trait Message: ser::Serialize {
@@ -103,3 +108,122 @@ impl Message for BuildFinished {
         "build-finished"
     }
 }
+
+#[derive(Serialize)]
+pub struct ResolveError<'a> {
+    pub message: String,
+    #[serde(flatten)]
+    pub conflict: &'a ResolveConflict,
+}
+
+impl<'a> Message for ResolveError<'a> {
+    fn reason(&self) -> &str {
+        "resolve-error"
+    }
+}
+
+/// A unit from a previous session, as recorded by `-Zbuild-analysis`.
+#[derive(Serialize)]
+pub struct ReportUnit<'a> {
+    pub package_id: &'a PackageIdSpec,
+    pub target: &'a log_message::Target,
+    pub mode: CompileMode,
+}
+
+#[derive(Serialize)]
+pub struct ReportSession {
+    pub id: String,
+    pub timestamp: jiff::Timestamp,
+}
+
+impl Message for ReportSession {
+    fn reason(&self) -> &str {
+        "report-session"
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportUnitTiming<'a> {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub unit: ReportUnit<'a>,
+    pub start: f64,
+    pub duration: f64,
+    pub rmeta_time: Option<f64>,
+    pub critical: bool,
+    pub slack: f64,
+}
+
+impl<'a> Message for ReportUnitTiming<'a> {
+    fn reason(&self) -> &str {
+        "report-unit-timing"
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportTimingsFinished {
+    pub session_id: String,
+    pub total_time: f64,
+    pub fresh_units: u32,
+    pub dirty_units: u32,
+}
+
+impl Message for ReportTimingsFinished {
+    fn reason(&self) -> &str {
+        "report-timings-finished"
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportRootRebuild<'a> {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub unit: ReportUnit<'a>,
+    pub cause: &'a DirtyReason,
+    pub affected_units: Vec<ReportUnit<'a>>,
+}
+
+impl<'a> Message for ReportRootRebuild<'a> {
+    fn reason(&self) -> &str {
+        "report-root-rebuild"
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportRebuildsFinished {
+    pub session_id: String,
+    pub rebuilt_units: usize,
+    pub cached_units: usize,
+    pub new_units: usize,
+}
+
+impl Message for ReportRebuildsFinished {
+    fn reason(&self) -> &str {
+        "report-rebuilds-finished"
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReportUnitComparison<'a> {
+    pub base_session_id: String,
+    pub new_session_id: String,
+    #[serde(flatten)]
+    pub unit: ReportUnit<'a>,
+    pub platform: &'a str,
+    pub base: Option<ReportUnitState<'a>>,
+    pub new: Option<ReportUnitState<'a>>,
+}
+
+impl<'a> Message for ReportUnitComparison<'a> {
+    fn reason(&self) -> &str {
+        "report-unit-comparison"
+    }
+}
+
+/// State of a unit in one of the sessions of a [`ReportUnitComparison`].
+#[derive(Serialize)]
+pub struct ReportUnitState<'a> {
+    pub status: Option<FingerprintStatus>,
+    pub cause: Option<&'a DirtyReason>,
+    pub duration: Option<f64>,
+}
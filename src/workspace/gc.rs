@@ -108,6 +108,10 @@ struct GlobalCleanConfig {
     max_git_co_age: Option<String>,
     /// Any git clone older than this duration will be deleted from the git cache.
     max_git_db_age: Option<String>,
+    /// Any build cache entry older than this duration will be deleted from the build cache.
+    max_build_cache_age: Option<String>,
+    /// The oldest build cache entries will be deleted until the build cache is under this size.
+    max_build_cache_size: Option<String>,
 }
 
 /// Options to use for garbage collection.
@@ -131,6 +135,10 @@ pub struct GcOpts {
     pub max_git_size: Option<u64>,
     /// The `--max-download-size` CLI option.
     pub max_download_size: Option<u64>,
+    /// The `--max-build-cache-age` CLI option.
+    pub max_build_cache_age: Option<Duration>,
+    /// The `--max-build-cache-size` CLI option.
+    pub max_build_cache_size: Option<u64>,
 }
 
 impl GcOpts {
@@ -155,6 +163,11 @@ impl GcOpts {
             || self.max_download_size.is_some()
     }
 
+    /// Returns whether any build cache cleaning options are set.
+    pub fn is_build_cache_opt_set(&self) -> bool {
+        self.max_build_cache_age.is_some() || self.max_build_cache_size.is_some()
+    }
+
     /// Updates the `GcOpts` to incorporate the specified max download age.
     ///
     /// "Download" means any cached data that can be re-downloaded.
@@ -241,6 +254,27 @@ impl GcOpts {
                 unstable_allowed
             ),
         )?;
+        self.max_build_cache_age = newer_time_span_for_config(
+            self.max_build_cache_age,
+            "cache.global-clean.max-build-cache-age",
+            config_default!(
+                config,
+                max_build_cache_age,
+                DEFAULT_MAX_AGE_EXTRACTED,
+                unstable_allowed
+            ),
+        )?;
+        if unstable_allowed {
+            if let Some(size) = &config.max_build_cache_size {
+                let size = parse_human_size(size).with_context(
+                    || "failed to parse config option `cache.global-clean.max-build-cache-size`",
+                )?;
+                self.max_build_cache_size = Some(
+                    self.max_build_cache_size
+                        .map_or(size, |cur_size| cur_size.min(size)),
+                );
+            }
+        }
         Ok(())
     }
 }
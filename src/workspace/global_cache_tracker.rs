@@ -139,6 +139,7 @@ const REGISTRY_CRATE_TABLE: &str = "registry_crate";
 const REGISTRY_SRC_TABLE: &str = "registry_src";
 const GIT_DB_TABLE: &str = "git_db";
 const GIT_CO_TABLE: &str = "git_checkout";
+const BUILD_CACHE_TABLE: &str = "build_cache";
 
 /// How often timestamps will be updated.
 ///
@@ -210,6 +211,15 @@ pub struct GitCheckout {
     pub size: Option<u64>,
 }
 
+/// The key for a build cache entry stored in the database.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct BuildCacheEntry {
+    /// The directory name of the entry, which is its key.
+    pub name: InternedString,
+    /// Total size of the entry directory in bytes.
+    pub size: u64,
+}
+
 /// Filesystem paths in the global cache.
 ///
 /// Accessing these assumes a lock has already been acquired.
@@ -224,6 +234,8 @@ struct BasePaths {
     crate_dir: PathBuf,
     /// Root path to the `src` directories.
     src: PathBuf,
+    /// Root path to the build cache entries.
+    build_cache: PathBuf,
 }
 
 /// Migrations which initialize the database, and can be used to evolve it over time.
@@ -304,6 +316,14 @@ fn migrations() -> Vec<Migration> {
             )?;
             Ok(())
         }),
+        // Entries of the build cache (`-Zbuild-cache`)
+        basic_migration(
+            "CREATE TABLE build_cache (
+                name TEXT UNIQUE NOT NULL,
+                size INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL
+             )",
+        ),
     ]
 }
 
@@ -513,6 +533,22 @@ impl GlobalCacheTracker {
         Ok(rows)
     }
 
+    /// Returns all build cache entry timestamps.
+    pub fn build_cache_all(&self) -> CargoResult<Vec<(BuildCacheEntry, Timestamp)>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT name, size, timestamp FROM build_cache")?;
+        let rows = stmt
+            .query_map([], |row| {
+                let name = row.get_unwrap(0);
+                let size = row.get_unwrap(1);
+                let timestamp = row.get_unwrap(2);
+                Ok((BuildCacheEntry { name, size }, timestamp))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
     /// Returns whether or not an auto GC should be performed, compared to the
     /// last time it was recorded in the database.
     pub fn should_run_auto_gc(&mut self, frequency: Duration) -> CargoResult<bool> {
@@ -560,6 +596,7 @@ impl GlobalCacheTracker {
             git_co: gctx.git_checkouts_path().into_path_unlocked(),
             crate_dir: gctx.registry_cache_path().into_path_unlocked(),
             src: gctx.registry_source_path().into_path_unlocked(),
+            build_cache: gctx.build_cache_path().into_path_unlocked(),
         };
         let now = now();
         trace!(target: "gc", "cleaning {gc_opts:?}");
@@ -578,6 +615,10 @@ impl GlobalCacheTracker {
             )
             .context("failed to sync tracking database")?
         }
+        if gc_opts.is_build_cache_opt_set() {
+            Self::sync_build_cache_with_files(&tx, now, &base.build_cache)
+                .context("failed to sync tracking database")?
+        }
         if let Some(max_age) = gc_opts.max_index_age {
             let max_age = now - max_age.as_secs();
             Self::get_registry_index_to_clean(&tx, max_age, &base, &mut delete_paths)?;
@@ -610,6 +651,10 @@ impl GlobalCacheTracker {
             let max_age = now - max_age.as_secs();
             Self::get_git_co_items_to_clean(&tx, max_age, &base.git_co, &mut delete_paths)?;
         }
+        if let Some(max_age) = gc_opts.max_build_cache_age {
+            let max_age = now - max_age.as_secs();
+            Self::get_build_cache_to_clean_age(&tx, max_age, &base.build_cache, &mut delete_paths)?;
+        }
         // Size collection must happen after date collection so that dates
         // have precedence, since size constraints are a more blunt
         // instrument.
@@ -647,6 +692,14 @@ impl GlobalCacheTracker {
         if let Some(max_size) = gc_opts.max_download_size {
             Self::get_registry_items_to_clean_size_both(&tx, max_size, &base, &mut delete_paths)?;
         }
+        if let Some(max_size) = gc_opts.max_build_cache_size {
+            Self::get_build_cache_to_clean_size(
+                &tx,
+                max_size,
+                &base.build_cache,
+                &mut delete_paths,
+            )?;
+        }
 
         clean_ctx.remove_paths(&delete_paths)?;
 
@@ -808,6 +861,45 @@ impl GlobalCacheTracker {
         Ok(())
     }
 
+    /// Synchronizes the `build_cache` table to match the entries on disk.
+    ///
+    /// Entries missing on disk are removed from the database, and entries
+    /// missing from the database (such as when saving the last-use data
+    /// failed) are added with their current size.
+    #[tracing::instrument(skip(conn, now, base_path))]
+    fn sync_build_cache_with_files(
+        conn: &Connection,
+        now: Timestamp,
+        base_path: &Path,
+    ) -> CargoResult<()> {
+        trace!(target: "gc", "syncing {BUILD_CACHE_TABLE} with files");
+        let mut select_stmt = conn.prepare_cached("SELECT rowid, name FROM build_cache")?;
+        let mut delete_stmt = conn.prepare_cached("DELETE FROM build_cache WHERE rowid = ?1")?;
+        let mut rows = select_stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let rowid: i64 = row.get_unwrap(0);
+            let name: String = row.get_unwrap(1);
+            if !base_path.join(&name).exists() {
+                delete_stmt.execute([rowid])?;
+            }
+        }
+
+        let mut exists_stmt = conn.prepare_cached("SELECT 1 FROM build_cache WHERE name = ?1")?;
+        let mut insert_stmt = conn.prepare_cached(
+            "INSERT INTO build_cache (name, size, timestamp)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT DO NOTHING",
+        )?;
+        for name in Self::list_dir_names(base_path)? {
+            if exists_stmt.exists([&name])? {
+                continue;
+            }
+            let size = du(&base_path.join(&name), BUILD_CACHE_TABLE)?;
+            insert_stmt.execute(params![name, size, now])?;
+        }
+        Ok(())
+    }
+
     /// For parent tables, add any entries that are on disk but aren't tracked in the db.
     #[tracing::instrument(skip(conn, now, base_path))]
     fn update_parent_for_missing_from_db(
@@ -1332,6 +1424,63 @@ impl GlobalCacheTracker {
         Ok(())
     }
 
+    /// Adds paths to delete from `build_cache` whose last use is older than
+    /// the given timestamp.
+    fn get_build_cache_to_clean_age(
+        conn: &Connection,
+        max_age: Timestamp,
+        base_path: &Path,
+        delete_paths: &mut Vec<PathBuf>,
+    ) -> CargoResult<()> {
+        debug!(target: "gc", "cleaning build cache since {max_age:?}");
+        let mut stmt = conn.prepare_cached(
+            "DELETE FROM build_cache WHERE timestamp < ?1
+                RETURNING name",
+        )?;
+        let mut rows = stmt.query([max_age])?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get_unwrap(0);
+            delete_paths.push(base_path.join(&name));
+        }
+        Ok(())
+    }
+
+    /// Adds paths to delete from `build_cache` in order to keep the total
+    /// size under the given max size.
+    fn get_build_cache_to_clean_size(
+        conn: &Connection,
+        max_size: u64,
+        base_path: &Path,
+        delete_paths: &mut Vec<PathBuf>,
+    ) -> CargoResult<()> {
+        debug!(target: "gc", "cleaning build cache till under {max_size:?}");
+        let total_size: u64 = conn.query_row(
+            "SELECT coalesce(SUM(size), 0) FROM build_cache",
+            [],
+            |row| row.get(0),
+        )?;
+        if total_size <= max_size {
+            return Ok(());
+        }
+        // See `get_registry_items_to_clean_size` for how this picks the
+        // oldest entries until enough bytes are deleted.
+        let mut stmt = conn.prepare_cached(
+            "DELETE FROM build_cache WHERE rowid IN \
+                (SELECT x.rowid FROM \
+                    (SELECT rowid, size, SUM(size) OVER \
+                        (ORDER BY timestamp, name ROWS UNBOUNDED PRECEDING) AS running_amount \
+                        FROM build_cache) x \
+                    WHERE x.running_amount - x.size < ?1) \
+                RETURNING name;",
+        )?;
+        let mut rows = stmt.query([total_size - max_size])?;
+        while let Some(row) = rows.next()? {
+            let name: String = row.get_unwrap(0);
+            delete_paths.push(base_path.join(&name));
+        }
+        Ok(())
+    }
+
     /// Adds paths to delete from `git_db` in order to keep the total size
     /// under the given max size.
     fn get_git_db_items_to_clean(
@@ -1443,6 +1592,8 @@ pub struct DeferredGlobalLastUse {
     git_db_timestamps: HashMap<GitDb, Timestamp>,
     /// New git checkout entries to insert.
     git_checkout_timestamps: HashMap<GitCheckout, Timestamp>,
+    /// New build cache entries to insert.
+    build_cache_timestamps: HashMap<BuildCacheEntry, Timestamp>,
     /// This is used so that a warning about failing to update the database is
     /// only displayed once.
     save_err_has_warned: bool,
@@ -1461,6 +1612,7 @@ impl DeferredGlobalLastUse {
             registry_src_timestamps: HashMap::default(),
             git_db_timestamps: HashMap::default(),
             git_checkout_timestamps: HashMap::default(),
+            build_cache_timestamps: HashMap::default(),
             save_err_has_warned: false,
             now: now(),
         }
@@ -1472,6 +1624,7 @@ impl DeferredGlobalLastUse {
             && self.registry_src_timestamps.is_empty()
             && self.git_db_timestamps.is_empty()
             && self.git_checkout_timestamps.is_empty()
+            && self.build_cache_timestamps.is_empty()
     }
 
     fn clear(&mut self) {
@@ -1480,6 +1633,7 @@ impl DeferredGlobalLastUse {
         self.registry_src_timestamps.clear();
         self.git_db_timestamps.clear();
         self.git_checkout_timestamps.clear();
+        self.build_cache_timestamps.clear();
     }
 
     /// Indicates the given [`RegistryIndex`] has been used right now.
@@ -1508,6 +1662,11 @@ impl DeferredGlobalLastUse {
         self.mark_git_checkout_used_stamp(git_checkout, None);
     }
 
+    /// Indicates the given [`BuildCacheEntry`] has been used right now.
+    pub fn mark_build_cache_used(&mut self, build_cache_entry: BuildCacheEntry) {
+        self.mark_build_cache_used_stamp(build_cache_entry, None);
+    }
+
     /// Indicates the given [`RegistryIndex`] has been used with the given
     /// time (or "now" if `None`).
     pub fn mark_registry_index_used_stamp(
@@ -1572,6 +1731,18 @@ impl DeferredGlobalLastUse {
         self.git_checkout_timestamps.insert(git_checkout, timestamp);
     }
 
+    /// Indicates the given [`BuildCacheEntry`] has been used with the given
+    /// time (or "now" if `None`).
+    pub fn mark_build_cache_used_stamp(
+        &mut self,
+        build_cache_entry: BuildCacheEntry,
+        timestamp: Option<&SystemTime>,
+    ) {
+        let timestamp = timestamp.map_or(self.now, to_timestamp);
+        self.build_cache_timestamps
+            .insert(build_cache_entry, timestamp);
+    }
+
     /// Saves all of the deferred information to the database.
     ///
     /// This will also clear the state of `self`.
@@ -1588,6 +1759,7 @@ impl DeferredGlobalLastUse {
         self.insert_registry_crate_from_cache(&tx)?;
         self.insert_registry_src_from_cache(&tx)?;
         self.insert_git_checkout_from_cache(&tx)?;
+        self.insert_build_cache_from_cache(&tx)?;
         tx.commit()?;
         trace!(target: "gc", "last-use save complete");
         Ok(())
@@ -1726,6 +1898,29 @@ impl DeferredGlobalLastUse {
         Ok(())
     }
 
+    /// Flushes all of the `build_cache_timestamps` to the database,
+    /// clearing `build_cache_timestamps`.
+    fn insert_build_cache_from_cache(&mut self, conn: &Connection) -> CargoResult<()> {
+        let build_cache_timestamps = std::mem::take(&mut self.build_cache_timestamps);
+        for (build_cache_entry, timestamp) in build_cache_timestamps {
+            trace!(target: "gc", "insert build cache {build_cache_entry:?} {timestamp}");
+            let mut stmt = conn.prepare_cached(
+                "INSERT INTO build_cache (name, size, timestamp)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT DO UPDATE SET timestamp=excluded.timestamp
+                    WHERE timestamp < ?4",
+            )?;
+            stmt.execute(params![
+                build_cache_entry.name,
+                build_cache_entry.size,
+                timestamp,
+                timestamp - UPDATE_RESOLUTION
+            ])?;
+        }
+
+        Ok(())
+    }
+
     /// Returns the numeric ID of the registry, either fetching from the local
     /// cache, or getting it from the database.
     ///
@@ -130,6 +130,8 @@ pub struct Workspace<'gctx> {
     resolve_honors_publish_age: bool,
     /// Latest publish time allowed for packages
     resolve_publish_time: Option<jiff::Timestamp>,
+    /// Whether resolution prefers the lowest compatible versions, like `-Zminimal-versions`.
+    resolve_minimal_versions: bool,
     /// Workspace-level custom metadata
     custom_metadata: Option<toml::Value>,
 
@@ -269,6 +271,7 @@ impl<'gctx> Workspace<'gctx> {
             resolve_feature_unification: FeatureUnification::Selected,
             resolve_honors_publish_age: true,
             resolve_publish_time: None,
+            resolve_minimal_versions: false,
             custom_metadata: None,
             local_overlays: HashMap::default(),
         }
@@ -358,6 +361,25 @@ impl<'gctx> Workspace<'gctx> {
                 .warn("ignoring `resolver.feature-unification` without `-Zfeature-unification`")?;
         };
 
+        if !self.gctx().cli_unstable().advisories {
+            if config.advisory_db.is_some() {
+                self.gctx()
+                    .shell()
+                    .warn("ignoring `resolver.advisory-db` without `-Zadvisories`")?;
+            }
+            if config.incompatible_advisories.is_some() {
+                self.gctx()
+                    .shell()
+                    .warn("ignoring `resolver.incompatible-advisories` without `-Zadvisories`")?;
+            }
+        }
+
+        if !self.gctx().cli_unstable().resolver_hold && config.hold.is_some() {
+            self.gctx()
+                .shell()
+                .warn("ignoring `resolver.hold` without `-Zresolver-hold`")?;
+        }
+
         if !self.gctx().cli_unstable().min_publish_age {
             if config.incompatible_publish_age.is_some() {
                 self.gctx().shell().warn(
@@ -800,6 +822,14 @@ impl<'gctx> Workspace<'gctx> {
         self.resolve_publish_time
     }
 
+    pub fn set_resolve_minimal_versions(&mut self, minimal_versions: bool) {
+        self.resolve_minimal_versions = minimal_versions;
+    }
+
+    pub fn resolve_minimal_versions(&self) -> bool {
+        self.resolve_minimal_versions || self.gctx.cli_unstable().minimal_versions
+    }
+
     pub fn custom_metadata(&self) -> Option<&toml::Value> {
         self.custom_metadata.as_ref()
     }
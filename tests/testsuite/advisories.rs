@@ -0,0 +1,213 @@
+//! Tests for the `-Zadvisories` feature.
+
+use cargo_test_support::registry::Package;
+use cargo_test_support::{Project, project, str};
+
+use crate::prelude::*;
+
+const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2024-0001"
+package = "bar"
+date = "2024-01-01"
+
+[versions]
+patched = [">= 1.2.0"]
+unaffected = ["< 1.1.0"]
+```
+
+# Memory corruption in `bar`
+"#;
+
+fn make_project(bar_req: &str, config: &str) -> Project {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.1.0").publish();
+    project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    edition = "2021"
+
+                    [dependencies]
+                    bar = "{bar_req}"
+                "#
+            ),
+        )
+        .file(
+            ".cargo/config.toml",
+            &format!(
+                r#"
+                    [resolver]
+                    advisory-db = "advisory-db"
+                    {config}
+                "#
+            ),
+        )
+        .file("advisory-db/crates/bar/RUSTSEC-2024-0001.md", ADVISORY)
+        .file(
+            "advisory-db/crates/bar/RUSTSEC-2024-0002.md",
+            r#"```toml
+[advisory]
+id = "RUSTSEC-2024-0002"
+package = "bar"
+date = "2024-02-01"
+withdrawn = "2024-03-01"
+
+[versions]
+patched = []
+```
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+fn assert_locked_bar(p: &Project, version: &str) {
+    let lock = p.read_lockfile();
+    assert!(
+        lock.contains(&format!("name = \"bar\"\nversion = \"{version}\"")),
+        "expected bar {version} in:\n{lock}"
+    );
+}
+
+#[cargo_test]
+fn feature_gated() {
+    let p = make_project("1.0", r#"incompatible-advisories = "deny""#);
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_stderr_data(str![[r#"
+[WARNING] ignoring `resolver.advisory-db` without `-Zadvisories`
+[WARNING] ignoring `resolver.incompatible-advisories` without `-Zadvisories`
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.1.0");
+}
+
+#[cargo_test]
+fn fallback_prefers_unaffected_versions() {
+    let p = make_project("1.0", "");
+
+    p.cargo("generate-lockfile -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[ADDING] bar v1.0.0 (available: v1.1.0)
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.0.0");
+
+    // A patched release is picked up by `cargo update`.
+    Package::new("bar", "1.2.0").publish();
+    p.cargo("update -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] bar v1.0.0 -> v1.2.0
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.2.0");
+}
+
+#[cargo_test]
+fn fallback_uses_affected_versions_when_required() {
+    let p = make_project("1.1", "");
+
+    p.cargo("generate-lockfile -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.1.0");
+}
+
+#[cargo_test]
+fn deny_rejects_affected_versions() {
+    let p = make_project("1.1", r#"incompatible-advisories = "deny""#);
+
+    p.cargo("generate-lockfile -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for the requirement `bar = "^1.1"`
+  version 1.1.0 is affected by RUSTSEC-2024-0001
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.0.0 ([ROOT]/foo)`
+[HELP] to use versions affected by advisories when no other version matches, re-resolve with `CARGO_RESOLVER_INCOMPATIBLE_ADVISORIES=fallback`
+
+"#]])
+        .run();
+
+    // Locked versions are kept.
+    p.cargo("generate-lockfile").run();
+    p.cargo("check -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .run();
+    assert_locked_bar(&p, "1.1.0");
+}
+
+#[cargo_test]
+fn allow_ignores_advisories() {
+    let p = make_project("1.0", r#"incompatible-advisories = "allow""#);
+
+    p.cargo("generate-lockfile -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .run();
+    assert_locked_bar(&p, "1.1.0");
+}
+
+#[cargo_test]
+fn tree_annotates_affected_packages() {
+    let p = make_project("1.1", r#"incompatible-advisories = "allow""#);
+
+    p.cargo("tree -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_stdout_data(str![[r#"
+foo v0.0.0 ([ROOT]/foo)
+└── bar v1.1.0 (RUSTSEC-2024-0001)
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn invalid_advisory() {
+    let p = make_project("1.0", "");
+    p.change_file(
+        "advisory-db/crates/bar/RUSTSEC-2024-0003.toml",
+        "[advisory]\nid = \"RUSTSEC-2024-0003\"\n",
+    );
+
+    p.cargo("generate-lockfile -Zadvisories")
+        .masquerade_as_nightly_cargo(&["advisories"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to load the advisory database at `[ROOT]/foo/advisory-db`
+
+Caused by:
+  failed to parse advisory `[ROOT]/foo/advisory-db/crates/bar/RUSTSEC-2024-0003.toml`
+
+Caused by:
+  TOML parse error at line 1, column 1
+    |
+  1 | [advisory]
+    | ^^^^^^^^^^
+  missing field `package`
+
+"#]])
+        .run();
+}
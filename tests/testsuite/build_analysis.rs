@@ -4,8 +4,10 @@ use crate::prelude::*;
 
 use cargo_test_support::basic_manifest;
 use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::is_coarse_mtime;
 use cargo_test_support::paths::log_file;
 use cargo_test_support::project;
+use cargo_test_support::sleep_ms;
 use cargo_test_support::str;
 
 #[cargo_test]
@@ -719,6 +721,120 @@ fn log_msg_resolution_events() {
     );
 }
 
+#[cargo_test]
+fn explain_fresh_gated() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("build --explain-fresh foo")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--explain-fresh` flag is unstable, pass `-Z build-analysis` to enable it
+See https://github.com/rust-lang/cargo/issues/15844 for more information about the `--explain-fresh` flag.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explain_fresh() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = { path = "a" }
+            "#,
+        )
+        .file("src/lib.rs", "extern crate a;")
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                b = { path = "../b" }
+            "#,
+        )
+        .file("a/src/lib.rs", "extern crate b;")
+        .file("b/Cargo.toml", &basic_manifest("b", "0.0.1"))
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("build --explain-fresh foo -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 2 packages to latest compatible versions
+[DIRTY] foo v0.0.1 ([ROOT]/foo): fresh build
+
+"#]])
+        .run();
+
+    p.cargo("build").run();
+
+    p.cargo("build --explain-fresh foo -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+[FRESH] foo v0.0.1 ([ROOT]/foo)
+
+"#]])
+        .run();
+
+    if is_coarse_mtime() {
+        sleep_ms(1000);
+    }
+    p.change_file("b/src/lib.rs", "pub fn b() {}");
+
+    p.cargo("build --explain-fresh foo -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+[DIRTY] foo v0.0.1 ([ROOT]/foo): the dependency `a` was rebuilt
+[DIRTY] a v0.0.1 ([ROOT]/foo/a): the dependency `b` was rebuilt
+[DIRTY] b v0.0.1 ([ROOT]/foo/b): the file `b/src/lib.rs` has changed ([TIME_DIFF_AFTER_LAST_BUILD])
+
+"#]])
+        .run();
+
+    p.cargo("build --explain-fresh b -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+[DIRTY] b v0.0.1 ([ROOT]/foo/b): the file `b/src/lib.rs` has changed ([TIME_DIFF_AFTER_LAST_BUILD])
+
+"#]])
+        .run();
+
+    // Nothing was compiled, so the next build still rebuilds everything.
+    p.cargo("build")
+        .with_stderr_data(str![[r#"
+[COMPILING] b v0.0.1 ([ROOT]/foo/b)
+[COMPILING] a v0.0.1 ([ROOT]/foo/a)
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    p.cargo("build --explain-fresh bar -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] package ID specification `bar` did not match any units in this build
+
+"#]])
+        .run();
+}
+
 fn get_log(idx: usize) -> String {
     std::fs::read_to_string(log_file(idx)).unwrap()
 }
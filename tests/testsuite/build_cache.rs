@@ -0,0 +1,297 @@
+//! Tests for the `-Zbuild-cache` shared build cache.
+
+use crate::prelude::*;
+use cargo_test_support::build_cache::BuildCacheServer;
+use cargo_test_support::paths;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{Project, basic_manifest, project, str};
+
+fn entries() -> usize {
+    match paths::cargo_home().join("build-cache").read_dir() {
+        Ok(entries) => entries.count(),
+        Err(_) => 0,
+    }
+}
+
+fn bar_user(name: &str) -> Project {
+    project()
+        .at(name)
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "{name}"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    bar = "1.0"
+                "#
+            ),
+        )
+        .file(
+            "src/main.rs",
+            r#"
+                extern crate bar;
+                fn main() { println!("{}", bar::answer()); }
+            "#,
+        )
+        .build()
+}
+
+#[cargo_test]
+fn shares_registry_deps_between_workspaces() {
+    Package::new("bar", "1.0.0")
+        .file(
+            "build.rs",
+            r#"fn main() { println!("cargo::rustc-cfg=from_build_script"); }"#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+                #[cfg(from_build_script)]
+                pub fn answer() -> u32 { 42 }
+            "#,
+        )
+        .publish();
+
+    let foo = bar_user("foo");
+    foo.cargo("build -v -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .with_stderr_contains("[RUNNING] `rustc --crate-name bar [..]")
+        .run();
+    // The library and its build script.
+    assert_eq!(entries(), 2);
+
+    let other = bar_user("other");
+    other
+        .cargo("run -v -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .with_stdout_data(str![[r#"
+42
+
+"#]])
+        .with_stderr_contains("[COMPILING] bar v1.0.0")
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name bar [..]")
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name build_script_build [..]")
+        .with_stderr_contains("[RUNNING] `rustc --crate-name other [..]")
+        .run();
+    assert_eq!(entries(), 2);
+
+    // Restored units are fresh afterwards.
+    other
+        .cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .with_stderr_data(str![[r#"
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn different_flags_are_not_shared() {
+    Package::new("bar", "1.0.0")
+        .file("src/lib.rs", "pub fn answer() -> u32 { 42 }")
+        .publish();
+
+    let foo = bar_user("foo");
+    foo.cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .run();
+    assert_eq!(entries(), 1);
+
+    let other = bar_user("other");
+    other
+        .cargo("build -v -Zbuild-cache --release")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .with_stderr_contains("[RUNNING] `rustc --crate-name bar [..]")
+        .run();
+    assert_eq!(entries(), 2);
+}
+
+#[cargo_test]
+fn path_deps_are_not_cached() {
+    // `bar` comes from the registry, but its dependency `baz` is patched
+    // with a path dependency, whose source can change at any time.
+    Package::new("baz", "1.0.0").publish();
+    Package::new("bar", "1.0.0")
+        .dep("baz", "1.0")
+        .file(
+            "src/lib.rs",
+            "extern crate baz; pub fn answer() -> u32 { baz::ANSWER }",
+        )
+        .publish();
+    let baz = project()
+        .at("baz")
+        .file("Cargo.toml", &basic_manifest("baz", "1.0.0"))
+        .file("src/lib.rs", "pub const ANSWER: u32 = 42;")
+        .build();
+
+    for name in ["foo", "other"] {
+        let p = bar_user(name);
+        p.change_file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "{name}"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    bar = "1.0"
+
+                    [patch.crates-io]
+                    baz = {{ path = "{}" }}
+                "#,
+                baz.root().display().to_string().replace('\\', "/")
+            ),
+        );
+        p.cargo("build -v -Zbuild-cache")
+            .masquerade_as_nightly_cargo(&["build-cache"])
+            .with_stderr_contains("[RUNNING] `rustc --crate-name bar [..]")
+            .with_stderr_contains("[RUNNING] `rustc --crate-name baz [..]")
+            .run();
+    }
+    assert_eq!(entries(), 0);
+}
+
+#[cargo_test]
+fn not_used_without_flag() {
+    Package::new("bar", "1.0.0")
+        .file("src/lib.rs", "pub fn answer() -> u32 { 42 }")
+        .publish();
+
+    let foo = bar_user("foo");
+    foo.cargo("build").run();
+    assert_eq!(entries(), 0);
+}
+
+#[cargo_test]
+fn remote_cache_shares_between_machines() {
+    Package::new("bar", "1.0.0")
+        .file("src/lib.rs", "pub fn answer() -> u32 { 42 }")
+        .publish();
+    let server = BuildCacheServer::new();
+
+    let foo = bar_user("foo");
+    foo.cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("CARGO_BUILD_CACHE_URL", server.url())
+        .run();
+    let remote = server.entries();
+    assert_eq!(remote.len(), 1);
+    let key = &remote[0];
+    let requests = server.take_requests();
+    assert_eq!(
+        requests.first().unwrap(),
+        &format!("GET /cache/{key}/manifest.json")
+    );
+    // The manifest is uploaded last.
+    assert_eq!(
+        requests.last().unwrap(),
+        &format!("PUT /cache/{key}/manifest.json")
+    );
+
+    // Another machine, with an empty local cache.
+    paths::cargo_home().join("build-cache").rm_rf();
+    let other = bar_user("other");
+    other
+        .cargo("run -v -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("CARGO_BUILD_CACHE_URL", server.url())
+        .with_stdout_data(str![[r#"
+42
+
+"#]])
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name bar [..]")
+        .with_stderr_contains("[RUNNING] `rustc --crate-name other [..]")
+        .run();
+    assert_eq!(entries(), 1);
+    assert!(
+        server
+            .take_requests()
+            .iter()
+            .all(|request| request.starts_with("GET "))
+    );
+}
+
+#[cargo_test]
+fn remote_cache_verifies_checksums() {
+    Package::new("bar", "1.0.0")
+        .file("src/lib.rs", "pub fn answer() -> u32 { 42 }")
+        .publish();
+    let server = BuildCacheServer::new();
+
+    let foo = bar_user("foo");
+    foo.cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("CARGO_BUILD_CACHE_URL", server.url())
+        .run();
+    let entry = server.dir().join(&server.entries()[0]);
+    let rlib = entry
+        .read_dir()
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "rlib"))
+        .unwrap();
+    std::fs::write(&rlib, "corrupted").unwrap();
+
+    paths::cargo_home().join("build-cache").rm_rf();
+    let other = bar_user("other");
+    other
+        .cargo("run -v -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("CARGO_BUILD_CACHE_URL", server.url())
+        .with_stdout_data(str![[r#"
+42
+
+"#]])
+        .with_stderr_contains("[WARNING] checksum mismatch for `http://[..]/cache/[..]/libbar-[..].rlib`, expected [..] but got [..]")
+        .with_stderr_contains("[RUNNING] `rustc --crate-name bar [..]")
+        .run();
+}
+
+#[cargo_test]
+fn remote_cache_skips_path_deps() {
+    let server = BuildCacheServer::new();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = { path = "bar" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "1.0.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+    p.cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("CARGO_BUILD_CACHE_URL", server.url())
+        .run();
+    assert!(server.take_requests().is_empty());
+    assert!(server.entries().is_empty());
+}
+
+#[cargo_test]
+fn cache_url_requires_flag() {
+    let p = project().file("src/lib.rs", "").build();
+    p.cargo("build")
+        .env("CARGO_BUILD_CACHE_URL", "http://127.0.0.1:1/cache")
+        .with_stderr_data(str![[r#"
+[WARNING] ignoring 'cache-url' config, pass `-Zbuild-cache` to enable it
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
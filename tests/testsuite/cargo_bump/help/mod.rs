@@ -0,0 +1,14 @@
+use crate::prelude::*;
+use cargo_test_support::file;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn case() {
+    snapbox::cmd::Command::cargo_ui()
+        .arg("bump")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(str![""]);
+}
@@ -0,0 +1,343 @@
+//! Tests for the `cargo bump` command.
+
+use crate::prelude::*;
+use cargo_test_support::Project;
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+mod help;
+
+fn workspace_with_path_deps() -> Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [workspace]
+            members = ["a", "b", "c"]
+
+            [workspace.dependencies]
+            # Shared by the members
+            c = { path = "c", version = "0.1" }
+        "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+            [package]
+            name = "a"
+            version = "0.1.0"
+            edition = "2015"
+
+            [dependencies]
+            b = { path = "../b", version = "0.1.0" }  # the one to bump
+            c.workspace = true
+        "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+            [package]
+            name = "b"
+            version = "0.1.0"  # keep in sync
+            edition = "2015"
+
+            [dependencies]
+            c = { workspace = true }
+
+            [dev-dependencies]
+            renamed = { package = "c", path = "../c", version = "=0.1.0" }
+        "#,
+        )
+        .file("b/src/lib.rs", "")
+        .file(
+            "c/Cargo.toml",
+            r#"
+            [package]
+            name = "c"
+            version = "0.1.0"
+            edition = "2015"
+        "#,
+        )
+        .file("c/src/lib.rs", "")
+        .build()
+}
+
+#[cargo_test]
+fn gated() {
+    let p = workspace_with_path_deps();
+
+    p.cargo("bump minor -p b")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `cargo bump` command is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn bump_dependents() {
+    let p = workspace_with_path_deps();
+    p.cargo("generate-lockfile").run();
+
+    p.cargo("bump minor -p b -p c -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] b v0.1.0 -> v0.2.0
+[BUMPING] c v0.1.0 -> v0.2.0
+[UPGRADING] c 0.1 -> 0.2
+[UPGRADING] b 0.1.0 -> 0.2.0
+[UPGRADING] c =0.1.0 -> =0.2.0
+
+"#]])
+        .run();
+
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+            [workspace]
+            members = ["a", "b", "c"]
+
+            [workspace.dependencies]
+            # Shared by the members
+            c = { path = "c", version = "0.2" }
+        
+"#]],
+    );
+    assert_e2e().eq(
+        p.read_file("a/Cargo.toml"),
+        str![[r#"
+
+            [package]
+            name = "a"
+            version = "0.1.0"
+            edition = "2015"
+
+            [dependencies]
+            b = { path = "../b", version = "0.2.0" }  # the one to bump
+            c.workspace = true
+        
+"#]],
+    );
+    assert_e2e().eq(
+        p.read_file("b/Cargo.toml"),
+        str![[r#"
+
+            [package]
+            name = "b"
+            version = "0.2.0"  # keep in sync
+            edition = "2015"
+
+            [dependencies]
+            c = { workspace = true }
+
+            [dev-dependencies]
+            renamed = { package = "c", path = "../c", version = "=0.2.0" }
+        
+"#]],
+    );
+    assert_e2e().eq(
+        p.read_lockfile(),
+        str![[r##"
+# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "a"
+version = "0.1.0"
+dependencies = [
+ "b",
+ "c",
+]
+
+[[package]]
+name = "b"
+version = "0.2.0"
+dependencies = [
+ "c",
+]
+
+[[package]]
+name = "c"
+version = "0.2.0"
+
+"##]],
+    );
+}
+
+#[cargo_test]
+fn bump_inherited_version() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [workspace]
+            members = ["a", "b"]
+
+            [workspace.package]
+            version = "1.0.0"
+
+            [workspace.dependencies]
+            b = { path = "b", version = "1.0.0" }
+        "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+            [package]
+            name = "a"
+            version.workspace = true
+            edition = "2015"
+
+            [dependencies]
+            b.workspace = true
+        "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+            [package]
+            name = "b"
+            version.workspace = true
+            edition = "2015"
+        "#,
+        )
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("bump major -p b -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] a v1.0.0 -> v2.0.0
+[BUMPING] b v1.0.0 -> v2.0.0
+[UPGRADING] b 1.0.0 -> 2.0.0
+
+"#]])
+        .run();
+
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+            [workspace]
+            members = ["a", "b"]
+
+            [workspace.package]
+            version = "2.0.0"
+
+            [workspace.dependencies]
+            b = { path = "b", version = "2.0.0" }
+        
+"#]],
+    );
+    assert_e2e().eq(
+        p.read_file("b/Cargo.toml"),
+        str![[r#"
+
+            [package]
+            name = "b"
+            version.workspace = true
+            edition = "2015"
+        
+"#]],
+    );
+}
+
+#[cargo_test]
+fn bump_pre_release() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "1.0.0"
+            edition = "2015"
+        "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("bump pre -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] foo v1.0.0 -> v1.0.1-alpha.1
+
+"#]])
+        .run();
+
+    p.cargo("bump pre -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] foo v1.0.1-alpha.1 -> v1.0.1-alpha.2
+
+"#]])
+        .run();
+
+    p.cargo("bump patch -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] foo v1.0.1-alpha.2 -> v1.0.1
+
+"#]])
+        .run();
+
+    p.cargo("bump minor -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] foo v1.0.1 -> v1.1.0
+
+"#]])
+        .run();
+
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+            [package]
+            name = "foo"
+            version = "1.1.0"
+            edition = "2015"
+        
+"#]],
+    );
+}
+
+#[cargo_test]
+fn dry_run() {
+    let p = workspace_with_path_deps();
+
+    p.cargo("bump patch -p b --dry-run -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-bump"])
+        .with_stderr_data(str![[r#"
+[BUMPING] b v0.1.0 -> v0.1.1
+[UPGRADING] b 0.1.0 -> 0.1.1
+[WARNING] aborting bump due to dry run
+
+"#]])
+        .run();
+
+    assert_e2e().eq(
+        p.read_file("b/Cargo.toml"),
+        str![[r#"
+
+            [package]
+            name = "b"
+            version = "0.1.0"  # keep in sync
+            edition = "2015"
+
+            [dependencies]
+            c = { workspace = true }
+
+            [dev-dependencies]
+            renamed = { package = "c", path = "../c", version = "=0.1.0" }
+        
+"#]],
+    );
+    assert!(!p.root().join("Cargo.lock").exists());
+}
@@ -644,3 +644,267 @@ fn invalid_session_id_format() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn compare_sessions() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let base_log = paths::log_file(0);
+    let base_id = base_log.file_stem().unwrap().to_str().unwrap().to_owned();
+
+    p.change_file("src/lib.rs", "// touched");
+    p.change_file("src/main.rs", "fn main() {}");
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let new_log = paths::log_file(1);
+    let new_id = new_log.file_stem().unwrap().to_str().unwrap().to_owned();
+
+    p.cargo(&format!(
+        "report rebuilds --compare {base_id} {new_id} -Zbuild-analysis"
+    ))
+    .masquerade_as_nightly_cargo(&["build-analysis"])
+    .with_stderr_data(str![[r#"
+Base session: [..]
+New session:  [..]
+Status: 1 unit in both, 0 only in base, 1 only in new
+
+Only in new:
+  - foo@0.0.0 foo "bin" (check)
+
+Fingerprint changes:
+  - foo@0.0.0 (check): new -> dirty (file modified: src/lib.rs)
+...
+"#]])
+    .run();
+}
+
+#[cargo_test]
+fn compare_duration_regressions() {
+    let log_dir = paths::log_dir();
+    std::fs::create_dir_all(&log_dir).unwrap();
+
+    let session = |run_id: &str, foo_duration: f64, bar_duration: f64| {
+        format!(
+            r#"{{"reason":"build-started","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","command":[],"cwd":"/ws","host":"x86_64-unknown-linux-gnu","jobs":1,"num_cpus":1,"profile":"dev","rustc_version":"1.0.0","rustc_version_verbose":"rustc 1.0.0","target_dir":"/ws/target","workspace_root":"/ws"}}
+{{"reason":"unit-registered","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","package_id":"path+file:///ws#foo@0.0.0","target":{{"name":"foo","kind":"lib"}},"mode":"check","platform":"x86_64-unknown-linux-gnu","index":0}}
+{{"reason":"unit-registered","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","package_id":"path+file:///ws/bar#0.0.0","target":{{"name":"bar","kind":"lib"}},"mode":"check","platform":"x86_64-unknown-linux-gnu","index":1}}
+{{"reason":"unit-fingerprint","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":0,"status":"new"}}
+{{"reason":"unit-fingerprint","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":1,"status":"new"}}
+{{"reason":"unit-started","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":0,"elapsed":0.0}}
+{{"reason":"unit-finished","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":0,"elapsed":{foo_duration}}}
+{{"reason":"unit-started","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":1,"elapsed":0.0}}
+{{"reason":"unit-finished","run_id":"{run_id}","timestamp":"2026-01-01T00:00:00Z","index":1,"elapsed":{bar_duration}}}
+"#
+        )
+    };
+
+    let base_id = "20260101T000000000Z-0000000000000000";
+    let new_id = "20260102T000000000Z-0000000000000000";
+    std::fs::write(
+        log_dir.join(format!("{base_id}.jsonl")),
+        session(base_id, 1.0, 3.0),
+    )
+    .unwrap();
+    std::fs::write(
+        log_dir.join(format!("{new_id}.jsonl")),
+        session(new_id, 2.5, 2.0),
+    )
+    .unwrap();
+
+    cargo_process(&format!(
+        "report rebuilds --compare {base_id} {new_id} -Zbuild-analysis"
+    ))
+    .masquerade_as_nightly_cargo(&["build-analysis"])
+    .with_stderr_data(str![[r#"
+Base session: 20260101T000000000Z-0000000000000000
+New session:  20260102T000000000Z-0000000000000000
+Status: 2 units in both, 0 only in base, 0 only in new
+
+Duration regressions:
+  - foo@0.0.0 (check): 1.00s -> 2.50s (+1.50s)
+
+"#]])
+    .run();
+}
+
+#[cargo_test]
+fn compare_session_not_found() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let base_log = paths::log_file(0);
+    let base_id = base_log.file_stem().unwrap().to_str().unwrap().to_owned();
+
+    p.cargo(&format!(
+        "report rebuilds --compare {base_id} 20260101T000000000Z-0000000000000000 -Zbuild-analysis"
+    ))
+    .masquerade_as_nightly_cargo(&["build-analysis"])
+    .with_status(101)
+    .with_stderr_data(str![[r#"
+[ERROR] session `20260101T000000000Z-0000000000000000` not found for workspace at `[ROOT]/foo`
+  |
+  = [NOTE] run `cargo report sessions` to list available sessions
+
+"#]])
+    .run();
+}
+
+#[cargo_test]
+fn json_message_format() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.change_file("src/lib.rs", "// touched");
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.cargo("report rebuilds --message-format=json -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "affected_units": [],
+    "cause": {
+      "dirty_reason": "fs-status-outdated",
+      "fs_status": "stale-item",
+      "reference": "[ROOT]/foo/target/debug/.fingerprint/foo-[HASH]/dep-lib-foo",
+      "reference_mtime": "{...}",
+      "stale": "[ROOT]/foo/src/lib.rs",
+      "stale_item": "changed-file",
+      "stale_mtime": "{...}"
+    },
+    "mode": "check",
+    "package_id": "path+[ROOTURL]/foo#0.0.0",
+    "reason": "report-root-rebuild",
+    "session_id": "[..]T[..]Z-[..]",
+    "target": {
+      "kind": "lib",
+      "name": "foo"
+    }
+  },
+  {
+    "cached_units": 0,
+    "new_units": 0,
+    "reason": "report-rebuilds-finished",
+    "rebuilt_units": 1,
+    "session_id": "[..]T[..]Z-[..]"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data("")
+        .run();
+}
+
+#[cargo_test]
+fn compare_json_message_format() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let base_log = paths::log_file(0);
+    let base_id = base_log.file_stem().unwrap().to_str().unwrap().to_owned();
+
+    p.change_file("src/main.rs", "fn main() {}");
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let new_log = paths::log_file(1);
+    let new_id = new_log.file_stem().unwrap().to_str().unwrap().to_owned();
+
+    p.cargo(&format!(
+        "report rebuilds --compare {base_id} {new_id} --message-format=json -Zbuild-analysis"
+    ))
+    .masquerade_as_nightly_cargo(&["build-analysis"])
+    .with_stdout_data(
+        str![[r#"
+[
+  {
+    "base": {
+      "cause": null,
+      "duration": "{...}",
+      "status": "new"
+    },
+    "base_session_id": "[..]T[..]Z-[..]",
+    "mode": "check",
+    "new": {
+      "cause": null,
+      "duration": null,
+      "status": "fresh"
+    },
+    "new_session_id": "[..]T[..]Z-[..]",
+    "package_id": "path+[ROOTURL]/foo#0.0.0",
+    "platform": "[HOST_TARGET]",
+    "reason": "report-unit-comparison",
+    "target": {
+      "kind": "lib",
+      "name": "foo"
+    }
+  },
+  {
+    "base": null,
+    "base_session_id": "[..]T[..]Z-[..]",
+    "mode": "check",
+    "new": {
+      "cause": null,
+      "duration": "{...}",
+      "status": "new"
+    },
+    "new_session_id": "[..]T[..]Z-[..]",
+    "package_id": "path+[ROOTURL]/foo#0.0.0",
+    "platform": "[HOST_TARGET]",
+    "reason": "report-unit-comparison",
+    "target": {
+      "kind": "bin",
+      "name": "foo"
+    }
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    )
+    .with_stderr_data("")
+    .run();
+}
@@ -242,3 +242,34 @@ Session IDs for `[ROOT]/bar` (most recent first):
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn json_message_format() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.cargo("report sessions --message-format=json -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "id": "[..]T[..]Z-[..]",
+    "reason": "report-session",
+    "timestamp": "[..]T[..]Z"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data("")
+        .run();
+}
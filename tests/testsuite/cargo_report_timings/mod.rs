@@ -512,3 +512,195 @@ fn invalid_session_id_format() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn with_last_sessions() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    // All units are fresh, so this session is left out of the trends.
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.change_file("src/lib.rs", "pub fn foo() {}");
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.change_file("src/lib.rs", "pub fn bar() {}");
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    let _ = paths::log_file(3);
+
+    p.cargo("report timings --last 3 -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+      Timing report saved to [ROOT]/foo/target/cargo-timings/cargo-timing-[..]T[..]Z-[..].html
+
+"#]])
+        .run();
+
+    let timing_files: Vec<_> = p.glob("**/cargo-timing-*.html").collect();
+    assert_eq!(timing_files.len(), 1);
+    let html = std::fs::read_to_string(timing_files[0].as_ref().unwrap()).unwrap();
+
+    // The fresh session doesn't count towards `--last`.
+    assert!(html.contains("<h2>Trends across 3 sessions</h2>"));
+    assert!(html.contains("<td>foo v0.0.0 (check)</td>\n<td>3</td>"));
+}
+
+#[cargo_test]
+fn critical_path() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            edition = "2021"
+
+            [dependencies]
+            bar = { path = "bar" }
+            "#,
+        )
+        .file("src/main.rs", "fn main() { bar::bar() }")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.0"))
+        .file("bar/src/lib.rs", "pub fn bar() {}")
+        .build();
+
+    p.cargo("build -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.cargo("report timings -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+      Timing report saved to [ROOT]/foo/target/cargo-timings/cargo-timing-[..]T[..]Z-[..].html
+
+"#]])
+        .run();
+
+    let timing_files: Vec<_> = p.glob("**/cargo-timing-*.html").collect();
+    let html = std::fs::read_to_string(timing_files[0].as_ref().unwrap()).unwrap();
+
+    // The binary can only start once `bar` fully finished,
+    // so both are on the critical path.
+    assert!(
+        html.contains(
+            r#"<td>Critical path:</td><td>bar v0.0.0 &rarr;<br>foo v0.0.0 foo "bin"</td>"#
+        )
+    );
+    assert!(!html.contains(r#""critical": false"#));
+    assert!(html.contains(r#""slack": 0.0"#));
+}
+
+#[cargo_test]
+fn json_message_format() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.cargo("report timings --message-format=json -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "critical": true,
+    "duration": "{...}",
+    "mode": "check",
+    "package_id": "path+[ROOTURL]/foo#0.0.0",
+    "reason": "report-unit-timing",
+    "rmeta_time": "{...}",
+    "session_id": "[..]T[..]Z-[..]",
+    "slack": 0.0,
+    "start": "{...}",
+    "target": {
+      "kind": "lib",
+      "name": "foo"
+    }
+  },
+  {
+    "dirty_units": 1,
+    "fresh_units": 0,
+    "reason": "report-timings-finished",
+    "session_id": "[..]T[..]Z-[..]",
+    "total_time": "{...}"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data("")
+        .run();
+
+    assert_eq!(p.glob("**/cargo-timing-*.html").count(), 0);
+}
+
+#[cargo_test]
+fn chrome_trace_format() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.0.0"))
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zbuild-analysis")
+        .env("CARGO_BUILD_ANALYSIS_ENABLED", "true")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .run();
+
+    p.cargo("report timings --message-format=chrome-trace -Zbuild-analysis")
+        .masquerade_as_nightly_cargo(&["build-analysis"])
+        .with_stderr_data(str![[r#"
+      Timing report saved to [ROOT]/foo/target/cargo-timings/cargo-timing-[..]T[..]Z-[..].json
+
+"#]])
+        .run();
+
+    let trace_files: Vec<_> = p.glob("**/cargo-timing-*.json").collect();
+    assert_eq!(trace_files.len(), 1);
+    let trace = std::fs::read_to_string(trace_files[0].as_ref().unwrap()).unwrap();
+    let trace: serde_json::Value = serde_json::from_str(&trace).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+
+    let names: Vec<_> = events
+        .iter()
+        .filter(|e| e["ph"] == "X")
+        .map(|e| (e["name"].as_str().unwrap(), e["tid"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("resolution", 0),
+            ("unit graph", 0),
+            ("foo v0.0.0 (check)", 1),
+            ("frontend", 1),
+            ("codegen", 1),
+        ]
+    );
+    assert!(events.iter().any(|e| e["ph"] == "M"
+        && e["name"] == "thread_name"
+        && e["tid"] == 1
+        && e["args"]["name"] == "job slot 1"));
+}
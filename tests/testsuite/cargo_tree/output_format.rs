@@ -0,0 +1,212 @@
+//! Tests for the `cargo tree --output-format` option.
+
+use crate::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{Project, project, str};
+
+fn make_project() -> Project {
+    Package::new("c", "1.0.0").publish();
+    Package::new("b", "1.0.0").dep("c", "1.0").publish();
+    Package::new("bdep", "1.0.0").publish();
+    Package::new("devdep", "1.0.0").dep("c", "1.0").publish();
+    Package::new("a", "1.0.0")
+        .dep("b", "1.0")
+        .feature("default", &["f1"])
+        .feature("f1", &[])
+        .publish();
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                a = "1.0"
+
+                [build-dependencies]
+                bdep = "1.0"
+
+                [dev-dependencies]
+                devdep = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .build()
+}
+
+#[cargo_test]
+fn dot() {
+    let p = make_project();
+    p.cargo("tree -Zunstable-options --output-format dot")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stdout_data(str![[r#"
+digraph {
+    n0 [label="foo v0.1.0 ([ROOT]/foo)"]
+    n1 [label="a v1.0.0"]
+    n2 [label="bdep v1.0.0"]
+    n3 [label="devdep v1.0.0"]
+    n4 [label="b v1.0.0"]
+    n5 [label="c v1.0.0"]
+    n0 -> n1
+    n0 -> n2 [label="build"]
+    n0 -> n3 [label="dev"]
+    n1 -> n4
+    n3 -> n5
+    n4 -> n5
+}
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn mermaid_with_features() {
+    let p = make_project();
+    p.cargo("tree -Zunstable-options --output-format mermaid -e features,normal")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stdout_data(str![[r#"
+flowchart TD
+    n0["foo v0.1.0 ([ROOT]/foo)"]
+    n1(["a feature #quot;default#quot;"])
+    n2["a v1.0.0"]
+    n3(["a feature #quot;f1#quot;"])
+    n4(["b feature #quot;default#quot;"])
+    n5["b v1.0.0"]
+    n6(["c feature #quot;default#quot;"])
+    n7["c v1.0.0"]
+    n0 --> n1
+    n1 -.-> n2
+    n1 -.-> n3
+    n2 --> n4
+    n3 -.-> n2
+    n4 -.-> n5
+    n5 --> n6
+    n6 -.-> n7
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn json() {
+    let p = make_project();
+    p.cargo("tree -Zunstable-options --output-format json -e normal --format {p}:{l}")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stdout_data(
+            str![[r#"
+{
+  "edges": [
+    {
+      "from": 0,
+      "kind": "normal",
+      "public": false,
+      "to": 1
+    },
+    {
+      "from": 1,
+      "kind": "normal",
+      "public": false,
+      "to": 2
+    },
+    {
+      "from": 2,
+      "kind": "normal",
+      "public": false,
+      "to": 3
+    }
+  ],
+  "nodes": [
+    {
+      "features": [],
+      "id": 0,
+      "kind": "package",
+      "label": "foo v0.1.0 ([ROOT]/foo):",
+      "package_id": "path+[ROOTURL]/foo#0.1.0"
+    },
+    {
+      "features": [
+        "default",
+        "f1"
+      ],
+      "id": 1,
+      "kind": "package",
+      "label": "a v1.0.0:",
+      "package_id": "registry+https://github.com/rust-lang/crates.io-index#a@1.0.0"
+    },
+    {
+      "features": [],
+      "id": 2,
+      "kind": "package",
+      "label": "b v1.0.0:",
+      "package_id": "registry+https://github.com/rust-lang/crates.io-index#b@1.0.0"
+    },
+    {
+      "features": [],
+      "id": 3,
+      "kind": "package",
+      "label": "c v1.0.0:",
+      "package_id": "registry+https://github.com/rust-lang/crates.io-index#c@1.0.0"
+    }
+  ],
+  "roots": [
+    0
+  ]
+}
+"#]]
+            .is_json(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn honors_invert_prune_and_depth() {
+    let p = make_project();
+    p.cargo("tree -Zunstable-options --output-format dot -i c")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stdout_data(str![[r#"
+digraph {
+    n0 [label="c v1.0.0"]
+    n1 [label="b v1.0.0"]
+    n2 [label="devdep v1.0.0"]
+    n3 [label="a v1.0.0"]
+    n4 [label="foo v0.1.0 ([ROOT]/foo)"]
+    n0 -> n1
+    n0 -> n2
+    n1 -> n3
+    n2 -> n4 [label="dev"]
+    n3 -> n4
+}
+
+"#]])
+        .run();
+
+    p.cargo("tree -Zunstable-options --output-format dot --prune devdep --depth 2 -e no-build")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stdout_data(str![[r#"
+digraph {
+    n0 [label="foo v0.1.0 ([ROOT]/foo)"]
+    n1 [label="a v1.0.0"]
+    n2 [label="b v1.0.0"]
+    n0 -> n1
+    n1 -> n2
+}
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn requires_unstable_options() {
+    let p = make_project();
+    p.cargo("tree --output-format dot")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `--output-format` requires `-Zunstable-options`
+
+"#]])
+        .run();
+}
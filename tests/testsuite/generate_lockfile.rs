@@ -447,3 +447,212 @@ required by package `foo v0.0.0 ([ROOT]/foo)`
 "#]])
         .run();
 }
+
+fn merge_project() -> cargo_test_support::Project {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.1.0").publish();
+    Package::new("baz", "1.0.0").publish();
+    Package::new("qux", "0.1.0").dep("bar", "1.0").publish();
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                edition = "2021"
+
+                [dependencies]
+                bar = "1.0"
+                baz = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+/// Writes a lockfile conflicting between a branch adding `qux` and one
+/// upgrading `bar`, returning our side.
+fn write_conflicted_lockfile(p: &cargo_test_support::Project, diff3: bool) -> String {
+    p.cargo("update bar --precise 1.0.0").run();
+    let base = p.read_lockfile();
+    p.cargo("update bar").run();
+    let theirs = p.read_lockfile();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            edition = "2021"
+
+            [dependencies]
+            bar = "1.0"
+            baz = "1.0"
+            qux = "0.1"
+        "#,
+    );
+    p.change_file("Cargo.lock", &base);
+    p.cargo("check").run();
+    let ours = p.read_lockfile();
+
+    let base = if diff3 {
+        format!("||||||| base\n{base}")
+    } else {
+        String::new()
+    };
+    p.change_file(
+        "Cargo.lock",
+        &format!("<<<<<<< HEAD\n{ours}{base}=======\n{theirs}>>>>>>> upgrade-bar\n"),
+    );
+    ours
+}
+
+#[cargo_test]
+fn merge_requires_unstable_options() {
+    let p = merge_project();
+    p.cargo("generate-lockfile --merge")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--merge` flag is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn merge_conflicts() {
+    let p = merge_project();
+    write_conflicted_lockfile(&p, false);
+    // Versions locked by either side are kept over newer ones.
+    Package::new("baz", "1.0.1").publish();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse lock file at: [ROOT]/foo/Cargo.lock
+
+Caused by:
+  TOML parse error at line 1, column 9
+    |
+  1 | <<<<<<< HEAD
+    |         ^
+  key with no value, expected `=`
+
+"#]])
+        .run();
+
+    p.cargo("generate-lockfile --merge -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["merge"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 1 package to latest compatible version
+[UPDATING] bar v1.0.0 -> v1.1.0
+
+"#]])
+        .run();
+    let lock = p.read_lockfile();
+    assert_e2e().eq(
+        &lock,
+        str![[r##"
+# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "bar"
+version = "1.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "2216902cacb5611f65e00da63d917a6b9f4dbb10ddc39ee24c853322ab44b041"
+
+[[package]]
+name = "baz"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "8a1fd96ffb19bc833952a11a7ca984459e1ec6d84bbbfbff19a8a97575739a3e"
+
+[[package]]
+name = "foo"
+version = "0.0.0"
+dependencies = [
+ "bar",
+ "baz",
+ "qux",
+]
+
+[[package]]
+name = "qux"
+version = "0.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "cf5fa78c20825aaa78a0e0650ac5440bf035c1333d72917252b6060a17d73d17"
+dependencies = [
+ "bar",
+]
+
+"##]],
+    );
+}
+
+#[cargo_test]
+fn merge_diff3_conflicts() {
+    let p = merge_project();
+    write_conflicted_lockfile(&p, true);
+
+    p.cargo("generate-lockfile --merge -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["merge"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 1 package to latest compatible version
+[UPDATING] bar v1.0.0 -> v1.1.0
+
+"#]])
+        .run();
+    let lock = p.read_lockfile();
+    assert!(lock.contains("name = \"bar\"\nversion = \"1.1.0\""));
+    assert!(lock.contains("name = \"qux\"\nversion = \"0.1.0\""));
+}
+
+#[cargo_test]
+fn merge_without_conflicts() {
+    let p = merge_project();
+    p.cargo("update bar --precise 1.0.0").run();
+    let lock = p.read_lockfile();
+
+    p.cargo("generate-lockfile --merge -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["merge"])
+        .with_stderr_data(str![[r#"
+"#]])
+        .run();
+    assert_eq!(lock, p.read_lockfile());
+}
+
+#[cargo_test]
+fn merge_unterminated_conflict() {
+    let p = merge_project();
+    let ours = write_conflicted_lockfile(&p, false);
+    p.change_file("Cargo.lock", &format!("<<<<<<< HEAD\n{ours}"));
+
+    p.cargo("generate-lockfile --merge -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["merge"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse lock file at: [ROOT]/foo/Cargo.lock
+
+Caused by:
+  unterminated merge conflict
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn merge_without_lockfile() {
+    let p = merge_project();
+
+    p.cargo("generate-lockfile --merge -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["merge"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] cannot merge a lockfile that does not exist
+[HELP] run `cargo generate-lockfile` to create one
+
+"#]])
+        .run();
+}
@@ -1853,6 +1853,61 @@ fn clean_max_git_size_deletes_co_from_db() {
         .run();
 }
 
+#[cargo_test]
+fn clean_max_build_cache_age_and_size() {
+    // --max-build-cache-age and --max-build-cache-size flags
+    let p = basic_foo_bar_project();
+    p.cargo("check -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("__CARGO_TEST_LAST_USE_NOW", days_ago_unix(4))
+        .run();
+    p.cargo("build -Zbuild-cache")
+        .masquerade_as_nightly_cargo(&["build-cache"])
+        .env("__CARGO_TEST_LAST_USE_NOW", days_ago_unix(2))
+        .run();
+    assert_eq!(get_names(".cargo/build-cache/*").len(), 2);
+
+    // Delete the entry from `check`.
+    p.cargo("clean gc -v -Zgc")
+        .arg("--max-build-cache-age=3 days")
+        .masquerade_as_nightly_cargo(&["gc"])
+        .with_stderr_data(str![[r#"
+[REMOVING] [ROOT]/home/.cargo/build-cache/[..]
+[REMOVED] [FILE_NUM] files, [FILE_SIZE]B total
+
+"#]])
+        .run();
+    assert_eq!(get_names(".cargo/build-cache/*").len(), 1);
+
+    // Entries missing from the database are tracked again before cleaning.
+    let gctx = GlobalContextBuilder::new().build();
+    let _lock = gctx
+        .acquire_package_cache_lock(CacheLockMode::MutateExclusive)
+        .unwrap();
+    let tracker = GlobalCacheTracker::new(&gctx).unwrap();
+    assert_eq!(tracker.build_cache_all().unwrap().len(), 1);
+    drop(tracker);
+    drop(_lock);
+    let untracked = paths::home().join(".cargo/build-cache/untracked");
+    untracked.mkdir_p();
+    std::fs::write(untracked.join("data"), "x".repeat(100)).unwrap();
+
+    p.cargo("clean gc -v -Zgc")
+        .arg("--max-build-cache-size=0")
+        .masquerade_as_nightly_cargo(&["gc"])
+        .with_stderr_data(
+            str![[r#"
+[REMOVING] [ROOT]/home/.cargo/build-cache/[..]
+[REMOVING] [ROOT]/home/.cargo/build-cache/untracked
+[REMOVED] [FILE_NUM] files, [FILE_SIZE]B total
+
+"#]]
+            .unordered(),
+        )
+        .run();
+    assert!(get_names(".cargo/build-cache/*").is_empty());
+}
+
 #[cargo_test]
 fn handles_missing_index() {
     // Checks behavior when index is missing.
@@ -0,0 +1,207 @@
+use crate::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn manifest_keys() {
+    Package::new("bar", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+rust-version = "1.56"
+resolver = "2"
+
+[features]
+default = ["dep:bar"]
+
+[dependencies]
+bar = { version = "1.0", optional = true }
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+incompatible_rust_version = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] incompatible with `package.rust-version`
+  --> Cargo.toml:10:12
+   |
+10 | default = ["dep:bar"]
+   |            ^^^^^^^^^ requires Rust 1.60.0
+   |
+   = [NOTE] `package.rust-version` is 1.56
+   = [NOTE] `cargo::incompatible_rust_version` is set to `warn` in `[lints]`
+[HELP] consider raising `package.rust-version` to 1.60.0
+[WARNING] incompatible with `package.rust-version`
+  --> Cargo.toml:15:2
+   |
+15 | [lints.cargo]
+   |  ^^^^^ requires Rust 1.74.0
+   |
+   = [NOTE] `package.rust-version` is 1.56
+[HELP] consider raising `package.rust-version` to 1.74.0
+[WARNING] `foo` (manifest) generated 2 warnings
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v1.0.0 (registry `dummy-registry`)
+[CHECKING] bar v1.0.0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_keys() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo"]
+resolver = "2"
+
+[workspace.package]
+rust-version = "1.60"
+
+[workspace.lints.cargo]
+default = { level = "allow", priority = -1 }
+incompatible_rust_version = "warn"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+rust-version.workspace = true
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] incompatible with `package.rust-version`
+ --> Cargo.toml:6:12
+  |
+6 | [workspace.package]
+  |            ^^^^^^^ requires Rust 1.64.0
+  |
+  = [NOTE] the lowest `package.rust-version` in the workspace is 1.60
+  = [NOTE] `cargo::incompatible_rust_version` is set to `warn` in `[lints]`
+[HELP] consider raising `package.rust-version` to 1.64.0
+[WARNING] incompatible with `package.rust-version`
+ --> Cargo.toml:9:12
+  |
+9 | [workspace.lints.cargo]
+  |            ^^^^^ requires Rust 1.74.0
+  |
+  = [NOTE] the lowest `package.rust-version` in the workspace is 1.60
+[HELP] consider raising `package.rust-version` to 1.74.0
+[WARNING] workspace (manifest) generated 2 warnings
+[WARNING] incompatible with `package.rust-version`
+ --> foo/Cargo.toml:6:1
+  |
+6 | rust-version.workspace = true
+  | ^^^^^^^^^^^^ requires Rust 1.64.0
+  |
+  = [NOTE] `package.rust-version` is 1.60
+  = [NOTE] `cargo::incompatible_rust_version` is set to `warn` in `[lints]`
+[HELP] consider raising `package.rust-version` to 1.64.0
+[WARNING] incompatible with `package.rust-version`
+ --> foo/Cargo.toml:8:2
+  |
+8 | [lints]
+  |  ^^^^^ requires Rust 1.74.0
+  |
+  = [NOTE] `package.rust-version` is 1.60
+[HELP] consider raising `package.rust-version` to 1.74.0
+[WARNING] `foo` (manifest) generated 2 warnings
+[CHECKING] foo v0.0.1 ([ROOT]/foo/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn dependency_rust_version() {
+    Package::new("new", "1.0.0").rust_version("1.80").publish();
+    Package::new("old", "1.0.0").rust_version("1.60").publish();
+    Package::new("dev", "1.0.0").rust_version("1.80").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+rust-version = "1.75"
+
+[dependencies]
+new = "1.0"
+old = "1.0"
+
+[dev-dependencies]
+dev = "1.0"
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+incompatible_rust_version = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] new v1.0.0 (registry `dummy-registry`)
+[DOWNLOADED] old v1.0.0 (registry `dummy-registry`)
+[WARNING] incompatible with `package.rust-version`
+ --> Cargo.toml:9:1
+  |
+9 | new = "1.0"
+  | ^^^ `new v1.0.0` requires Rust 1.80
+  |
+  = [NOTE] `package.rust-version` is 1.75
+  = [NOTE] `cargo::incompatible_rust_version` is set to `warn` in `[lints]`
+[HELP] consider raising `package.rust-version` to 1.80 or selecting an older version of `new`
+[WARNING] `foo` (manifest) generated 1 warning
+[CHECKING] new v1.0.0
+[CHECKING] old v1.0.0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
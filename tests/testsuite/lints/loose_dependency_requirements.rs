@@ -0,0 +1,285 @@
+use crate::prelude::*;
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn lower_than_locked() {
+    Package::new("dep", "1.0.0").publish();
+    Package::new("dep", "1.0.190").publish();
+    Package::new("exact", "1.1.0").publish();
+    Package::new("zero", "0.1.0").publish();
+    Package::new("zero", "0.3.2").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+dep = "1.0"
+exact = "=1.1.0"
+zero = { version = "0", default-features = false }
+
+[lints.cargo]
+loose_dependency_requirements = "warn"
+"#,
+        )
+        .file(
+            "src/lib.rs",
+            "extern crate dep; extern crate exact; extern crate zero;",
+        )
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[UPDATING] `dummy-registry` index
+[WARNING] dependency requirement is older than the locked version
+ --> Cargo.toml:8:1
+  |
+8 | dep = "1.0"
+  | ^^^   ----- minimal versions select `1.0.0`
+  |
+  = [NOTE] `dep` is locked to `1.0.190`
+  = [NOTE] `cargo::loose_dependency_requirements` is set to `warn` in `[lints]`
+[HELP] to require the locked version, raise the lower bound of the requirement
+  |
+8 | dep = "1.0.190"
+  |           ++++
+[WARNING] dependency requirement is older than the locked version
+  --> Cargo.toml:10:1
+   |
+10 | zero = { version = "0", default-features = false }
+   | ^^^^               --- minimal versions select `0.1.0`
+   |
+   = [NOTE] `zero` is locked to `0.3.2`
+[WARNING] `foo` (manifest) generated 2 warnings
+[DOWNLOADING] crates ...
+[DOWNLOADED] zero v0.3.2 (registry `dummy-registry`)
+[DOWNLOADED] exact v1.1.0 (registry `dummy-registry`)
+[DOWNLOADED] dep v1.0.190 (registry `dummy-registry`)
+[CHECKING] exact v1.1.0
+[CHECKING] dep v1.0.190
+[CHECKING] zero v0.3.2
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn raised_by_other_dependency() {
+    Package::new("dep", "1.0.0").publish();
+    Package::new("dep", "1.0.190").publish();
+    Package::new("other", "1.0.0")
+        .dep("dep", "1.0.190")
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+dep = "1.0"
+other = "1.0"
+
+[lints.cargo]
+loose_dependency_requirements = "warn"
+"#,
+        )
+        .file("src/lib.rs", "extern crate dep; extern crate other;")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    // `other` requires the locked version, so minimal versions select it as well
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] dep v1.0.190 (registry `dummy-registry`)
+[DOWNLOADED] other v1.0.0 (registry `dummy-registry`)
+[UPDATING] `dummy-registry` index
+[CHECKING] dep v1.0.190
+[CHECKING] other v1.0.0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn without_lockfile() {
+    Package::new("dep", "1.0.0").publish();
+    Package::new("dep", "1.0.190").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+dep = "1.0"
+
+[lints.cargo]
+loose_dependency_requirements = "warn"
+"#,
+        )
+        .file("src/lib.rs", "extern crate dep;")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[DOWNLOADING] crates ...
+[DOWNLOADED] dep v1.0.190 (registry `dummy-registry`)
+[WARNING] dependency requirement is older than the locked version
+ --> Cargo.toml:8:1
+  |
+8 | dep = "1.0"
+  | ^^^   ----- minimal versions select `1.0.0`
+  |
+  = [NOTE] `dep` is locked to `1.0.190`
+  = [NOTE] `cargo::loose_dependency_requirements` is set to `warn` in `[lints]`
+[HELP] to require the locked version, raise the lower bound of the requirement
+  |
+8 | dep = "1.0.190"
+  |           ++++
+[WARNING] `foo` (manifest) generated 1 warning
+[CHECKING] dep v1.0.190
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn inherited() {
+    Package::new("dep", "1.0.0").publish();
+    Package::new("dep", "1.0.190").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
+
+[workspace.dependencies]
+dep = { version = "1.0", default-features = false }  # shared
+
+[workspace.lints.cargo]
+loose_dependency_requirements = "warn"
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+dep.workspace = true
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("bar/src/lib.rs", "extern crate dep;")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] dep v1.0.190 (registry `dummy-registry`)
+[UPDATING] `dummy-registry` index
+[WARNING] dependency requirement is older than the locked version
+ --> bar/Cargo.toml:8:1
+  |
+8 | dep.workspace = true
+  | ^^^
+  |
+  = [NOTE] `dep` is locked to `1.0.190`
+  = [NOTE] with minimal versions, `dep` resolves to `1.0.0`
+  = [NOTE] `cargo::loose_dependency_requirements` is set to `warn` in `[lints]`
+[HELP] to require the locked version, raise the lower bound of the requirement
+ --> Cargo.toml:6:23
+  |
+6 | dep = { version = "1.0.190", default-features = false }  # shared
+  |                       ++++
+[WARNING] `bar` (manifest) generated 1 warning
+[CHECKING] dep v1.0.190
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // Raising the lower bound narrows the resolution of dependents, so it is not applied
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[WARNING] dependency requirement is older than the locked version
+ --> bar/Cargo.toml:8:1
+  |
+8 | dep.workspace = true
+  | ^^^
+  |
+  = [NOTE] `dep` is locked to `1.0.190`
+  = [NOTE] with minimal versions, `dep` resolves to `1.0.0`
+  = [NOTE] `cargo::loose_dependency_requirements` is set to `warn` in `[lints]`
+[HELP] to require the locked version, raise the lower bound of the requirement
+ --> Cargo.toml:6:23
+  |
+6 | dep = { version = "1.0.190", default-features = false }  # shared
+  |                       ++++
+[WARNING] `bar` (manifest) generated 1 warning
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[workspace]
+members = ["bar"]
+
+[workspace.dependencies]
+dep = { version = "1.0", default-features = false }  # shared
+
+[workspace.lints.cargo]
+loose_dependency_requirements = "warn"
+
+"#]],
+    );
+}
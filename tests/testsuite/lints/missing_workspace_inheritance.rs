@@ -0,0 +1,205 @@
+use crate::prelude::*;
+use cargo_test_support::Project;
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+fn workspace() -> Project {
+    Package::new("dep", "1.0.0").feature("std", &[]).publish();
+    Package::new("other", "1.0.0").publish();
+    Package::new("other", "1.1.0").publish();
+
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar", "baz"]
+resolver = "2"
+
+[workspace.package]
+edition = "2021"
+license = "MIT"
+repository = "https://github.com/rust-lang/cargo/"
+
+[workspace.dependencies]
+dep = "1.0"
+other = { version = "1.0", default-features = false }
+baz = { path = "baz" }
+
+[workspace.lints.cargo]
+default = { level = "allow", priority = -1 }
+missing_workspace_inheritance = "warn"
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2021"
+license = "MIT OR Apache-2.0"  # differs
+repository = "https://github.com/rust-lang/cargo/"
+
+[dependencies]
+dep = { version = "1.0", features = ["std"] }  # keep features
+other = { version = "1.1", default-features = false }
+baz = { path = "../baz" }
+
+[dev-dependencies]
+dep = "1.0"
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .file(
+            "baz/Cargo.toml",
+            r#"
+[package]
+name = "baz"
+version = "0.0.1"
+edition.workspace = true
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("baz/src/lib.rs", "")
+        .build()
+}
+
+#[cargo_test]
+fn duplicated() {
+    let p = workspace();
+
+    p.cargo("fetch -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[WARNING] value could be inherited from the workspace
+ --> bar/Cargo.toml:5:1
+  |
+5 | edition = "2021"
+  | ^^^^^^^^^^^^^^^^
+  |
+ ::: Cargo.toml:7:1
+  |
+7 | edition = "2021"
+  | ---------------- defined by the workspace
+  |
+  = [NOTE] `cargo::missing_workspace_inheritance` is set to `warn` in `[lints]`
+[HELP] to inherit `workspace.package.edition`, set `workspace = true`
+[WARNING] value could be inherited from the workspace
+ --> bar/Cargo.toml:7:1
+  |
+7 | repository = "https://github.com/rust-lang/cargo/"
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+ ::: Cargo.toml:9:1
+  |
+9 | repository = "https://github.com/rust-lang/cargo/"
+  | -------------------------------------------------- defined by the workspace
+  |
+[HELP] to inherit `workspace.package.repository`, set `workspace = true`
+[WARNING] value could be inherited from the workspace
+  --> bar/Cargo.toml:12:1
+   |
+12 | baz = { path = "../baz" }
+   | ^^^^^^^^^^^^^^^^^^^^^^^^^
+   |
+  ::: Cargo.toml:14:1
+   |
+14 | baz = { path = "baz" }
+   | ---------------------- defined by the workspace
+   |
+[HELP] to inherit `workspace.dependencies.baz`, set `workspace = true`
+[WARNING] value could be inherited from the workspace
+  --> bar/Cargo.toml:10:1
+   |
+10 | dep = { version = "1.0", features = ["std"] }  # keep features
+   | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+   |
+  ::: Cargo.toml:12:1
+   |
+12 | dep = "1.0"
+   | ----------- defined by the workspace
+   |
+[HELP] to inherit `workspace.dependencies.dep`, set `workspace = true`
+[WARNING] value could be inherited from the workspace
+  --> bar/Cargo.toml:15:1
+   |
+15 | dep = "1.0"
+   | ^^^^^^^^^^^
+   |
+  ::: Cargo.toml:12:1
+   |
+12 | dep = "1.0"
+   | ----------- defined by the workspace
+   |
+[HELP] to inherit `workspace.dependencies.dep`, set `workspace = true`
+[WARNING] `bar` (manifest) generated 5 warnings (run `cargo fix` to apply 5 suggestions)
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] other v1.1.0 (registry `dummy-registry`)
+[DOWNLOADED] dep v1.0.0 (registry `dummy-registry`)
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = workspace();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[FIXED] bar/Cargo.toml (5 fixes)
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] dep v1.0.0 (registry `dummy-registry`)
+[DOWNLOADED] other v1.1.0 (registry `dummy-registry`)
+[CHECKING] other v1.1.0
+[CHECKING] baz v0.0.1 ([ROOT]/foo/baz)
+[CHECKING] dep v1.0.0
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+    assert_e2e().eq(
+        p.read_file("bar/Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "bar"
+version = "0.0.1"
+edition.workspace = true
+license = "MIT OR Apache-2.0"  # differs
+repository.workspace = true
+
+[dependencies]
+dep = { workspace = true, features = ["std"] }  # keep features
+other = { version = "1.1", default-features = false }
+baz = { workspace = true }
+
+[dev-dependencies]
+dep.workspace = true
+
+[lints]
+workspace = true
+
+"#]],
+    );
+}
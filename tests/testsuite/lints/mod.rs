@@ -5,8 +5,11 @@ use cargo_test_support::str;
 
 mod blanket_hint_mostly_unused;
 mod error;
+mod incompatible_rust_version;
 mod inherited;
+mod loose_dependency_requirements;
 mod missing_lints_inheritance;
+mod missing_workspace_inheritance;
 mod non_kebab_case_bins;
 mod non_kebab_case_features;
 mod non_kebab_case_packages;
@@ -17,6 +20,7 @@ mod redundant_readme;
 mod text_direction_codepoint;
 mod unknown_lints;
 mod unused_dependencies;
+mod unused_features;
 mod unused_workspace_dependencies;
 mod unused_workspace_package_fields;
 mod warning;
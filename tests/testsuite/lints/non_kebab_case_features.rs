@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::project;
 use cargo_test_support::registry::Package;
 use cargo_test_support::str;
@@ -92,3 +93,97 @@ non_kebab_case_features = "warn"
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn fix_does_not_rename() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+foo_bar = []
+
+[lints.cargo]
+non_kebab_case_features = "warn"
+"#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+#[cfg(feature = "foo_bar")]
+pub fn gated() {}
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+foo = { path = "..", features = ["foo_bar"] }
+"#,
+        )
+        .file(
+            "bar/src/lib.rs",
+            r#"
+pub fn call() {
+    foo::gated();
+}
+"#,
+        )
+        .build();
+
+    p.cargo("fix --allow-no-vcs --workspace -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] features should have a kebab-case name
+  --> Cargo.toml:11:1
+   |
+11 | foo_bar = []
+   | ^^^^^^^
+   |
+   = [NOTE] `cargo::non_kebab_case_features` is set to `warn` in `[lints]`
+[HELP] to change the feature name to kebab case, convert the `features` key
+   |
+11 - foo_bar = []
+11 + foo-bar = []
+   |
+[WARNING] `foo` (manifest) generated 1 warning
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[workspace]
+members = ["bar"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+foo_bar = []
+
+[lints.cargo]
+non_kebab_case_features = "warn"
+
+"#]],
+    );
+}
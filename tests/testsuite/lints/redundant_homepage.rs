@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::project;
 use cargo_test_support::str;
 
@@ -37,7 +38,7 @@ redundant_homepage = "warn"
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `cargo` (manifest) generated 1 warning
+[WARNING] `cargo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 
 "#]])
         .run();
@@ -78,7 +79,7 @@ redundant_homepage = "warn"
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `cargo` (manifest) generated 1 warning
+[WARNING] `cargo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 
 "#]])
         .run();
@@ -123,8 +124,56 @@ redundant_homepage = "warn"
    |
    = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `cargo` (manifest) generated 1 warning
+[WARNING] `cargo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "cargo"
+version = "0.0.1"
+edition = "2015"
+repository = "https://github.com/rust-lang/cargo/"
+homepage = "https://github.com/rust-lang/cargo/"
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+redundant_homepage = "warn"
+"#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+[CHECKING] cargo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "cargo"
+version = "0.0.1"
+edition = "2015"
+repository = "https://github.com/rust-lang/cargo/"
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+redundant_homepage = "warn"
+
+"#]],
+    );
+}
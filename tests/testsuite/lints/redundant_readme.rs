@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::project;
 use cargo_test_support::str;
 
@@ -35,7 +36,7 @@ redundant_readme = "warn"
   |
   = [NOTE] `cargo::redundant_readme` is set to `warn` in `[lints]`
 [HELP] consider removing `package.readme`
-[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] `foo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 
 "#]])
         .run();
@@ -154,3 +155,50 @@ redundant_readme = "warn"
         .with_stderr_data(str![""])
         .run();
 }
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+readme = "README.md"  # the default
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+redundant_readme = "warn"
+"#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file("README.md", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[lints.cargo]
+default = { level = "allow", priority = -1 }
+redundant_readme = "warn"
+
+"#]],
+    );
+}
@@ -0,0 +1,348 @@
+use crate::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn never_enabled() {
+    Package::new("used-opt", "1.0.0")
+        .feature("std", &[])
+        .publish();
+    Package::new("unused-opt", "1.0.0")
+        .feature("std", &[])
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+default = ["std"]
+std = ["used-opt/std", "unused-opt?/std"]
+cli = []
+unused = ["dep:unused-opt"]
+
+[dependencies]
+bar = { path = "bar", features = ["enabled"] }
+used-opt = { version = "1.0", optional = true }
+unused-opt = { version = "1.0", optional = true }
+
+[[bin]]
+name = "foo-cli"
+path = "src/main.rs"
+required-features = ["cli"]
+
+[lints]
+workspace = true
+
+[workspace.lints.cargo]
+default = { level = "allow", priority = -1 }
+unused_features = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("src/main.rs", "fn main() {}")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+enabled = []
+never = []
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[WARNING] unused feature
+ --> bar/Cargo.toml:9:1
+  |
+9 | never = []
+  | ^^^^^
+  |
+  = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused feature
+[WARNING] `bar` (manifest) generated 1 warning
+[WARNING] unused feature
+  --> Cargo.toml:14:1
+   |
+14 | unused = ["dep:unused-opt"]
+   | ^^^^^^
+   |
+   = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused feature
+[WARNING] unused optional dependency
+  --> Cargo.toml:19:1
+   |
+19 | unused-opt = { version = "1.0", optional = true }
+   | ^^^^^^^^^^
+   |
+[HELP] consider removing the unused dependency
+[WARNING] `foo` (manifest) generated 2 warnings
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] used-opt v1.0.0 (registry `dummy-registry`)
+[DOWNLOADED] unused-opt v1.0.0 (registry `dummy-registry`)
+[CHECKING] used-opt v1.0.0
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn used_in_cfg() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+gated = []
+sse2 = []
+never = []
+nested = []
+renamed = []
+commented = []
+quoted = []
+orphaned = []
+
+[lints.cargo]
+unused_features = "warn"
+"#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+#[cfg(feature = "gated")]
+pub fn gated() {}
+
+#[cfg(target_feature = "sse2")]
+pub fn simd() {}
+
+// #[cfg(feature = "commented")]
+pub const QUOTED: &str = "cfg(feature = \"quoted\")";
+
+pub mod outer {
+    pub mod inner;
+}
+
+#[path = "other.rs"]
+mod renamed;
+"#,
+        )
+        .file(
+            "src/outer/inner.rs",
+            r#"pub fn nested() -> bool { cfg!(feature = "nested") }"#,
+        )
+        .file(
+            "src/other.rs",
+            r#"#![cfg_attr(feature = "renamed", allow(dead_code))]"#,
+        )
+        .file(
+            "src/orphaned.rs",
+            r#"#[cfg(feature = "orphaned")] fn f() {}"#,
+        )
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused feature
+  --> Cargo.toml:13:1
+   |
+13 | commented = []
+   | ^^^^^^^^^
+   |
+   = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused feature
+[WARNING] unused feature
+  --> Cargo.toml:10:1
+   |
+10 | never = []
+   | ^^^^^
+   |
+[HELP] consider removing the unused feature
+[WARNING] unused feature
+  --> Cargo.toml:15:1
+   |
+15 | orphaned = []
+   | ^^^^^^^^
+   |
+[HELP] consider removing the unused feature
+[WARNING] unused feature
+  --> Cargo.toml:14:1
+   |
+14 | quoted = []
+   | ^^^^^^
+   |
+[HELP] consider removing the unused feature
+[WARNING] unused feature
+ --> Cargo.toml:9:1
+  |
+9 | sse2 = []
+  | ^^^^
+  |
+[HELP] consider removing the unused feature
+[WARNING] `foo` (manifest) generated 5 warnings
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn weak_dependency_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo", "bar"]
+resolver = "2"
+
+[workspace.lints.cargo]
+unused_features = "warn"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+default = ["bar?/weak"]
+
+[dependencies]
+bar = { path = "../bar", optional = true }
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+weak = []
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[WARNING] unused optional dependency
+  --> foo/Cargo.toml:11:1
+   |
+11 | bar = { path = "../bar", optional = true }
+   | ^^^
+   |
+   = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused dependency
+[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] unused feature
+ --> bar/Cargo.toml:8:1
+  |
+8 | weak = []
+  | ^^^^
+  |
+  = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused feature
+[WARNING] `bar` (manifest) generated 1 warning
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[CHECKING] foo v0.0.1 ([ROOT]/foo/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn self_dependency() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+testing = []
+never = []
+
+[dev-dependencies]
+foo = { path = ".", features = ["testing"] }
+
+[lints.cargo]
+unused_features = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused feature
+ --> Cargo.toml:9:1
+  |
+9 | never = []
+  | ^^^^^
+  |
+  = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+[HELP] consider removing the unused feature
+[WARNING] `foo` (manifest) generated 1 warning
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
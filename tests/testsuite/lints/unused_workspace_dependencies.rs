@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::project;
 use cargo_test_support::registry::Package;
 use cargo_test_support::str;
@@ -94,7 +95,7 @@ workspace = true
    | ^^^^^^
    |
 [HELP] consider removing the unused workspace dependency
-[WARNING] workspace (manifest) generated 2 warnings
+[WARNING] workspace (manifest) generated 2 warnings (run `cargo fix` to apply 2 suggestions)
 [UPDATING] `dummy-registry` index
 [LOCKING] 6 packages to latest compatible versions
 [DOWNLOADING] crates ...
@@ -103,3 +104,75 @@ workspace = true
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn fix() {
+    Package::new("dep", "1.0.0").publish();
+    Package::new("unused", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
+
+[workspace.dependencies]
+dep = "1"
+# Not inherited by any member
+unused = "1"
+
+[workspace.lints.cargo]
+default = { level = "allow", priority = -1 }
+unused_workspace_dependencies = "warn"
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+dep.workspace = true
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("bar/src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[DOWNLOADING] crates ...
+[DOWNLOADED] dep v1.0.0 (registry `dummy-registry`)
+[CHECKING] dep v1.0.0
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[workspace]
+members = ["bar"]
+
+[workspace.dependencies]
+dep = "1"
+
+[workspace.lints.cargo]
+default = { level = "allow", priority = -1 }
+unused_workspace_dependencies = "warn"
+
+"#]],
+    );
+}
@@ -66,7 +66,7 @@ workspace = true
   | ^^^^^^^
   |
 [HELP] consider removing the unused field
-[WARNING] workspace (manifest) generated 2 warnings
+[WARNING] workspace (manifest) generated 2 warnings (run `cargo fix` to apply 2 suggestions)
 [WARNING] Cargo.toml: unused manifest key: workspace.package.unknown
 [WARNING] `foo` (manifest) generated 1 warning
 
@@ -3,6 +3,7 @@
 #![allow(clippy::print_stdout)]
 
 mod advanced_env;
+mod advisories;
 mod alt_registry;
 mod artifact_dep;
 mod artifact_dir;
@@ -12,6 +13,7 @@ mod bench;
 mod binary_name;
 mod build;
 mod build_analysis;
+mod build_cache;
 mod build_dir;
 mod build_dir_fine_grain_locking;
 mod build_dir_legacy;
@@ -26,6 +28,7 @@ mod cargo_add;
 mod cargo_alias_config;
 mod cargo_bench;
 mod cargo_build;
+mod cargo_bump;
 mod cargo_check;
 mod cargo_clean;
 mod cargo_command;
@@ -173,6 +176,7 @@ mod registry_overlay;
 mod rename_deps;
 mod replace;
 mod required_features;
+mod resolver_hold;
 mod run;
 mod rust_version;
 mod rustc;
@@ -1,6 +1,7 @@
 //! Tests for --message-format flag.
 
 use crate::prelude::*;
+use cargo_test_support::registry::Package;
 use cargo_test_support::{basic_lib_manifest, basic_manifest, project, str};
 
 #[cargo_test]
@@ -201,3 +202,154 @@ mod tests {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn resolve_error_rejected_versions() {
+    Package::new("bar", "1.0.0").yanked(true).publish();
+    Package::new("bar", "1.1.0")
+        .rust_version("1.9876.0")
+        .schema_version(3)
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check --message-format json")
+        .with_status(101)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "candidates": [
+      {
+        "reason": "yanked",
+        "version": "1.0.0"
+      },
+      {
+        "reason": "rust-version",
+        "rust_version": "1.9876.0",
+        "version": "1.1.0"
+      }
+    ],
+    "conflicts": [],
+    "locked_version": null,
+    "message": "failed to select a version for the requirement `bar = \"^1.0\"`\n  version 1.0.0 is yanked\n  version 1.1.0 requires cargo 1.9876.0\nlocation searched: `dummy-registry` index (which is replacing registry `crates-io`)\nrequired by package `foo v0.1.0 ([ROOT]/foo)`",
+    "package_name": "bar",
+    "reason": "resolve-error",
+    "required_by": [
+      {
+        "package_id": "path+[ROOTURL]/foo#0.1.0",
+        "requirement": null
+      }
+    ],
+    "requirement": "^1.0",
+    "source": "registry+https://github.com/rust-lang/crates.io-index"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for the requirement `bar = "^1.0"`
+  version 1.0.0 is yanked
+  version 1.1.0 requires cargo 1.9876.0
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.1.0 ([ROOT]/foo)`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn resolve_error_conflict() {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.0.1").publish();
+    Package::new("a", "1.0.0").dep("bar", "=1.0.0").publish();
+    Package::new("b", "1.0.0").dep("bar", "=1.0.1").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                a = "1.0"
+                b = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check --message-format json")
+        .with_status(101)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "candidates": [
+      {
+        "reason": "conflict",
+        "version": "1.0.1"
+      }
+    ],
+    "conflicts": [
+      {
+        "package_id": "registry+https://github.com/rust-lang/crates.io-index#bar@1.0.0",
+        "reason": "semver",
+        "required_by": [
+          {
+            "package_id": "registry+https://github.com/rust-lang/crates.io-index#bar@1.0.0",
+            "requirement": null
+          },
+          {
+            "package_id": "registry+https://github.com/rust-lang/crates.io-index#a@1.0.0",
+            "requirement": "=1.0.0"
+          },
+          {
+            "package_id": "path+[ROOTURL]/foo#0.1.0",
+            "requirement": "^1.0"
+          }
+        ]
+      }
+    ],
+    "locked_version": null,
+    "message": "failed to select a version for `bar`.\n    ... required by package `b v1.0.0`\n    ... which satisfies dependency `b = \"^1.0\"` of package `foo v0.1.0 ([ROOT]/foo)`\nversions that meet the requirements `=1.0.1` are: 1.0.1\n\nall possible versions conflict with previously selected packages\n\n  previously selected package `bar v1.0.0`\n    ... which satisfies dependency `bar = \"=1.0.0\"` of package `a v1.0.0`\n    ... which satisfies dependency `a = \"^1.0\"` of package `foo v0.1.0 ([ROOT]/foo)`\n\nfailed to select a version for `bar` which could resolve this conflict",
+    "package_name": "bar",
+    "reason": "resolve-error",
+    "required_by": [
+      {
+        "package_id": "registry+https://github.com/rust-lang/crates.io-index#b@1.0.0",
+        "requirement": null
+      },
+      {
+        "package_id": "path+[ROOTURL]/foo#0.1.0",
+        "requirement": "^1.0"
+      }
+    ],
+    "requirement": "=1.0.1",
+    "source": "registry+https://github.com/rust-lang/crates.io-index"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .run();
+}
@@ -4913,3 +4913,335 @@ fn workspace_circular_publish_dependency_with_non_cycle_package() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn workspace_resume_requires_unstable_options() {
+    let registry = RegistryBuilder::new().http_api().http_index().build();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish --resume")
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--resume` flag is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_resume() {
+    // Fail the upload of the last package of the first run.
+    let uploads = Arc::new(Mutex::new(0));
+    let registry = RegistryBuilder::new()
+        .http_api()
+        .http_index()
+        .add_responder("/api/v1/crates/new", move |req, server| {
+            let mut uploads = uploads.lock().unwrap();
+            *uploads += 1;
+            if *uploads == 3 {
+                Response {
+                    code: 500,
+                    headers: vec![],
+                    body: b"internal server error".to_vec(),
+                }
+            } else {
+                server.check_authorized_publish(req)
+            }
+        })
+        .build();
+    let p = workspace_with_local_deps_project();
+    let state = p.root().join("target/package/publish-state.json");
+
+    p.cargo("publish --no-verify -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["publish-resume"])
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[PACKAGING] level3 v0.0.1 ([ROOT]/foo/level3)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level2 v0.0.1 ([ROOT]/foo/level2)
+[UPDATING] crates.io index
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level1 v0.0.1 ([ROOT]/foo/level1)
+[UPDATING] crates.io index
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[UPLOADING] level3 v0.0.1 ([ROOT]/foo/level3)
+[UPLOADED] level3 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level3 v0.0.1 to be available at registry `crates-io`.
+      2 remaining crates to be published
+[PUBLISHED] level3 v0.0.1 at registry `crates-io`
+[UPLOADING] level2 v0.0.1 ([ROOT]/foo/level2)
+[UPLOADED] level2 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level2 v0.0.1 to be available at registry `crates-io`.
+      1 remaining crate to be published
+[PUBLISHED] level2 v0.0.1 at registry `crates-io`
+[UPLOADING] level1 v0.0.1 ([ROOT]/foo/level1)
+[NOTE] publishing was interrupted
+        published: level3 v0.0.1, level2 v0.0.1
+        not yet uploaded: level1 v0.0.1
+  |
+  = [HELP] to publish the remaining packages, run `cargo publish --resume -Zunstable-options`
+[ERROR] failed to publish level1 v0.0.1 to registry at http://127.0.0.1:[..]/
+
+Caused by:
+  failed to get a 200 OK response, got 500
+  headers:
+  	content-length: 21
+  	connection: close
+  body:
+  internal server error
+
+"#]])
+        .run();
+    assert!(state.exists());
+
+    p.cargo("publish --no-verify --resume -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["publish-resume"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[SKIPPING] level2 v0.0.1, already published to registry `crates-io`
+[SKIPPING] level3 v0.0.1, already published to registry `crates-io`
+[PACKAGING] level1 v0.0.1 ([ROOT]/foo/level1)
+[UPDATING] crates.io index
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[UPLOADING] level1 v0.0.1 ([ROOT]/foo/level1)
+[UPLOADED] level1 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level1 v0.0.1 to be available at registry `crates-io`
+[HELP] you may press ctrl-c to skip waiting; the crate should be available shortly
+[PUBLISHED] level1 v0.0.1 at registry `crates-io`
+
+"#]])
+        .run();
+    assert!(!state.exists());
+
+    p.cargo("publish --no-verify --resume -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["publish-resume"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[SKIPPING] level1 v0.0.1, already published to registry `crates-io`
+[SKIPPING] level2 v0.0.1, already published to registry `crates-io`
+[SKIPPING] level3 v0.0.1, already published to registry `crates-io`
+[NOTE] all packages have already been published
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_resume_unconfirmed() {
+    // Time out waiting for the first package of the first run to be available.
+    let registry = RegistryBuilder::new()
+        .http_api()
+        .delayed_index_update(5)
+        .build();
+    let p = workspace_with_local_deps_project();
+    let state = p.root().join("target/package/publish-state.json");
+
+    p.cargo("publish --no-verify -Zunstable-options -Zpublish-timeout --config publish.timeout=1")
+        .masquerade_as_nightly_cargo(&["publish-resume", "publish-timeout"])
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[PACKAGING] level3 v0.0.1 ([ROOT]/foo/level3)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level2 v0.0.1 ([ROOT]/foo/level2)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level1 v0.0.1 ([ROOT]/foo/level1)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[UPLOADING] level3 v0.0.1 ([ROOT]/foo/level3)
+[UPLOADED] level3 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level3 v0.0.1 to be available at registry `crates-io`.
+      2 remaining crates to be published
+[WARNING] timed out waiting for level3 v0.0.1 to be available in registry `crates-io`
+  |
+  = [NOTE] the registry may have a backlog that is delaying making the crate available. The crate should be available soon.
+[NOTE] publishing was interrupted
+        uploaded, not yet available: level3 v0.0.1
+        not yet uploaded: level2 v0.0.1, level1 v0.0.1
+  |
+  = [HELP] to publish the remaining packages, run `cargo publish --resume -Zunstable-options`
+[ERROR] unable to publish level1 v0.0.1 and level2 v0.0.1 due to a timeout while waiting for published dependencies to be available.
+
+"#]])
+        .run();
+    assert!(state.exists());
+
+    // The uploaded package is only waited on, without being uploaded again.
+    p.cargo(
+        "publish --no-verify --resume -Zunstable-options -Zpublish-timeout --config publish.timeout=20",
+    )
+    .masquerade_as_nightly_cargo(&["publish-resume", "publish-timeout"])
+    .replace_crates_io(registry.index_url())
+    .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[PACKAGING] level3 v0.0.1 ([ROOT]/foo/level3)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level2 v0.0.1 ([ROOT]/foo/level2)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level1 v0.0.1 ([ROOT]/foo/level1)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[NOTE] waiting for level3 v0.0.1 to be available at registry `crates-io`.
+      2 remaining crates to be published
+[PUBLISHED] level3 v0.0.1 at registry `crates-io`
+[UPLOADING] level2 v0.0.1 ([ROOT]/foo/level2)
+[UPLOADED] level2 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level2 v0.0.1 to be available at registry `crates-io`.
+      1 remaining crate to be published
+[PUBLISHED] level2 v0.0.1 at registry `crates-io`
+[UPLOADING] level1 v0.0.1 ([ROOT]/foo/level1)
+[UPLOADED] level1 v0.0.1 to registry `crates-io`
+[NOTE] waiting for level1 v0.0.1 to be available at registry `crates-io`
+[HELP] you may press ctrl-c to skip waiting; the crate should be available shortly
+[PUBLISHED] level1 v0.0.1 at registry `crates-io`
+
+"#]])
+    .run();
+    assert!(!state.exists());
+}
+
+#[cargo_test]
+fn workspace_verify_index_requires_unstable_options() {
+    let registry = RegistryBuilder::new().http_api().http_index().build();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish --dry-run --verify-index")
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--verify-index` flag is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_verify_index() {
+    let registry = RegistryBuilder::new().http_api().http_index().build();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish --dry-run --no-verify --verify-index -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["publish-verify-index"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[PACKAGING] level3 v0.0.1 ([ROOT]/foo/level3)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level2 v0.0.1 ([ROOT]/foo/level2)
+[UPDATING] crates.io index
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] level1 v0.0.1 ([ROOT]/foo/level1)
+[UPDATING] crates.io index
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[VALIDATING] level1 v0.0.1 against the index
+[UPDATING] crates.io index
+[VALIDATING] level2 v0.0.1 against the index
+[VALIDATING] level3 v0.0.1 against the index
+[UPLOADING] level3 v0.0.1 ([ROOT]/foo/level3)
+[WARNING] aborting upload due to dry run
+[UPLOADING] level2 v0.0.1 ([ROOT]/foo/level2)
+[WARNING] aborting upload due to dry run
+[UPLOADING] level1 v0.0.1 ([ROOT]/foo/level1)
+[WARNING] aborting upload due to dry run
+
+"#]])
+        .run();
+    assert!(!paths::cargo_home().join("registry/src").exists());
+}
+
+#[cargo_test]
+fn workspace_verify_index_yanked_dependency() {
+    // The lockfile keeps the yanked version, but a dependent of the published
+    // package couldn't select it.
+    let registry = RegistryBuilder::new().http_api().http_index().build();
+    Package::new("dep", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [workspace]
+            members = ["a", "b"]
+        "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+            [package]
+            name = "a"
+            version = "0.0.1"
+            edition = "2015"
+            license = "MIT"
+            description = "a"
+            repository = "bar"
+
+            [dependencies]
+            b = { path = "../b", version = "0.0.1" }
+        "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+            [package]
+            name = "b"
+            version = "0.0.1"
+            edition = "2015"
+            license = "MIT"
+            description = "b"
+            repository = "bar"
+
+            [dependencies]
+            dep = "1.0.0"
+        "#,
+        )
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile")
+        .replace_crates_io(registry.index_url())
+        .run();
+    // Yank it in place, as `Package::yanked` would add a second index entry.
+    let index = registry::registry_path().join("3/d/dep");
+    let yanked = fs::read_to_string(&index)
+        .unwrap()
+        .replace(r#""yanked":false"#, r#""yanked":true"#);
+    fs::write(&index, yanked).unwrap();
+
+    p.cargo("publish --dry-run --no-verify --verify-index -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["publish-verify-index"])
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[PACKAGING] b v0.0.1 ([ROOT]/foo/b)
+[UPDATING] crates.io index
+[WARNING] package `dep v1.0.0` in Cargo.lock is yanked in registry `crates-io`
+  |
+  = [HELP] consider updating to a version that is not yanked
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[PACKAGING] a v0.0.1 ([ROOT]/foo/a)
+[UPDATING] crates.io index
+[WARNING] package `dep v1.0.0` in Cargo.lock is yanked in registry `crates-io`
+  |
+  = [HELP] consider updating to a version that is not yanked
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[VALIDATING] a v0.0.1 against the index
+[UPDATING] crates.io index
+[ERROR] failed to resolve a v0.0.1 from the index of the packages to publish
+
+Caused by:
+  failed to select a version for the requirement `dep = "^1.0.0"`
+    version 1.0.0 is yanked
+  location searched: crates.io index
+  required by package `b v0.0.1`
+      ... which satisfies dependency `b = "^0.0.1"` of package `a v0.0.1`
+      ... which satisfies dependency `a = "=0.0.1"` of package `verify-index v0.0.0 ([ROOT]/foo)`
+
+"#]])
+        .run();
+}
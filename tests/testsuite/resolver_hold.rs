@@ -0,0 +1,177 @@
+//! Tests for the `-Zresolver-hold` feature.
+
+use cargo_test_support::registry::Package;
+use cargo_test_support::{Project, project, str};
+
+use crate::prelude::*;
+
+fn make_project(hold: &str) -> Project {
+    Package::new("bar", "1.38.0").publish();
+    Package::new("bar", "1.38.2").publish();
+    Package::new("bar", "1.40.0").publish();
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                edition = "2021"
+
+                [dependencies]
+                bar = "1.38"
+            "#,
+        )
+        .file(
+            ".cargo/config.toml",
+            &format!(
+                r#"
+                    [resolver]
+                    hold = {{ bar = "{hold}" }}
+                "#
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+fn assert_locked_bar(p: &Project, version: &str) {
+    let lock = p.read_lockfile();
+    assert!(
+        lock.contains(&format!("name = \"bar\"\nversion = \"{version}\"")),
+        "expected bar {version} in:\n{lock}"
+    );
+}
+
+#[cargo_test]
+fn feature_gated() {
+    let p = make_project("~1.38");
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[WARNING] ignoring `resolver.hold` without `-Zresolver-hold`
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.40.0");
+}
+
+#[cargo_test]
+fn hold_constrains_resolution() {
+    let p = make_project("~1.38");
+
+    p.cargo("generate-lockfile -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.38.2");
+}
+
+#[cargo_test]
+fn update_reports_held_versions() {
+    let p = make_project("~1.38");
+    p.cargo("update bar --precise 1.38.0 -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .run();
+    assert_locked_bar(&p, "1.38.0");
+
+    p.cargo("update -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] bar v1.38.0 -> v1.38.2
+[NOTE] `resolver.hold` keeps `bar` at `~1.38`, v1.40.0 is available
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.38.2");
+
+    // Nothing left to update within the hold, but it is still reported.
+    p.cargo("update -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 0 packages to latest compatible versions
+[NOTE] `resolver.hold` keeps `bar` at `~1.38`, v1.40.0 is available
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn locked_versions_are_kept() {
+    let p = make_project("~1.38");
+    p.cargo("generate-lockfile").run();
+    assert_locked_bar(&p, "1.40.0");
+
+    // Building doesn't move locked versions into the hold,
+    p.cargo("check -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v1.40.0 (registry `dummy-registry`)
+[CHECKING] bar v1.40.0
+[CHECKING] foo v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.40.0");
+
+    // but updating does.
+    p.cargo("update -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[DOWNGRADING] bar v1.40.0 -> v1.38.2
+[NOTE] `resolver.hold` keeps `bar` at `~1.38`, v1.40.0 is available
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.38.2");
+}
+
+#[cargo_test]
+fn precise_outside_of_hold() {
+    let p = make_project("~1.38");
+    p.cargo("generate-lockfile -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .run();
+
+    p.cargo("update bar --precise 1.40.0 -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for the requirement `bar = "^1.38"`
+  version 1.40.0 is held back by `resolver.hold` (`~1.38`)
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.0.0 ([ROOT]/foo)`
+[HELP] to use newer versions, update `resolver.hold.bar` in your config
+
+"#]])
+        .run();
+    assert_locked_bar(&p, "1.38.2");
+}
+
+#[cargo_test]
+fn invalid_hold() {
+    let p = make_project("one point three eight");
+
+    p.cargo("generate-lockfile -Zresolver-hold")
+        .masquerade_as_nightly_cargo(&["resolver-hold"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] invalid value for `resolver.hold.bar`: unexpected character 'o' while parsing major version number
+
+"#]])
+        .run();
+}
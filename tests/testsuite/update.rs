@@ -2169,6 +2169,108 @@ fn update_breaking_dry_run() {
     assert_e2e().eq(&lock_file_after, lock_file);
 }
 
+#[cargo_test]
+fn update_breaking_report() {
+    Package::new("incompatible", "1.0.0").publish();
+    Package::new("ws", "1.0.0").publish();
+    Package::new("duplicate", "1.0.0").publish();
+    Package::new("duplicate", "2.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["foo", "bar"]
+
+                [workspace.dependencies]
+                ws = "1.0"
+            "#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                edition = "2015"
+
+                [dependencies]
+                incompatible = "1.0"
+                duplicate = "1.0"
+                ws.workspace = true
+            "#,
+        )
+        .file("foo/src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+                [package]
+                name = "bar"
+                edition = "2015"
+
+                [dependencies]
+                duplicate = "2.0"
+
+                [dev-dependencies]
+                ws.workspace = true
+            "#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    let lock_file = p.read_file("Cargo.lock");
+
+    Package::new("incompatible", "2.0.0").publish();
+    Package::new("ws", "2.0.0").publish();
+    Package::new("duplicate", "3.0.0").publish();
+
+    p.cargo("update -Zunstable-options --breaking --report --dry-run")
+        .masquerade_as_nightly_cargo(&["update-breaking"])
+        .with_stdout_data(str![[r#"
+duplicate v1.0.0, v2.0.0 -> v3.0.0 (unifies 2 versions)
+  bar: ^2.0 -> ^3.0
+  foo: ^1.0 -> ^3.0
+incompatible v1.0.0 -> v2.0.0
+  foo: ^1.0 -> ^2.0
+ws v1.0.0 -> v2.0.0
+  bar: ^1.0 -> ^2.0 (from `workspace.dependencies`)
+  foo: ^1.0 -> ^2.0 (from `workspace.dependencies`)
+
+"#]])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[UPGRADING] duplicate ^2.0 -> ^3.0
+[UPGRADING] ws ^1.0 -> ^2.0
+[UPGRADING] duplicate ^1.0 -> ^3.0
+[UPGRADING] incompatible ^1.0 -> ^2.0
+[LOCKING] 3 packages to latest compatible versions
+[ADDING] duplicate v3.0.0
+[UPDATING] incompatible v1.0.0 -> v2.0.0
+[UPDATING] ws v1.0.0 -> v2.0.0
+[WARNING] aborting update due to dry run
+
+"#]])
+        .run();
+    assert_e2e().eq(p.read_file("Cargo.lock"), lock_file);
+
+    p.cargo("update -Zunstable-options --breaking --report")
+        .masquerade_as_nightly_cargo(&["update-breaking"])
+        .with_stdout_data(str![[r#"
+duplicate v1.0.0, v2.0.0 -> v3.0.0 (unifies 2 versions)
+  bar: ^2.0 -> ^3.0
+  foo: ^1.0 -> ^3.0
+incompatible v1.0.0 -> v2.0.0
+  foo: ^1.0 -> ^2.0
+ws v1.0.0 -> v2.0.0
+  bar: ^1.0 -> ^2.0 (from `workspace.dependencies`)
+  foo: ^1.0 -> ^2.0 (from `workspace.dependencies`)
+
+"#]])
+        .run();
+    assert!(p.read_file("Cargo.toml").contains(r#"ws = "2.0""#));
+}
+
 #[cargo_test]
 fn update_breaking() {
     registry::alt_init();
@@ -3354,3 +3456,252 @@ fn update_breaking_missing_package_error() {
 "#]])
         .run();
 }
+
+fn plan_project() -> cargo_test_support::Project {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1"
+                baz = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile").run();
+    Package::new("bar", "0.1.1")
+        .pubtime("2025-01-01T06:00:00Z")
+        .publish();
+    Package::new("baz", "0.1.1").publish();
+    p
+}
+
+#[cargo_test]
+fn plan_requires_unstable_options() {
+    let p = plan_project();
+    p.cargo("update --plan plan.json")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--plan` flag is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn plan_writes_changes() {
+    let p = plan_project();
+    let lockfile = p.read_lockfile();
+    p.cargo("update -Zunstable-options --plan plan.json")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[UPDATING] bar v0.1.0 -> v0.1.1
+[UPDATING] baz v0.1.0 -> v0.1.1
+[NOTE] not updating lockfile, wrote the changes to `[ROOT]/foo/plan.json`
+
+"#]])
+        .run();
+    assert_eq!(lockfile, p.read_lockfile());
+    assert_e2e().eq(
+        p.read_file("plan.json"),
+        str![[r#"
+{
+  "changes": [
+    {
+      "from": "0.1.0",
+      "kind": "upgraded",
+      "name": "bar",
+      "published": "2025-01-01T06:00:00Z",
+      "semver_compatible": true,
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "0.1.1"
+    },
+    {
+      "from": "0.1.0",
+      "kind": "upgraded",
+      "name": "baz",
+      "semver_compatible": true,
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "0.1.1"
+    }
+  ],
+  "version": 1
+}
+"#]]
+        .is_json(),
+    );
+}
+
+#[cargo_test]
+fn apply_plan_subset() {
+    let p = plan_project();
+    p.cargo("update -Zunstable-options --plan plan.json")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .run();
+    p.cargo("update -Zunstable-options --apply plan.json bar")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[UPDATING] bar v0.1.0 -> v0.1.1
+[NOTE] pass `--verbose` to see 1 unchanged dependencies behind latest
+
+"#]])
+        .run();
+    let lockfile = p.read_lockfile();
+    assert!(lockfile.contains("name = \"bar\"\nversion = \"0.1.1\""));
+    assert!(lockfile.contains("name = \"baz\"\nversion = \"0.1.0\""));
+
+    // Newer versions published since don't change what is applied.
+    Package::new("baz", "0.1.2").publish();
+    p.cargo("update -Zunstable-options --apply plan.json")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[UPDATING] baz v0.1.0 -> v0.1.1
+
+"#]])
+        .run();
+    let lockfile = p.read_lockfile();
+    assert!(lockfile.contains("name = \"bar\"\nversion = \"0.1.1\""));
+    assert!(lockfile.contains("name = \"baz\"\nversion = \"0.1.1\""));
+
+    p.cargo("update -Zunstable-options --apply plan.json qux")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] package ID specification `qux` did not match any change in `[ROOT]/foo/plan.json`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn apply_outdated_plan() {
+    let p = plan_project();
+    p.cargo("update -Zunstable-options --plan plan.json")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .run();
+    p.cargo("update bar --precise 0.1.1").run();
+    Package::new("bar", "0.1.2").publish();
+    p.cargo("update bar").run();
+    p.cargo("update -Zunstable-options --apply plan.json bar")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `bar v0.1.0` is not in the lockfile
+[HELP] the lockfile changed since `[ROOT]/foo/plan.json` was written
+
+"#]])
+        .run();
+}
+
+fn dedupe_project() -> cargo_test_support::Project {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("qux", "1.0.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = ">=1.0, <3"
+                qux = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    // `baz` is added later and requires newer versions, duplicating them.
+    Package::new("bar", "2.0.0").publish();
+    Package::new("qux", "2.0.0").publish();
+    Package::new("baz", "1.0.0")
+        .dep("bar", "2.0")
+        .dep("qux", "2.0")
+        .publish();
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            edition = "2015"
+
+            [dependencies]
+            bar = ">=1.0, <3"
+            qux = "1.0"
+            baz = "1.0"
+        "#,
+    );
+    p.cargo("fetch").run();
+    p
+}
+
+#[cargo_test]
+fn dedupe_requires_unstable_options() {
+    let p = dedupe_project();
+    p.cargo("update --dedupe")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--dedupe` flag is unstable, pass `-Z unstable-options` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn dedupe() {
+    let p = dedupe_project();
+    let lockfile = p.read_lockfile();
+
+    p.cargo("update -Zunstable-options --dedupe --dry-run")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[REMOVING] bar v1.0.0
+[NOTE] pass `--verbose` to see 1 unchanged dependencies behind latest
+[NOTE] cannot unify `qux` v1.0.0, v2.0.0 into v2.0.0, blocked by
+        foo v0.1.0 ([ROOT]/foo) requiring `^1.0`
+[WARNING] not updating lockfile due to dry run
+
+"#]])
+        .run();
+    assert_e2e().eq(p.read_lockfile(), lockfile);
+
+    p.cargo("update -Zunstable-options --dedupe")
+        .masquerade_as_nightly_cargo(&["unstable-options"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[REMOVING] bar v1.0.0
+[NOTE] pass `--verbose` to see 1 unchanged dependencies behind latest
+[NOTE] cannot unify `qux` v1.0.0, v2.0.0 into v2.0.0, blocked by
+        foo v0.1.0 ([ROOT]/foo) requiring `^1.0`
+
+"#]])
+        .run();
+    p.cargo("tree --duplicates")
+        .with_stdout_data(str![[r#"
+qux v1.0.0
+└── foo v0.1.0 ([ROOT]/foo)
+
+qux v2.0.0
+└── baz v1.0.0
+    └── foo v0.1.0 ([ROOT]/foo)
+
+"#]])
+        .run();
+}
@@ -268,7 +268,7 @@ fn lint_parse_pass() {
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] `foo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 
 "#]])
         .run();
@@ -285,7 +285,7 @@ fn lint_parse_pass() {
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] `foo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 [CHECKING] foo v0.1.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 
@@ -324,7 +324,7 @@ fn lint_parse_pass() {
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] `foo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 [ERROR] warnings are denied by `build.warnings` configuration
 
 "#]])
@@ -345,7 +345,7 @@ fn lint_parse_pass() {
   |
   = [NOTE] `cargo::redundant_homepage` is set to `warn` in `[lints]`
 [HELP] consider removing `package.homepage`
-[WARNING] `foo` (manifest) generated 1 warning
+[WARNING] `foo` (manifest) generated 1 warning (run `cargo fix` to apply 1 suggestion)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [ERROR] warnings are denied by `build.warnings` configuration
 